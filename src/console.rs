@@ -1,5 +1,7 @@
 use std::io::{self};
+#[cfg(windows)]
 use windows::Win32::System::Console::AllocConsole;
+#[cfg(windows)]
 pub fn allocate_console() -> Result<(), Box<dyn std::error::Error>> {
     unsafe {
         AllocConsole()?;
@@ -7,6 +9,12 @@ pub fn allocate_console() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+// processes elsewhere keep the terminal they were started from
+#[cfg(not(windows))]
+pub fn allocate_console() -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
+}
+
 pub fn wait_line() {
     let mut input = String::new();
     let _ = io::stdin().read_line(&mut input);
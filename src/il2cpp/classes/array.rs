@@ -1,4 +1,17 @@
-use crate::{il2cpp::classes::object::ObjectInner, il2cpp_view_generic};
+use crate::{
+    il2cpp::{
+        array_element_size, array_new, class_get_element_class, class_get_full_name,
+        class_get_rank, class_get_type, class_is_assignable_from, class_is_enum,
+        class_is_valuetype, class_value_size,
+        classes::itype::TypeKind,
+        classes::{class::ClassInner, il2cpp_view::Il2CppView, object::ObjectInner},
+        gc_wbarrier_set_field,
+        il2cpp_sys::c_types::Il2CppClass,
+        pod::Pod,
+        probe, type_get_type,
+    },
+    il2cpp_view_generic,
+};
 use std::{marker::PhantomData, slice};
 
 #[repr(C)]
@@ -10,17 +23,18 @@ pub struct Il2CppArrayBounds {
 il2cpp_view_generic! {
     pub struct Il2CppArray<T> {
         pub obj: ObjectInner,
+        // null for single dimension, zero based arrays
         pub bounds: *mut Il2CppArrayBounds,
-        pub max_length: u32,
+        pub max_length: usize,
         _phantom: PhantomData<T>,
     }
 }
 
 impl<'a, T> Il2CppArrayView<'a, T> {
-    /// Number of elements
+    /// Number of elements, 0 for an unreadable array with checked reads enabled
     #[inline(always)]
     pub fn len(&self) -> usize {
-        unsafe { (self.ptr.as_ref()).max_length as usize }
+        self.checked_ref().map_or(0, |header| header.max_length)
     }
 
     #[inline(always)]
@@ -28,19 +42,104 @@ impl<'a, T> Il2CppArrayView<'a, T> {
         self.len() == 0
     }
 
-    /// Immutable reference to element at index
+    /// Immutable reference to element at index. With checked reads enabled an unreadable
+    /// array is `None` as well.
     pub fn at(&self, index: usize) -> Option<&'a T> {
-        if index >= self.len() {
+        if index >= self.checked_ref().ok()?.max_length {
             return None;
         }
-        unsafe { Some(&*self.data_ptr().add(index)) }
+        let element = unsafe { self.data_ptr().add(index) };
+        if probe::checked_reads() && !probe::is_readable(element as usize, std::mem::size_of::<T>())
+        {
+            return None;
+        }
+        unsafe { Some(&*element) }
+    }
+
+    /// Element at `indices` of a multi-dimensional array, honoring lower bounds
+    pub fn at_multi(&self, indices: &[isize]) -> Option<&'a T> {
+        let index = self.flat_index(indices).ok()?;
+        self.at(index)
+    }
+
+    /// Number of dimensions
+    pub fn rank(&self) -> Result<usize, String> {
+        let header = self.checked_ref()?;
+        if header.bounds.is_null() {
+            return Ok(1);
+        }
+        Ok(class_get_rank(header.obj.klass)?.max(1) as usize)
+    }
+
+    /// Per dimension length and lower bound, empty for single dimension arrays
+    pub fn bounds(&self) -> Result<&'a [Il2CppArrayBounds], String> {
+        let bounds = self.checked_ref()?.bounds;
+        if bounds.is_null() {
+            return Ok(&[]);
+        }
+        let rank = self.rank()?;
+        if probe::checked_reads() {
+            probe::check_readable(
+                bounds as usize,
+                rank * std::mem::size_of::<Il2CppArrayBounds>(),
+            )?;
+        }
+        Ok(unsafe { slice::from_raw_parts(bounds, rank) })
     }
 
-    /// Slice over the array (immutable)
+    /// Row-major index into the element storage
+    fn flat_index(&self, indices: &[isize]) -> Result<usize, String> {
+        let bounds = self.bounds()?;
+        if bounds.is_empty() {
+            return match indices {
+                [index] if *index >= 0 => Ok(*index as usize),
+                [index] => Err(format!("Index {} out of bounds", index)),
+                _ => Err(format!("Expected 1 index, got {}", indices.len())),
+            };
+        }
+        if indices.len() != bounds.len() {
+            return Err(format!(
+                "Expected {} indices, got {}",
+                bounds.len(),
+                indices.len()
+            ));
+        }
+
+        let mut flat = 0usize;
+        for (index, bound) in indices.iter().zip(bounds) {
+            let relative = index - bound.lower_bound as isize;
+            if relative < 0 || relative as usize >= bound.length {
+                return Err(format!("Index {} out of bounds", index));
+            }
+            flat = flat * bound.length + relative as usize;
+        }
+        Ok(flat)
+    }
+
+    /// Class of the elements, read from the array's own class
+    pub fn element_class(&self) -> Result<Il2CppClass, String> {
+        class_get_element_class(self.checked_ref()?.obj.klass)
+    }
+
+    /// Slice over the array (immutable). With checked reads enabled an array that is not
+    /// entirely readable is empty.
     pub fn as_slice(&self) -> &'a [T] {
+        if probe::checked_reads() {
+            return self.try_as_slice().unwrap_or(&[]);
+        }
         unsafe { slice::from_raw_parts(self.data_ptr(), self.len()) }
     }
 
+    /// `as_slice` that first checks the header and every element are readable memory
+    pub fn try_as_slice(&self) -> Result<&'a [T], String> {
+        let len = self.try_as_ref()?.max_length;
+        let size = len
+            .checked_mul(std::mem::size_of::<T>())
+            .ok_or_else(|| format!("Array at {:p} has length {}", self.as_ptr(), len))?;
+        probe::check_readable(unsafe { self.data_ptr() } as usize, size)?;
+        Ok(unsafe { slice::from_raw_parts(self.data_ptr(), len) })
+    }
+
     /// Iterator
     pub fn iter(&self) -> slice::Iter<'a, T> {
         self.as_slice().iter()
@@ -62,6 +161,189 @@ impl<'a, T> Il2CppArrayView<'a, T> {
     }
 }
 
+impl<'a, T: ElementType> Il2CppArrayView<'a, T> {
+    /// Allocate a new single dimension array of `len` elements of `element_class`
+    pub fn new(element_class: &ClassInner, len: usize) -> Result<Self, String> {
+        T::check_element_class(element_class.address)?;
+        let ptr = array_new(element_class.address, len)?;
+        Self::from_ptr(ptr as *mut Il2CppArrayInner<T>)
+            .ok_or_else(|| format!("Failed to allocate {}[{}]", element_class.name, len))
+    }
+
+    /// Store `value` at `index`. Reference elements must be instances of the element
+    /// class and go through the GC write barrier when the runtime exports one, so the
+    /// incremental GC sees the new reference.
+    pub fn set(&self, index: usize, value: T) -> Result<(), String> {
+        let len = self.checked_ref()?.max_length;
+        if index >= len {
+            return Err(format!(
+                "Index {} out of bounds for array of length {}",
+                index, len
+            ));
+        }
+        unsafe {
+            let slot = (self.data_ptr() as *mut T).add(index);
+            let element_class = self.element_class()?;
+            if std::mem::size_of::<T>() == std::mem::size_of::<*mut u8>()
+                && !class_is_valuetype(element_class)?
+            {
+                let object: *mut u8 = std::mem::transmute_copy(&value);
+                check_assignable(element_class, object as *mut ObjectInner)?;
+                if gc_wbarrier_set_field(self.as_il2cpp_object() as *mut u8, slot as _, object)
+                    .is_ok()
+                {
+                    return Ok(());
+                }
+            }
+            std::ptr::write(slot, value);
+        }
+        Ok(())
+    }
+
+    /// Store `value` at `indices` of a multi-dimensional array, see `set`
+    pub fn set_multi(&self, indices: &[isize], value: T) -> Result<(), String> {
+        let index = self.flat_index(indices)?;
+        self.set(index, value)
+    }
+
+    /// Check that the array's elements are what `T` expects: the same primitive, a
+    /// reference for pointers, or a value type of `T`'s size
+    pub fn verify_element_type(&self) -> Result<(), String> {
+        let element_class = self.element_class()?;
+        T::check_element_class(element_class)?;
+        let element_size = array_element_size(self.checked_ref()?.obj.klass)?;
+        if element_size as usize != std::mem::size_of::<T>() {
+            return Err(format!(
+                "Array elements of {} are {} bytes, not {}",
+                class_get_full_name(element_class)?,
+                element_size,
+                std::mem::size_of::<T>()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checked `from_ptr`, rejects arrays whose elements do not fit `T`
+    pub fn from_ptr_checked(ptr: *mut Il2CppArrayInner<T>) -> Result<Self, String> {
+        let view = Self::from_ptr(ptr).ok_or_else(|| "Array pointer is null".to_string())?;
+        view.verify_element_type()?;
+        Ok(view)
+    }
+}
+
+/// A Rust type that can stand in for the elements of a managed array (or a boxed value).
+/// Structs mirroring a managed value type opt in with an empty impl, which checks for a
+/// value type of the same size. Managed `bool` elements are read as `u8`.
+///
+/// # Safety
+/// Managed bytes are reinterpreted as `Self`, so on top of the `Pod` rules a struct must
+/// mirror the managed layout field for field.
+pub unsafe trait ElementType: Pod {
+    fn check_element_class(element_class: Il2CppClass) -> Result<(), String> {
+        check_value_type_size::<Self>(element_class)
+    }
+}
+
+macro_rules! impl_element_primitive {
+    ($($ty:ty => $($kind:ident)|+),* $(,)?) => {
+        $(
+            unsafe impl ElementType for $ty {
+                fn check_element_class(element_class: Il2CppClass) -> Result<(), String> {
+                    // enums are stored as their underlying integer
+                    if class_is_enum(element_class)? {
+                        return check_value_type_size::<Self>(element_class);
+                    }
+                    let kind = TypeKind::from_raw(type_get_type(class_get_type(element_class)?)?);
+                    if !matches!(kind, $(TypeKind::$kind)|+) {
+                        return Err(format!(
+                            "{} cannot be read as {}",
+                            class_get_full_name(element_class)?,
+                            stringify!($ty)
+                        ));
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_element_primitive! {
+    u16 => Char | U2,
+    i8 => I1,
+    u8 => U1 | Boolean,
+    i16 => I2,
+    i32 => I4,
+    u32 => U4,
+    i64 => I8,
+    u64 => U8,
+    f32 => R4,
+    f64 => R8,
+    isize => I,
+    usize => U,
+}
+
+// object references, or native pointers stored in IntPtr/UIntPtr/pointer slots
+unsafe impl<T: 'static> ElementType for *mut T {
+    fn check_element_class(element_class: Il2CppClass) -> Result<(), String> {
+        if !class_is_valuetype(element_class)? {
+            return Ok(());
+        }
+        let kind = TypeKind::from_raw(type_get_type(class_get_type(element_class)?)?);
+        if !matches!(kind, TypeKind::I | TypeKind::U | TypeKind::Ptr) {
+            return Err(format!(
+                "{} is a value type, not a reference",
+                class_get_full_name(element_class)?
+            ));
+        }
+        Ok(())
+    }
+}
+
+unsafe impl<T: 'static> ElementType for *const T {
+    fn check_element_class(element_class: Il2CppClass) -> Result<(), String> {
+        <*mut T>::check_element_class(element_class)
+    }
+}
+
+fn check_value_type_size<T>(element_class: Il2CppClass) -> Result<(), String> {
+    if !class_is_valuetype(element_class)? {
+        return Err(format!(
+            "{} is a reference type, not a value type",
+            class_get_full_name(element_class)?
+        ));
+    }
+    let size = class_value_size(element_class)? as usize;
+    if size != std::mem::size_of::<T>() {
+        return Err(format!(
+            "Elements of {} are {} bytes, not {}",
+            class_get_full_name(element_class)?,
+            size,
+            std::mem::size_of::<T>()
+        ));
+    }
+    Ok(())
+}
+
+// what a stelem would check: null, or an instance of the element class
+fn check_assignable(element_class: Il2CppClass, object: *mut ObjectInner) -> Result<(), String> {
+    if object.is_null() {
+        return Ok(());
+    }
+    if probe::checked_reads() {
+        probe::check_readable(object as usize, std::mem::size_of::<ObjectInner>())?;
+    }
+    let klass = unsafe { (*object).klass };
+    if !class_is_assignable_from(element_class, klass)? {
+        return Err(format!(
+            "Cannot store {} in an array of {}",
+            class_get_full_name(klass)?,
+            class_get_full_name(element_class)?
+        ));
+    }
+    Ok(())
+}
+
 impl<'a, T> IntoIterator for &'a Il2CppArrayView<'a, T> {
     type Item = &'a T;
     type IntoIter = std::slice::Iter<'a, T>;
@@ -71,6 +353,7 @@ impl<'a, T> IntoIterator for &'a Il2CppArrayView<'a, T> {
     }
 }
 // this will be handy for hooking , but needs to be tested at some point
+// note: writes through these references bypass the GC write barrier, prefer `set` for references
 impl<'a, T> IntoIterator for &'a mut Il2CppArrayView<'a, T> {
     type Item = &'a mut T;
     type IntoIter = std::slice::IterMut<'a, T>;
@@ -1,15 +1,47 @@
-use crate::il2cpp::classes::{field::Field, method::Method};
+use crate::il2cpp::classes::{
+    arg::Arg,
+    exception::{Il2CppException, InvokeError},
+    field::Field,
+    method::Method,
+    object::{ObjectInner, ObjectView},
+    value::Il2CppValue,
+};
 use crate::il2cpp::il2cpp_sys::c_types::Il2CppObject;
-use crate::il2cpp::{class_get_type, type_get_object};
+use crate::il2cpp::{
+    class_get_type, field_static_get_value, object_new, runtime_class_init, type_get_object,
+};
 use parking_lot::RwLock;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
+// Il2CppClass::static_fields in pointer-sized slots, the same from metadata 24 to 31
+const CLASS_STATIC_FIELDS_SLOT: usize = 23;
+// largest field compared when checking the static fields slot
+const MAX_CHECKED_STATIC_SIZE: isize = 64;
+
+// TypeAttributes from the ECMA-335 metadata tables
+pub const TYPE_ATTRIBUTE_VISIBILITY_MASK: i32 = 0x0007;
+pub const TYPE_ATTRIBUTE_PUBLIC: i32 = 0x0001;
+pub const TYPE_ATTRIBUTE_NESTED_PUBLIC: i32 = 0x0002;
+pub const TYPE_ATTRIBUTE_NESTED_PRIVATE: i32 = 0x0003;
+pub const TYPE_ATTRIBUTE_NESTED_FAMILY: i32 = 0x0004;
+pub const TYPE_ATTRIBUTE_NESTED_FAM_AND_ASSEM: i32 = 0x0006;
+pub const TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM: i32 = 0x0007;
+pub const TYPE_ATTRIBUTE_INTERFACE: i32 = 0x0020;
+pub const TYPE_ATTRIBUTE_ABSTRACT: i32 = 0x0080;
+pub const TYPE_ATTRIBUTE_SEALED: i32 = 0x0100;
+
 pub struct ClassInner {
     pub address: *mut u8,
     pub name: String,
     pub parent: String,
     pub namespace: String,
+    pub flags: i32,
+    pub value_type: bool,
+    pub enum_type: bool,
+    /// `Namespace.Name` of the enclosing class of a nested class, empty otherwise. Filled in
+    /// after construction, like the members.
+    pub declaring_type: RwLock<String>,
     pub fields: RwLock<Vec<Field>>,
     pub methods: RwLock<Vec<Method>>,
 }
@@ -17,17 +49,105 @@ pub struct ClassInner {
 pub type Class = Arc<ClassInner>;
 
 impl ClassInner {
-    pub fn new(address: *mut u8, name: String, parent: String, namespace: String) -> Class {
+    pub fn new(
+        address: *mut u8,
+        name: String,
+        parent: String,
+        namespace: String,
+        flags: i32,
+        value_type: bool,
+        enum_type: bool,
+    ) -> Class {
         Arc::new(Self {
             address,
             name,
             parent,
             namespace,
+            flags,
+            value_type,
+            enum_type,
+            declaring_type: RwLock::new(String::new()),
             fields: RwLock::new(Vec::new()),
             methods: RwLock::new(Vec::new()),
         })
     }
 
+    /// `Namespace.Name`, or just `Name` in the global namespace
+    pub fn full_name(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.namespace, self.name)
+        }
+    }
+
+    /// `Outer.Name` for nested classes, `full_name` otherwise. This is how il2cpp names the
+    /// type of a nested class, so fields and arguments of that type resolve to it.
+    pub fn qualified_name(&self) -> String {
+        let declaring_type = self.declaring_type.read();
+        if declaring_type.is_empty() {
+            self.full_name()
+        } else {
+            format!("{}.{}", declaring_type, self.name)
+        }
+    }
+
+    pub fn is_interface(&self) -> bool {
+        (self.flags & TYPE_ATTRIBUTE_INTERFACE) != 0
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        (self.flags & TYPE_ATTRIBUTE_ABSTRACT) != 0
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        (self.flags & TYPE_ATTRIBUTE_SEALED) != 0
+    }
+
+    /// Generic type definitions such as ``List`1``, whose fields are not laid out
+    pub fn is_generic_definition(&self) -> bool {
+        self.name.contains('`')
+    }
+
+    /// Start of the class's static field storage, `FieldInner.offset` of a static field is
+    /// relative to it. Runs the static constructor first. The pointer is read from the
+    /// `Il2CppClass` and checked against a static field read through il2cpp, so an
+    /// unexpected struct layout is an error rather than a wrong address.
+    pub fn static_field_data(&self) -> Result<*mut u8, String> {
+        if self.address.is_null() {
+            return Err(format!("{} is not a live class", self.full_name()));
+        }
+        runtime_class_init(self.address)?;
+        let data = unsafe { *(self.address as *const *mut u8).add(CLASS_STATIC_FIELDS_SLOT) };
+        if data.is_null() {
+            return Err(format!("{} has no static field storage", self.full_name()));
+        }
+
+        let fields = self.fields.read();
+        let probe = fields.iter().find(|f| {
+            f.static_field
+                && !f.thread_static
+                && !f.is_literal()
+                && f.offset >= 0
+                && (1..=MAX_CHECKED_STATIC_SIZE).contains(&f.itype.size)
+        });
+        if let Some(field) = probe {
+            let mut expected = vec![0u8; field.itype.size as usize];
+            field_static_get_value(field.address, expected.as_mut_ptr())?;
+            let stored = unsafe {
+                std::slice::from_raw_parts(data.add(field.offset as usize), expected.len())
+            };
+            if stored != expected.as_slice() {
+                return Err(format!(
+                    "Static storage of {} not found, the Il2CppClass layout of this Unity \
+                     version is not supported",
+                    self.full_name()
+                ));
+            }
+        }
+        Ok(data)
+    }
+
     pub fn get_field(&self, name: &str) -> Option<Field> {
         let guard = self.fields.read();
         guard.iter().find(|f| f.name == name).cloned()
@@ -38,27 +158,70 @@ impl ClassInner {
         let guard = self.methods.read();
         guard
             .iter()
-            .find(|m| {
-                if m.name.trim() == name.trim() {
-                    if m.args.read().is_empty() && args.is_empty() {
-                        return true;
-                    } else if m.args.read().len() == args.len() {
-                        for (arg, arg2) in m.args.read().iter().zip(args.iter()) {
-                            if arg.itype.name != arg2.to_string() {
-                                return false;
-                            }
-                        }
-                        return true;
-                    } else {
-                        return false;
-                    }
-                } else {
-                    false
-                }
-            })
+            .find(|m| m.name.trim() == name.trim() && args_match(&m.args.read(), &args))
             .cloned()
     }
 
+    /// Pick the `.ctor` for `args`: exact parameter type names first, then the single
+    /// constructor whose parameters accept the values (null objects, raw structs, subclasses)
+    pub fn find_constructor(&self, args: &[Il2CppValue]) -> Result<Method, String> {
+        let type_names: Option<Vec<String>> = args.iter().map(|a| a.type_name()).collect();
+        if let Some(names) = &type_names {
+            let names = names.iter().map(|n| n.as_str()).collect();
+            if let Some(ctor) = self.get_method_with_args(".ctor", names) {
+                return Ok(ctor);
+            }
+        }
+
+        let guard = self.methods.read();
+        let candidates: Vec<&Method> = guard
+            .iter()
+            .filter(|m| {
+                let params = m.args.read();
+                m.name == ".ctor"
+                    && params.len() == args.len()
+                    && params.iter().zip(args).all(|(p, v)| v.fits(&p.itype))
+            })
+            .collect();
+
+        match candidates.as_slice() {
+            [ctor] => Ok((*ctor).clone()),
+            [] => Err(format!(
+                "No constructor of {} accepts the given arguments",
+                self.name
+            )),
+            _ => Err(format!(
+                "Ambiguous constructor for {}: {} candidates",
+                self.name,
+                candidates.len()
+            )),
+        }
+    }
+
+    /// Allocate an instance without running any constructor, fields are zeroed
+    pub fn new_object<'a>(&self) -> Result<ObjectView<'a>, String> {
+        let obj = object_new(self.address)?;
+        ObjectView::from_ptr(obj as *mut ObjectInner)
+            .ok_or_else(|| format!("Failed to allocate {}", self.name))
+    }
+
+    /// Allocate an instance and run the `.ctor` matching `args`
+    pub fn construct(&self, args: &[Il2CppValue]) -> Result<ObjectView<'_>, InvokeError<'_>> {
+        let ctor = self.find_constructor(args)?;
+        let obj = self.new_object()?;
+        if let Err(e) = ctor.invoke(obj.as_il2cpp_object(), &mut args.to_vec()) {
+            return Err(match e {
+                // re-borrowed from the class, `ctor` is a clone dropped on return
+                InvokeError::Exception(exception) => InvokeError::Exception(
+                    Il2CppException::from_ptr(exception.as_ptr())
+                        .ok_or_else(|| "Null exception".to_string())?,
+                ),
+                InvokeError::Failed(e) => InvokeError::Failed(e),
+            });
+        }
+        Ok(obj)
+    }
+
     pub fn get_method(&self, name: &str) -> Option<Method> {
         let guard = self.methods.read();
         guard.iter().find(|m| m.name == name).cloned()
@@ -76,6 +239,7 @@ impl Debug for ClassInner {
         write!(f, "Name: {}\n", self.name)?;
         write!(f, "Parent: {}\n", self.parent)?;
         write!(f, "Namespace: {}\n", self.namespace)?;
+        write!(f, "Flags: {:#x}\n", self.flags)?;
         let fields = self.fields.read();
         write!(f, "Fields Len: {}\n", fields.len())?;
         for field in fields.iter() {
@@ -85,5 +249,13 @@ impl Debug for ClassInner {
     }
 }
 
+fn args_match(params: &[Arg], arg_types: &[&str]) -> bool {
+    params.len() == arg_types.len()
+        && params
+            .iter()
+            .zip(arg_types.iter())
+            .all(|(param, arg_type)| param.itype.name == *arg_type)
+}
+
 unsafe impl Send for ClassInner {}
 unsafe impl Sync for ClassInner {}
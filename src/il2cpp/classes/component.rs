@@ -1,6 +1,7 @@
 use crate::{il2cpp::classes::unity_object::UnityObjectInner, il2cpp_view};
 
 il2cpp_view! {
+    #[il2cpp(class = "UnityEngine.Component", assembly = "UnityEngine.CoreModule.dll")]
     pub struct Component {
         pub obj: UnityObjectInner,
     }
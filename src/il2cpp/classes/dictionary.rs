@@ -0,0 +1,173 @@
+use crate::{
+    il2cpp::classes::{
+        array::{Array, ArrayInner},
+        il2cpp_view::Il2CppView,
+        object::ObjectInner,
+        string::{UnityString, UnityStringInner},
+    },
+    il2cpp_view_generic,
+};
+
+// Dictionary<TKey, TValue>.Entry
+#[repr(C)]
+pub struct DictionaryEntry<K, V> {
+    // negative for free entries
+    pub hash_code: i32,
+    pub next: i32,
+    pub key: K,
+    pub value: V,
+}
+
+// System.Collections.Generic.Dictionary<TKey, TValue> as implemented by the Mono class libraries
+il2cpp_view_generic! {
+    pub struct Dictionary<K, V> {
+        pub obj: ObjectInner,
+        pub buckets: *mut ArrayInner<i32>,
+        pub entries: *mut ArrayInner<DictionaryEntry<K, V>>,
+        pub count: i32,
+        pub version: i32,
+        pub free_list: i32,
+        pub free_count: i32,
+        pub comparer: *mut ObjectInner,
+        pub keys: *mut ObjectInner,
+        pub values: *mut ObjectInner,
+        pub sync_root: *mut ObjectInner,
+    }
+}
+
+// Compares a stored collection key with a Rust lookup key
+pub trait KeyMatch<Q: ?Sized> {
+    fn key_matches(&self, key: &Q) -> bool;
+}
+
+macro_rules! impl_key_match_primitive {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl KeyMatch<$ty> for $ty {
+                fn key_matches(&self, key: &$ty) -> bool {
+                    self == key
+                }
+            }
+        )*
+    };
+}
+
+impl_key_match_primitive!(
+    bool, u16, i8, u8, i16, i32, u32, i64, u64, f32, f64, isize, usize
+);
+
+impl KeyMatch<str> for *mut UnityStringInner {
+    fn key_matches(&self, key: &str) -> bool {
+        UnityString::from_ptr(*self).is_some_and(|s| s == key)
+    }
+}
+
+impl<'a, K, V> DictionaryView<'a, K, V> {
+    /// Number of key/value pairs, 0 for an unreadable dictionary with checked reads enabled
+    pub fn len(&self) -> usize {
+        self.checked_ref().map_or(0, |dictionary| {
+            (dictionary.count - dictionary.free_count).max(0) as usize
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Used entries of `_entries`, free slots are skipped
+    pub fn entries(&self) -> impl Iterator<Item = &'a DictionaryEntry<K, V>> + 'a {
+        self.filled_entries()
+            .unwrap_or(&[])
+            .iter()
+            .filter(|entry| entry.hash_code >= 0)
+    }
+
+    // `_entries` up to `_count`, `None` without an array or for an unreadable dictionary
+    fn filled_entries(&self) -> Option<&'a [DictionaryEntry<K, V>]> {
+        let dictionary = self.checked_ref().ok()?;
+        let entries = Array::from_ptr(dictionary.entries)?.as_slice();
+        Some(&entries[..(dictionary.count.max(0) as usize).min(entries.len())])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.entries().map(|entry| (&entry.key, &entry.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &'a K> + 'a {
+        self.entries().map(|entry| &entry.key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &'a V> + 'a {
+        self.entries().map(|entry| &entry.value)
+    }
+
+    /// Value stored under `key`. Keys are compared one by one instead of hashed,
+    /// which keeps lookups independent of the runtime's hash functions.
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&'a V>
+    where
+        K: KeyMatch<Q>,
+    {
+        self.entries()
+            .find(|entry| entry.key.key_matches(key))
+            .map(|entry| &entry.value)
+    }
+
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: KeyMatch<Q>,
+    {
+        self.get(key).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::fixture;
+    use crate::il2cpp::probe::CheckedReads;
+
+    fn entry(hash_code: i32, key: i32, value: u64) -> DictionaryEntry<i32, u64> {
+        DictionaryEntry {
+            hash_code,
+            next: -1,
+            key,
+            value,
+        }
+    }
+
+    #[test]
+    fn free_entries_and_entries_past_count_are_skipped() {
+        let _reads = CheckedReads::set(false);
+        let mut entries = fixture::array(vec![
+            entry(1, 1, 100),
+            // removed, still holding its old key
+            entry(-1, 2, 200),
+            entry(3, 3, 300),
+            // capacity past `_count`, never used
+            entry(4, 4, 400),
+        ]);
+        let inner = DictionaryInner {
+            obj: fixture::header(),
+            buckets: std::ptr::null_mut(),
+            entries: entries.as_mut_ptr() as *mut ArrayInner<DictionaryEntry<i32, u64>>,
+            count: 3,
+            version: 0,
+            free_list: 1,
+            free_count: 1,
+            comparer: std::ptr::null_mut(),
+            keys: std::ptr::null_mut(),
+            values: std::ptr::null_mut(),
+            sync_root: std::ptr::null_mut(),
+        };
+        let dictionary = DictionaryView::from_ref(&inner);
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(
+            dictionary.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            vec![(1, 100), (3, 300)]
+        );
+        assert_eq!(dictionary.get(&3), Some(&300));
+        assert!(!dictionary.contains_key(&2));
+        assert!(!dictionary.contains_key(&4));
+    }
+}
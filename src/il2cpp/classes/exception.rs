@@ -0,0 +1,131 @@
+use crate::il2cpp::classes::il2cpp_view::Il2CppView;
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp::classes::string::{UnityString, UnityStringInner};
+use crate::il2cpp::{class_get_full_name, format_exception, format_stack_trace};
+use crate::il2cpp_view;
+use std::fmt::{Debug, Display, Formatter};
+
+// System.Exception as laid out by il2cpp for the .NET 4.x profile
+il2cpp_view! {
+    #[il2cpp(class = "System.Exception", assembly = "mscorlib.dll")]
+    pub struct Il2CppException {
+        pub obj: ObjectInner,
+        pub class_name: *mut UnityStringInner,
+        pub message: *mut UnityStringInner,
+        pub data: *mut ObjectInner,
+        pub inner_ex: *mut Il2CppExceptionInner,
+        pub help_url: *mut UnityStringInner,
+        pub trace_ips: *mut ObjectInner,
+        pub stack_trace: *mut UnityStringInner,
+        pub remote_stack_trace: *mut UnityStringInner,
+        pub remote_stack_index: i32,
+        pub dynamic_methods: *mut ObjectInner,
+        pub hresult: i32,
+        pub source: *mut UnityStringInner,
+        pub safe_serialization_manager: *mut ObjectInner,
+        pub captured_traces: *mut ObjectInner,
+        pub native_trace_ips: *mut ObjectInner,
+        pub caught_in_unmanaged: i32,
+    }
+}
+
+impl<'a> Il2CppExceptionView<'a> {
+    /// Full name of the exception class, e.g. `System.NullReferenceException`
+    pub fn type_name(&self) -> String {
+        self.checked_ref()
+            .and_then(|exception| class_get_full_name(exception.obj.klass))
+            .unwrap_or_default()
+    }
+
+    /// The `Message` of the exception, if any
+    pub fn message(&self) -> Option<String> {
+        UnityString::from_ptr(self.checked_ref().ok()?.message).map(|s| s.to_string())
+    }
+
+    /// Managed stack trace as formatted by il2cpp
+    pub fn stack_trace(&self) -> String {
+        self.checked_ref()
+            .and_then(|_| format_stack_trace(self.as_ptr() as *mut u8))
+            .unwrap_or_default()
+    }
+
+    /// `Type: Message` line as formatted by il2cpp
+    pub fn formatted(&self) -> String {
+        self.checked_ref()
+            .and_then(|_| format_exception(self.as_ptr() as *mut u8))
+            .unwrap_or_else(|_| self.type_name())
+    }
+
+    pub fn inner_exception(&self) -> Option<Il2CppException<'a>> {
+        Il2CppExceptionView::from_ptr(self.checked_ref().ok()?.inner_ex)
+    }
+}
+
+impl Debug for Il2CppExceptionView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Il2CppException")
+            .field("address", &self.as_ptr())
+            .field("type", &self.type_name())
+            .field("message", &self.message())
+            .field("stack_trace", &self.stack_trace())
+            .field("inner", &self.inner_exception())
+            .finish()
+    }
+}
+
+impl Display for Il2CppExceptionView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.formatted())
+    }
+}
+
+pub type Il2CppException<'a> = Il2CppExceptionView<'a>;
+
+// Error returned by managed invocation APIs
+pub enum InvokeError<'a> {
+    /// The callee threw, the exception object is still owned by the GC
+    Exception(Il2CppException<'a>),
+    /// The call could not be made (bad arguments, missing export, ...)
+    Failed(String),
+}
+
+impl<'a> InvokeError<'a> {
+    pub fn exception(&self) -> Option<Il2CppException<'a>> {
+        match self {
+            InvokeError::Exception(ex) => Some(*ex),
+            InvokeError::Failed(_) => None,
+        }
+    }
+}
+
+impl From<String> for InvokeError<'_> {
+    fn from(e: String) -> Self {
+        InvokeError::Failed(e)
+    }
+}
+
+impl From<InvokeError<'_>> for String {
+    fn from(e: InvokeError<'_>) -> Self {
+        e.to_string()
+    }
+}
+
+impl Debug for InvokeError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeError::Exception(ex) => write!(f, "Exception({:?})", ex),
+            InvokeError::Failed(e) => write!(f, "Failed({:?})", e),
+        }
+    }
+}
+
+impl Display for InvokeError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeError::Exception(ex) => write!(f, "Managed exception: {}", ex),
+            InvokeError::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for InvokeError<'_> {}
@@ -1,17 +1,30 @@
 use crate::il2cpp::classes::class::ClassInner;
 use crate::il2cpp::classes::itype::Type;
+use crate::il2cpp::pod::Pod;
+use crate::il2cpp::{field_static_get_value, field_static_set_value, runtime_class_init};
 
 use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
 use std::sync::{Arc, Weak};
 
+// FieldAttributes from the ECMA-335 metadata tables
+pub const FIELD_ATTRIBUTE_FIELD_ACCESS_MASK: i32 = 0x0007;
+pub const FIELD_ATTRIBUTE_STATIC: i32 = 0x0010;
+pub const FIELD_ATTRIBUTE_INIT_ONLY: i32 = 0x0020;
+pub const FIELD_ATTRIBUTE_LITERAL: i32 = 0x0040;
+
+// il2cpp reports this offset for [ThreadStatic] fields, their storage lives per thread
+pub const THREAD_STATIC_FIELD_OFFSET: i32 = -1;
+
 pub struct FieldInner {
     pub address: *mut u8,
     pub name: String,
     pub itype: Type,
     pub class: Weak<ClassInner>,
     pub offset: i32,
+    pub flags: i32,
     pub static_field: bool,
-    pub vtable: *mut u8,
+    pub thread_static: bool,
 }
 
 pub type Field = Arc<FieldInner>;
@@ -23,19 +36,76 @@ impl FieldInner {
         itype: Type,
         class: Weak<ClassInner>,
         offset: i32,
-        static_field: bool,
-        vtable: *mut u8,
+        flags: i32,
     ) -> Field {
+        let static_field = (flags & FIELD_ATTRIBUTE_STATIC) != 0;
         Arc::new(Self {
             address,
             name,
             itype,
             class,
             offset,
+            flags,
             static_field,
-            vtable,
+            thread_static: static_field && offset == THREAD_STATIC_FIELD_OFFSET,
         })
     }
+
+    /// True for `const` fields, which have no storage and only a metadata default value
+    pub fn is_literal(&self) -> bool {
+        (self.flags & FIELD_ATTRIBUTE_LITERAL) != 0
+    }
+
+    /// Read the value of a static field.
+    /// `T` must match the field storage: the value itself for value types,
+    /// a pointer (e.g. `*mut ObjectInner`) for reference types. Its size is checked
+    /// against the field type, read `bool` fields as `u8`.
+    /// Thread-static fields are read from the calling thread's storage.
+    pub fn get_static<T: Pod>(&self) -> Result<T, String> {
+        self.check_storage_size::<T>()?;
+        self.ensure_class_initialized()?;
+        let mut value = MaybeUninit::<T>::uninit();
+        field_static_get_value(self.address, value.as_mut_ptr() as *mut u8)?;
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// Write the value of a static field, see `get_static` for the expected `T`
+    pub fn set_static<T: Pod>(&self, value: T) -> Result<(), String> {
+        if self.is_literal() {
+            return Err(format!(
+                "Field {} is a constant and cannot be set",
+                self.name
+            ));
+        }
+        self.check_storage_size::<T>()?;
+        self.ensure_class_initialized()?;
+        let mut value = value;
+        field_static_set_value(self.address, &mut value as *mut T as *mut u8)
+    }
+
+    // il2cpp copies as many bytes as the field holds, whatever `T` is
+    fn check_storage_size<T>(&self) -> Result<(), String> {
+        let size = std::mem::size_of::<T>();
+        if self.itype.size > 0 && self.itype.size as usize != size {
+            return Err(format!(
+                "Field {} is {} bytes, not {}",
+                self.name, self.itype.size, size
+            ));
+        }
+        Ok(())
+    }
+
+    /// Runs the static constructor of the declaring class if it has not run yet,
+    /// otherwise static storage may still be unallocated or hold default values
+    fn ensure_class_initialized(&self) -> Result<(), String> {
+        if !self.static_field {
+            return Err(format!("Field {} is not static", self.name));
+        }
+        match self.class.upgrade() {
+            Some(cls) => runtime_class_init(cls.address),
+            None => Err(format!("Class of field {} was dropped", self.name)),
+        }
+    }
 }
 
 impl Debug for FieldInner {
@@ -48,8 +118,9 @@ impl Debug for FieldInner {
             write!(f, "Class: <dropped>\n")?;
         }
         write!(f, "Offset: {}\n", self.offset)?;
+        write!(f, "Flags: {:#x}\n", self.flags)?;
         write!(f, "StaticField: {}\n", self.static_field)?;
-        write!(f, "Vtable: {:p}\n", self.vtable)?;
+        write!(f, "ThreadStatic: {}\n", self.thread_static)?;
         Ok(())
     }
 }
@@ -0,0 +1,27 @@
+// Managed objects built in Rust memory for the view tests, laid out like il2cpp's
+use crate::il2cpp::classes::array::Il2CppArrayInner;
+use crate::il2cpp::classes::object::ObjectInner;
+use std::mem::{offset_of, size_of};
+
+pub(crate) fn header() -> ObjectInner {
+    ObjectInner {
+        klass: std::ptr::null_mut(),
+        monitor: std::ptr::null_mut(),
+    }
+}
+
+/// A single dimension array holding `elements`, backed by u64s for the header alignment.
+/// Point views at it with `array.as_mut_ptr() as *mut ArrayInner<T>`.
+pub(crate) fn array<T>(mut elements: Vec<T>) -> Vec<u64> {
+    let data = size_of::<Il2CppArrayInner<T>>();
+    let mut storage = vec![0u64; (data + size_of_val(elements.as_slice())).div_ceil(8)];
+    let bytes = storage.as_mut_ptr() as *mut u8;
+    unsafe {
+        let length = bytes.add(offset_of!(Il2CppArrayInner<T>, max_length)) as *mut usize;
+        length.write(elements.len());
+        std::ptr::copy_nonoverlapping(elements.as_ptr(), bytes.add(data) as *mut T, elements.len());
+        // moved into the array
+        elements.set_len(0);
+    }
+    storage
+}
@@ -9,6 +9,7 @@ use crate::{
 };
 
 il2cpp_view! {
+    #[il2cpp(class = "UnityEngine.GameObject", assembly = "UnityEngine.CoreModule.dll")]
     pub struct GameObject {
         pub obj: UnityObjectInner,
     }
@@ -0,0 +1,97 @@
+use crate::il2cpp::classes::il2cpp_view::Il2CppView;
+use crate::il2cpp::{gchandle_free, gchandle_get_target, gchandle_new, gchandle_new_weakref};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcHandleKind {
+    /// Keeps the object alive
+    Strong,
+    /// Keeps the object alive and prevents the GC from moving it
+    Pinned,
+    /// Does not keep the object alive, the target becomes null once collected
+    Weak,
+    /// Weak handle that keeps tracking the object while its finalizer runs
+    WeakTrackResurrection,
+}
+
+/// Owned GC handle to a managed object, freed on drop.
+/// Views borrowed from a raw pointer are only valid until the next GC,
+/// a handle lets the object be reached again in later frames.
+pub struct GcHandle<Inner> {
+    handle: u32,
+    kind: GcHandleKind,
+    _marker: PhantomData<*mut Inner>,
+}
+
+impl<Inner> GcHandle<Inner> {
+    pub fn new<'a, V: Il2CppView<'a, Inner>>(view: &V, kind: GcHandleKind) -> Result<Self, String> {
+        let obj = view.as_il2cpp_object() as *mut u8;
+        let handle = match kind {
+            GcHandleKind::Strong => gchandle_new(obj, false)?,
+            GcHandleKind::Pinned => gchandle_new(obj, true)?,
+            GcHandleKind::Weak => gchandle_new_weakref(obj, false)?,
+            GcHandleKind::WeakTrackResurrection => gchandle_new_weakref(obj, true)?,
+        };
+        if handle == 0 {
+            return Err("Failed to create gc handle".to_string());
+        }
+        Ok(Self {
+            handle,
+            kind,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn strong<'a, V: Il2CppView<'a, Inner>>(view: &V) -> Result<Self, String> {
+        Self::new(view, GcHandleKind::Strong)
+    }
+
+    pub fn pinned<'a, V: Il2CppView<'a, Inner>>(view: &V) -> Result<Self, String> {
+        Self::new(view, GcHandleKind::Pinned)
+    }
+
+    pub fn weak<'a, V: Il2CppView<'a, Inner>>(view: &V) -> Result<Self, String> {
+        Self::new(view, GcHandleKind::Weak)
+    }
+
+    /// A fresh view of the target, `None` once a weak target has been collected.
+    /// The view borrows the handle so it cannot outlive it, but do not keep it
+    /// past the current frame either, ask the handle again instead.
+    pub fn target<'a, V: Il2CppView<'a, Inner>>(&'a self) -> Option<V> {
+        let obj = gchandle_get_target(self.handle).ok()?;
+        V::from_ptr(obj as *mut Inner)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        gchandle_get_target(self.handle).is_ok_and(|obj| !obj.is_null())
+    }
+
+    pub fn kind(&self) -> GcHandleKind {
+        self.kind
+    }
+
+    /// The raw il2cpp handle value
+    pub fn raw(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl<Inner> Drop for GcHandle<Inner> {
+    fn drop(&mut self) {
+        let _ = gchandle_free(self.handle);
+    }
+}
+
+impl<Inner> Debug for GcHandle<Inner> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcHandle")
+            .field("handle", &self.handle)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+// handles are plain integers, the target is only reached through il2cpp
+unsafe impl<Inner> Send for GcHandle<Inner> {}
+unsafe impl<Inner> Sync for GcHandle<Inner> {}
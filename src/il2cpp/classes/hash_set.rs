@@ -0,0 +1,122 @@
+use crate::{
+    il2cpp::classes::{
+        array::{Array, ArrayInner},
+        dictionary::KeyMatch,
+        il2cpp_view::Il2CppView,
+        object::ObjectInner,
+    },
+    il2cpp_view_generic,
+};
+
+// HashSet<T>.Slot
+#[repr(C)]
+pub struct HashSetSlot<T> {
+    // negative for free slots
+    pub hash_code: i32,
+    pub next: i32,
+    pub value: T,
+}
+
+// System.Collections.Generic.HashSet<T>
+il2cpp_view_generic! {
+    pub struct HashSet<T> {
+        pub obj: ObjectInner,
+        pub buckets: *mut ArrayInner<i32>,
+        pub slots: *mut ArrayInner<HashSetSlot<T>>,
+        pub count: i32,
+        pub last_index: i32,
+        pub free_list: i32,
+        pub comparer: *mut ObjectInner,
+        pub version: i32,
+        pub si_info: *mut ObjectInner,
+    }
+}
+
+impl<'a, T> HashSetView<'a, T> {
+    pub fn len(&self) -> usize {
+        self.checked_ref()
+            .map_or(0, |set| set.count.max(0) as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Values of the used slots, slots past `m_lastIndex` were never filled
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.filled_slots()
+            .unwrap_or(&[])
+            .iter()
+            .filter(|slot| slot.hash_code >= 0)
+            .map(|slot| &slot.value)
+    }
+
+    // `m_slots` up to `m_lastIndex`, `None` without an array or for an unreadable set
+    fn filled_slots(&self) -> Option<&'a [HashSetSlot<T>]> {
+        let set = self.checked_ref().ok()?;
+        let slots = Array::from_ptr(set.slots)?.as_slice();
+        Some(&slots[..(set.last_index.max(0) as usize).min(slots.len())])
+    }
+
+    /// Linear search, see `DictionaryView::get`
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        T: KeyMatch<Q>,
+    {
+        self.iter().any(|v| v.key_matches(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::fixture;
+    use crate::il2cpp::probe::CheckedReads;
+
+    fn slot(hash_code: i32, value: i32) -> HashSetSlot<i32> {
+        HashSetSlot {
+            hash_code,
+            next: -1,
+            value,
+        }
+    }
+
+    #[test]
+    fn slots_past_last_index_and_free_slots_are_skipped() {
+        let _reads = CheckedReads::set(false);
+        let mut slots = fixture::array(vec![
+            slot(10, 10),
+            slot(-1, 20),
+            slot(30, 30),
+            // stale data past `m_lastIndex`
+            slot(40, 40),
+            slot(50, 50),
+        ]);
+        let inner = HashSetInner {
+            obj: fixture::header(),
+            buckets: std::ptr::null_mut(),
+            slots: slots.as_mut_ptr() as *mut ArrayInner<HashSetSlot<i32>>,
+            count: 2,
+            last_index: 3,
+            free_list: 1,
+            comparer: std::ptr::null_mut(),
+            version: 0,
+            si_info: std::ptr::null_mut(),
+        };
+        let set = HashSetView::from_ref(&inner);
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), vec![10, 30]);
+        assert!(set.contains(&30));
+        assert!(!set.contains(&20));
+        assert!(!set.contains(&40));
+
+        // a `m_lastIndex` beyond the array is clamped to its length
+        let inner = HashSetInner {
+            last_index: 99,
+            ..inner
+        };
+        let set = HashSetView::from_ref(&inner);
+        assert_eq!(set.iter().count(), 4);
+    }
+}
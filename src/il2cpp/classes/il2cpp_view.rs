@@ -1,10 +1,22 @@
 use crate::{
-    il2cpp::classes::{
-        object::{ObjectInner, ObjectView},
-        string::UnityString,
+    il2cpp::{
+        class_get_full_name, class_is_assignable_from,
+        classes::{
+            class::Class,
+            field::Field,
+            method::Method,
+            object::{ObjectInner, ObjectView},
+            string::UnityString,
+        },
+        find_class,
+        il2cpp_sys::c_types::Il2CppClass,
+        probe,
     },
-    il2cpp_cache,
+    il2cpp_cache::{self, Il2CppCacheTrait},
 };
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 // Special trait for views of Il2Cpp objects
 // THIS IS ONLY FOR IL2CPP OBJECTS with an obj field
@@ -19,6 +31,23 @@ pub trait Il2CppView<'a, Inner> {
     fn as_ref(&self) -> &'a Inner;
 
     fn as_il2cpp_object(&self) -> *mut ObjectInner;
+
+    /// `as_ref` that first checks the whole inner struct is readable memory, so a stale
+    /// pointer is an error instead of a crash of the game
+    fn try_as_ref(&self) -> Result<&'a Inner, String> {
+        probe::check_readable(self.as_ptr() as usize, std::mem::size_of::<Inner>())?;
+        Ok(self.as_ref())
+    }
+
+    /// `try_as_ref` while checked reads are enabled (`probe::set_checked_reads`),
+    /// `as_ref` otherwise
+    fn checked_ref(&self) -> Result<&'a Inner, String> {
+        if probe::checked_reads() {
+            self.try_as_ref()
+        } else {
+            Ok(self.as_ref())
+        }
+    }
 }
 
 // Zero-Cost casting from a *mut Inner to a View
@@ -39,6 +68,73 @@ where
     }
 }
 
+// Resolved classes keyed by (assembly, full name), class pointers live as long as the domain
+static MANAGED_CLASSES: LazyLock<RwLock<HashMap<(&'static str, &'static str), usize>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// A field of a view's inner struct as laid out by rustc
+#[derive(Debug, Clone, Copy)]
+pub struct ViewField {
+    pub name: &'static str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+// The managed class a view models, used by checked casts and layout validation.
+// Usually generated by the `#[il2cpp(class = ..., assembly = ...)]` attribute of `il2cpp_view!`
+pub trait Il2CppManagedClass {
+    type Inner;
+    /// Image the class is defined in, e.g. `UnityEngine.CoreModule.dll`
+    const ASSEMBLY: &'static str;
+    /// Full name of the class, e.g. `UnityEngine.GameObject`
+    const CLASS_NAME: &'static str;
+    /// Rust side layout of the inner struct, in declaration order
+    const FIELDS: &'static [ViewField];
+
+    /// Runtime class, looked up once then cached
+    fn il2cpp_class() -> Result<Il2CppClass, String> {
+        let key = (Self::ASSEMBLY, Self::CLASS_NAME);
+        if let Some(klass) = MANAGED_CLASSES.read().get(&key) {
+            return Ok(*klass as Il2CppClass);
+        }
+        let klass = find_class(Self::ASSEMBLY, Self::CLASS_NAME)?;
+        MANAGED_CLASSES.write().insert(key, klass as usize);
+        Ok(klass)
+    }
+
+    /// The class in the metadata cache, matched by namespace and name
+    fn cached_class(cache: &impl Il2CppCacheTrait) -> Option<&Class> {
+        let (namespace, name) = Self::CLASS_NAME
+            .rsplit_once('.')
+            .unwrap_or(("", Self::CLASS_NAME));
+        cache
+            .get_assembly(Self::ASSEMBLY)?
+            .classes
+            .iter()
+            .find(|c| c.name == name && c.namespace == namespace)
+    }
+
+    /// Field of the class by name, from the metadata cache
+    fn cached_field(cache: &impl Il2CppCacheTrait, name: &str) -> Result<Field, String> {
+        Self::cached_class(cache)
+            .ok_or_else(|| format!("Class {} is not in the cache", Self::CLASS_NAME))?
+            .get_field(name)
+            .ok_or_else(|| format!("Field {}.{} not found", Self::CLASS_NAME, name))
+    }
+
+    /// Method of the class by name and parameter type names, from the metadata cache
+    fn cached_method(
+        cache: &impl Il2CppCacheTrait,
+        name: &str,
+        arg_types: &[&str],
+    ) -> Result<Method, String> {
+        Self::cached_class(cache)
+            .ok_or_else(|| format!("Class {} is not in the cache", Self::CLASS_NAME))?
+            .get_method_with_args(name, arg_types.to_vec())
+            .ok_or_else(|| format!("Method {}.{} not found", Self::CLASS_NAME, name))
+    }
+}
+
 // Extension trait to provide unchecked zero-cost casts between views
 pub trait Il2CppViewCast<'a, Inner>: Il2CppView<'a, Inner> + Sized {
     /// Reinterpret this view as another view type without runtime checks.
@@ -60,10 +156,42 @@ pub trait Il2CppViewCast<'a, Inner>: Il2CppView<'a, Inner> + Sized {
     {
         D::from_ptr(self.as_ptr() as *mut DInner).expect("Failed to build destination view")
     }
+
+    /// Whether the dynamic class of the object is `D`'s managed class or derives from it
+    fn is<D>(&self) -> Result<bool, String>
+    where
+        D: Il2CppManagedClass,
+    {
+        class_is_assignable_from(D::il2cpp_class()?, object_class(self.as_il2cpp_object())?)
+    }
+
+    /// Checked cast, fails unless the object is an instance of `D`'s managed class
+    fn try_cast<D>(&self) -> Result<D, String>
+    where
+        D: Il2CppManagedClass + Il2CppView<'a, <D as Il2CppManagedClass>::Inner>,
+    {
+        if !self.is::<D>()? {
+            return Err(format!(
+                "Cannot cast {} to {}",
+                class_get_full_name(object_class(self.as_il2cpp_object())?)?,
+                D::CLASS_NAME
+            ));
+        }
+        D::from_ptr(self.as_ptr() as *mut D::Inner)
+            .ok_or_else(|| "Failed to build destination view".to_string())
+    }
 }
 
 impl<'a, Inner, T> Il2CppViewCast<'a, Inner> for T where T: Il2CppView<'a, Inner> {}
 
+// Runtime class from the object header, checked first while checked reads are enabled
+fn object_class(object: *mut ObjectInner) -> Result<Il2CppClass, String> {
+    if probe::checked_reads() {
+        probe::check_readable(object as usize, std::mem::size_of::<ObjectInner>())?;
+    }
+    Ok(unsafe { (*object).klass })
+}
+
 pub trait Il2CppViewGetName<'a, Inner>: Il2CppViewCast<'a, Inner> {
     fn get_name(&self, cache: &il2cpp_cache::Cache) -> Result<UnityString<'a>, String> {
         self.cast::<ObjectInner, ObjectView>().get_name(cache)
@@ -72,15 +200,46 @@ pub trait Il2CppViewGetName<'a, Inner>: Il2CppViewCast<'a, Inner> {
 
 impl<'a, Inner, T> Il2CppViewGetName<'a, Inner> for T where T: Il2CppViewCast<'a, Inner> {}
 
-// Macro to declare a C-compatible inner struct and generate its zero-cost View
+// Macro to declare a C-compatible inner struct and generate its zero-cost View.
+// An optional leading `#[il2cpp(class = "Namespace.Name", assembly = "Image.dll")]`
+// records the managed class the struct models (see `Il2CppManagedClass`).
 #[macro_export]
 macro_rules! il2cpp_view {
+    (
+        #[il2cpp(class = $class:literal, assembly = $assembly:literal $(,)?)]
+        $(#[$m:meta])* $vis:vis struct $Name:ident {
+            $( $field_vis:vis $field_name:ident : $field_ty:ty, )* $(,)?
+        }
+    ) => {
+        $crate::il2cpp_view! {
+            $(#[$m])* $vis struct $Name {
+                $( $field_vis $field_name : $field_ty, )*
+            }
+        }
+
+        $crate::paste::paste! {
+            impl $crate::il2cpp::classes::il2cpp_view::Il2CppManagedClass for [<$Name View>]<'_> {
+                type Inner = [<$Name Inner>];
+                const ASSEMBLY: &'static str = $assembly;
+                const CLASS_NAME: &'static str = $class;
+                const FIELDS: &'static [$crate::il2cpp::classes::il2cpp_view::ViewField] = &[
+                    $(
+                        $crate::il2cpp::classes::il2cpp_view::ViewField {
+                            name: stringify!($field_name),
+                            offset: ::std::mem::offset_of!([<$Name Inner>], $field_name),
+                            size: ::std::mem::size_of::<$field_ty>(),
+                        },
+                    )*
+                ];
+            }
+        }
+    };
     (
         $(#[$m:meta])* $vis:vis struct $Name:ident {
             $( $field_vis:vis $field_name:ident : $field_ty:ty, )* $(,)?
         }
     ) => {
-        ::paste::paste! {
+        $crate::paste::paste! {
             #[repr(C)]
             $(#[$m])* $vis struct [<$Name Inner>] {
                 $( $field_vis $field_name : $field_ty, )*
@@ -110,10 +269,10 @@ macro_rules! il2cpp_view {
                 pub fn as_ref(&self) -> &'a [<$Name Inner>] { unsafe { self.ptr.as_ref() } }
 
                 #[inline(always)]
-                pub fn as_il2cpp_object(&self) -> *mut crate::il2cpp::classes::object::ObjectInner { self.ptr.as_ptr() as *mut _ }
+                pub fn as_il2cpp_object(&self) -> *mut $crate::il2cpp::classes::object::ObjectInner { self.ptr.as_ptr() as *mut _ }
             }
 
-            impl<'a> crate::il2cpp::classes::il2cpp_view::Il2CppView<'a, [<$Name Inner>]> for [<$Name View>]<'a> {
+            impl<'a> $crate::il2cpp::classes::il2cpp_view::Il2CppView<'a, [<$Name Inner>]> for [<$Name View>]<'a> {
                 #[inline(always)]
                 fn from_ptr(ptr: *mut [<$Name Inner>]) -> Option<Self> { <Self>::from_ptr(ptr) }
 
@@ -127,7 +286,7 @@ macro_rules! il2cpp_view {
                 fn as_ref(&self) -> &'a [<$Name Inner>] { <Self>::as_ref(self) }
 
                 #[inline(always)]
-                fn as_il2cpp_object(&self) -> *mut crate::il2cpp::classes::object::ObjectInner { self.ptr.as_ptr() as *mut _ }
+                fn as_il2cpp_object(&self) -> *mut $crate::il2cpp::classes::object::ObjectInner { self.ptr.as_ptr() as *mut _ }
             }
 
             unsafe impl Send for [<$Name Inner>] {}
@@ -147,7 +306,7 @@ macro_rules! il2cpp_view_generic {
             $( $field_vis:vis $field_name:ident : $field_ty:ty, )* $(,)?
         }
     ) => {
-        ::paste::paste! {
+        $crate::paste::paste! {
             #[repr(C)]
             $(#[$m])* $vis struct [<$Name Inner>] < $($gen),+ >
             $(where $($whr)*)?
@@ -190,12 +349,12 @@ macro_rules! il2cpp_view_generic {
 
                 // `obj` is the first field, so the struct address is a valid ObjectInner pointer
                 #[inline(always)]
-                pub fn as_il2cpp_object(&self) -> *mut crate::il2cpp::classes::object::ObjectInner {
+                pub fn as_il2cpp_object(&self) -> *mut $crate::il2cpp::classes::object::ObjectInner {
                     self.ptr.as_ptr() as *mut _
                 }
             }
 
-            impl<'a, $($gen),+> crate::il2cpp::classes::il2cpp_view::Il2CppView<'a, [<$Name Inner>] < $($gen),+ > >
+            impl<'a, $($gen),+> $crate::il2cpp::classes::il2cpp_view::Il2CppView<'a, [<$Name Inner>] < $($gen),+ > >
                 for [<$Name View>]<'a, $($gen),+>
             $(where $($whr)*)?
             {
@@ -212,7 +371,7 @@ macro_rules! il2cpp_view_generic {
                 fn as_ref(&self) -> &'a [<$Name Inner>]<$($gen),+> { <Self>::as_ref(self) }
 
                 #[inline(always)]
-                fn as_il2cpp_object(&self) -> *mut crate::il2cpp::classes::object::ObjectInner {
+                fn as_il2cpp_object(&self) -> *mut $crate::il2cpp::classes::object::ObjectInner {
                     self.ptr.as_ptr() as *mut _
                 }
             }
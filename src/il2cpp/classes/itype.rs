@@ -1,12 +1,107 @@
 use std::sync::Arc;
 
 use crate::il2cpp::il2cpp_sys::c_types::Il2CppType;
+use serde::{Deserialize, Serialize};
+
+// Decoded Il2CppTypeEnum, the element type tag of an Il2CppType
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TypeKind {
+    Void,
+    Boolean,
+    Char,
+    I1,
+    U1,
+    I2,
+    U2,
+    I4,
+    U4,
+    I8,
+    U8,
+    R4,
+    R8,
+    String,
+    Ptr,
+    ByRef,
+    ValueType,
+    Class,
+    Var,
+    Array,
+    GenericInst,
+    TypedByRef,
+    I,
+    U,
+    FnPtr,
+    Object,
+    SzArray,
+    MVar,
+    Unknown(i32),
+}
+
+impl TypeKind {
+    pub fn from_raw(raw: i32) -> Self {
+        match raw {
+            0x01 => Self::Void,
+            0x02 => Self::Boolean,
+            0x03 => Self::Char,
+            0x04 => Self::I1,
+            0x05 => Self::U1,
+            0x06 => Self::I2,
+            0x07 => Self::U2,
+            0x08 => Self::I4,
+            0x09 => Self::U4,
+            0x0a => Self::I8,
+            0x0b => Self::U8,
+            0x0c => Self::R4,
+            0x0d => Self::R8,
+            0x0e => Self::String,
+            0x0f => Self::Ptr,
+            0x10 => Self::ByRef,
+            0x11 => Self::ValueType,
+            0x12 => Self::Class,
+            0x13 => Self::Var,
+            0x14 => Self::Array,
+            0x15 => Self::GenericInst,
+            0x16 => Self::TypedByRef,
+            0x18 => Self::I,
+            0x19 => Self::U,
+            0x1b => Self::FnPtr,
+            0x1c => Self::Object,
+            0x1d => Self::SzArray,
+            0x1e => Self::MVar,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Primitive kinds are stored inline and boxed into their `System.*` class
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            Self::Boolean
+                | Self::Char
+                | Self::I1
+                | Self::U1
+                | Self::I2
+                | Self::U2
+                | Self::I4
+                | Self::U4
+                | Self::I8
+                | Self::U8
+                | Self::R4
+                | Self::R8
+                | Self::I
+                | Self::U
+        )
+    }
+}
 
 #[derive(Debug)]
 pub struct TypeInner {
     pub address: Il2CppType,
     pub name: String,
     pub size: isize,
+    pub kind: TypeKind,
+    pub byref: bool,
+    pub value_type: bool,
 }
 
 // Public handle used throughout the model
@@ -18,16 +113,33 @@ impl TypeInner {
             address: std::ptr::null_mut(),
             name: "".to_string(),
             size: 0,
+            kind: TypeKind::Void,
+            byref: false,
+            value_type: false,
         })
     }
 
-    pub fn new(address: Il2CppType, name: String, size: isize) -> Type {
+    pub fn new(
+        address: Il2CppType,
+        name: String,
+        size: isize,
+        kind: TypeKind,
+        byref: bool,
+        value_type: bool,
+    ) -> Type {
         Arc::new(Self {
             address,
             name,
             size,
+            kind,
+            byref,
+            value_type,
         })
     }
+
+    pub fn is_void(&self) -> bool {
+        self.kind == TypeKind::Void && !self.byref
+    }
 }
 
 unsafe impl Send for TypeInner {}
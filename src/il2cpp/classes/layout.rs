@@ -0,0 +1,332 @@
+use crate::il2cpp::array_object_header_size;
+use crate::il2cpp::classes::array::ArrayInner;
+use crate::il2cpp::classes::component::ComponentView;
+use crate::il2cpp::classes::exception::Il2CppExceptionView;
+use crate::il2cpp::classes::field::{Field, FieldInner};
+use crate::il2cpp::classes::gameobject::GameObjectView;
+use crate::il2cpp::classes::il2cpp_view::{Il2CppManagedClass, ViewField};
+use crate::il2cpp::classes::object::ObjectView;
+use crate::il2cpp::classes::string::UnityString;
+use crate::il2cpp::classes::transform::TransformView;
+use crate::il2cpp::classes::unity_object::UnityObject;
+use crate::il2cpp_cache::Il2CppCacheTrait;
+use std::fmt::{Display, Formatter};
+
+// One difference between a view struct and the metadata of its managed class
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    /// The managed class is not in the cache
+    ClassNotFound,
+    /// The field exists on both sides at different offsets
+    Offset {
+        field: &'static str,
+        managed_name: String,
+        rust: usize,
+        managed: i32,
+    },
+    /// The field exists on both sides with different storage sizes
+    Size {
+        field: &'static str,
+        managed_name: String,
+        rust: usize,
+        managed: isize,
+    },
+    /// No instance field of the class matches the Rust field name.
+    /// Expected for runtime internals (object header, `m_` renamed fields), so not an error
+    Unmatched { field: &'static str, rust: usize },
+    /// A runtime struct whose size il2cpp reports directly (e.g. the array header)
+    HeaderSize { rust: usize, managed: usize },
+}
+
+impl LayoutIssue {
+    /// Whether this issue means reads through the view are wrong
+    pub fn is_error(&self) -> bool {
+        !matches!(self, LayoutIssue::Unmatched { .. })
+    }
+}
+
+impl Display for LayoutIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutIssue::ClassNotFound => write!(f, "class not found in cache"),
+            LayoutIssue::Offset {
+                field,
+                managed_name,
+                rust,
+                managed,
+            } => write!(
+                f,
+                "{} at 0x{:X}, but {} is at 0x{:X}",
+                field, rust, managed_name, managed
+            ),
+            LayoutIssue::Size {
+                field,
+                managed_name,
+                rust,
+                managed,
+            } => write!(
+                f,
+                "{} is {} bytes, but {} is {} bytes",
+                field, rust, managed_name, managed
+            ),
+            LayoutIssue::Unmatched { field, rust } => {
+                write!(f, "{} at 0x{:X} has no matching field", field, rust)
+            }
+            LayoutIssue::HeaderSize { rust, managed } => write!(
+                f,
+                "header is {} bytes, but il2cpp uses {} bytes",
+                rust, managed
+            ),
+        }
+    }
+}
+
+// Result of checking one view against the metadata
+#[derive(Debug, Clone)]
+pub struct LayoutReport {
+    pub class_name: &'static str,
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl LayoutReport {
+    /// True when no issue affects reads through the view
+    pub fn is_ok(&self) -> bool {
+        !self.issues.iter().any(|i| i.is_error())
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &LayoutIssue> {
+        self.issues.iter().filter(|i| i.is_error())
+    }
+}
+
+impl Display for LayoutReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = if self.is_ok() { "ok" } else { "MISMATCH" };
+        writeln!(f, "{}: {}", self.class_name, status)?;
+        for issue in &self.issues {
+            let prefix = if issue.is_error() { "error" } else { "note" };
+            writeln!(f, "    {}: {}", prefix, issue)?;
+        }
+        Ok(())
+    }
+}
+
+// `m_CachedPtr`, `_cachedPtr` and `m_cached_ptr` all compare as `cachedptr`,
+// generated `_Name_k__BackingField` matches `<Name>k__BackingField`
+fn normalize_field_name(name: &str) -> String {
+    let name = name.strip_prefix("m_").unwrap_or(name);
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn find_managed_field<'f>(fields: &'f [Field], field: &ViewField) -> Option<&'f FieldInner> {
+    let name = normalize_field_name(field.name);
+    fields
+        .iter()
+        .filter(|f| !f.static_field)
+        .find(|f| normalize_field_name(&f.name) == name)
+        .map(|f| f.as_ref())
+}
+
+/// Compare the offsets of `V`'s inner struct (`offset_of!`) with the field offsets in the cache.
+/// The field at offset 0 is the embedded parent or object header and is skipped,
+/// inherited fields are checked by validating the parent view.
+pub fn validate_layout<V: Il2CppManagedClass>(cache: &impl Il2CppCacheTrait) -> LayoutReport {
+    let mut report = LayoutReport {
+        class_name: V::CLASS_NAME,
+        issues: Vec::new(),
+    };
+    let Some(class) = V::cached_class(cache) else {
+        report.issues.push(LayoutIssue::ClassNotFound);
+        return report;
+    };
+
+    let fields = class.fields.read();
+    // generated bindings fill inherited and unknown ranges with `_pad_*` fields
+    let checked = V::FIELDS
+        .iter()
+        .filter(|f| f.offset != 0 && !f.name.starts_with("_pad_"));
+    for field in checked {
+        let Some(managed) = find_managed_field(&fields, field) else {
+            report.issues.push(LayoutIssue::Unmatched {
+                field: field.name,
+                rust: field.offset,
+            });
+            continue;
+        };
+        if managed.offset < 0 || managed.offset as usize != field.offset {
+            report.issues.push(LayoutIssue::Offset {
+                field: field.name,
+                managed_name: managed.name.clone(),
+                rust: field.offset,
+                managed: managed.offset,
+            });
+        }
+        // zero sized fields stand for flexible array members
+        if field.size != 0 && managed.itype.size != field.size as isize {
+            report.issues.push(LayoutIssue::Size {
+                field: field.name,
+                managed_name: managed.name.clone(),
+                rust: field.size,
+                managed: managed.itype.size,
+            });
+        }
+    }
+    report
+}
+
+/// Check the array header against `il2cpp_array_object_header_size`
+pub fn validate_array_layout() -> LayoutReport {
+    let mut report = LayoutReport {
+        class_name: "System.Array",
+        issues: Vec::new(),
+    };
+    let rust = std::mem::size_of::<ArrayInner<u8>>();
+    // runtimes without the export are not checked
+    if let Some(managed) = array_object_header_size()
+        .ok()
+        .filter(|managed| *managed as usize != rust)
+    {
+        report.issues.push(LayoutIssue::HeaderSize {
+            rust,
+            managed: managed as usize,
+        });
+    }
+    report
+}
+
+/// Validate every view shipped with this crate, e.g. once at startup after building the cache
+pub fn validate_builtin_layouts(cache: &impl Il2CppCacheTrait) -> Vec<LayoutReport> {
+    vec![
+        validate_layout::<ObjectView>(cache),
+        validate_layout::<UnityString>(cache),
+        validate_layout::<Il2CppExceptionView>(cache),
+        validate_layout::<UnityObject>(cache),
+        validate_layout::<ComponentView>(cache),
+        validate_layout::<GameObjectView>(cache),
+        validate_layout::<TransformView>(cache),
+        validate_array_layout(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::{
+        assembly::Assembly,
+        class::ClassInner,
+        field::{FIELD_ATTRIBUTE_STATIC, FieldInner},
+        itype::{TypeInner, TypeKind},
+    };
+    use crate::il2cpp_cache::Cache;
+    use std::sync::Arc;
+
+    const POINTER: isize = std::mem::size_of::<usize>() as isize;
+
+    // UnityEngine.Object with the given (name, offset, size, flags) fields
+    fn cache_with(fields: &[(&str, i32, isize, i32)]) -> Cache {
+        let class = ClassInner::new(
+            std::ptr::null_mut(),
+            "Object".to_string(),
+            "System.Object".to_string(),
+            "UnityEngine".to_string(),
+            0,
+            false,
+            false,
+        );
+        for (name, offset, size, flags) in fields {
+            let itype = TypeInner::new(
+                std::ptr::null_mut(),
+                "System.IntPtr".to_string(),
+                *size,
+                TypeKind::I,
+                false,
+                true,
+            );
+            class.fields.write().push(FieldInner::new(
+                std::ptr::null_mut(),
+                name.to_string(),
+                itype,
+                Arc::downgrade(&class),
+                *offset,
+                *flags,
+            ));
+        }
+        let mut assembly = Assembly::new(
+            std::ptr::null_mut(),
+            "UnityEngine.CoreModule.dll".to_string(),
+            "UnityEngine.CoreModule.dll".to_string(),
+        );
+        assembly.classes.push(class);
+        let mut cache = Cache::default();
+        cache.assemblies.push(assembly);
+        cache
+    }
+
+    fn cached_ptr_offset() -> i32 {
+        std::mem::offset_of!(
+            crate::il2cpp::classes::unity_object::UnityObjectInner,
+            m_cached_ptr
+        ) as i32
+    }
+
+    #[test]
+    fn field_names_normalize() {
+        assert_eq!(normalize_field_name("m_CachedPtr"), "cachedptr");
+        assert_eq!(normalize_field_name("m_cached_ptr"), "cachedptr");
+        assert_eq!(
+            normalize_field_name("<Name>k__BackingField"),
+            normalize_field_name("_Name_k__BackingField")
+        );
+    }
+
+    #[test]
+    fn matching_layout_is_ok() {
+        let cache = cache_with(&[("m_CachedPtr", cached_ptr_offset(), POINTER, 0)]);
+        let report = validate_layout::<UnityObject>(&cache);
+        assert!(report.is_ok(), "{}", report);
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn offset_and_size_mismatches_are_errors() {
+        let offset = cached_ptr_offset() + 8;
+        let cache = cache_with(&[("m_CachedPtr", offset, 4, 0)]);
+        let report = validate_layout::<UnityObject>(&cache);
+        assert!(!report.is_ok());
+        assert!(matches!(
+            report.issues[..],
+            [
+                LayoutIssue::Offset { managed, .. },
+                LayoutIssue::Size { managed: 4, .. }
+            ] if managed == offset
+        ));
+    }
+
+    #[test]
+    fn static_and_missing_fields_are_unmatched() {
+        let cache = cache_with(&[(
+            "m_CachedPtr",
+            cached_ptr_offset(),
+            POINTER,
+            FIELD_ATTRIBUTE_STATIC,
+        )]);
+        let report = validate_layout::<UnityObject>(&cache);
+        assert!(report.is_ok());
+        assert!(matches!(
+            report.issues[..],
+            [LayoutIssue::Unmatched {
+                field: "m_cached_ptr",
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn missing_class_is_reported() {
+        let report = validate_layout::<ObjectView>(&cache_with(&[]));
+        assert_eq!(report.issues, vec![LayoutIssue::ClassNotFound]);
+    }
+}
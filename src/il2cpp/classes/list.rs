@@ -0,0 +1,99 @@
+use crate::{
+    il2cpp::classes::{
+        array::{Array, ArrayInner},
+        il2cpp_view::Il2CppView,
+        object::ObjectInner,
+    },
+    il2cpp_view_generic,
+};
+
+// System.Collections.Generic.List<T>
+il2cpp_view_generic! {
+    pub struct List<T> {
+        pub obj: ObjectInner,
+        pub items: *mut ArrayInner<T>,
+        pub size: i32,
+        pub version: i32,
+        pub sync_root: *mut ObjectInner,
+    }
+}
+
+impl<'a, T> ListView<'a, T> {
+    /// Number of elements in the list, not the capacity of the backing array.
+    /// 0 for an unreadable list with checked reads enabled.
+    pub fn len(&self) -> usize {
+        self.checked_ref()
+            .map_or(0, |list| list.size.max(0) as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Backing `_items` array, its length is the capacity of the list.
+    /// With checked reads enabled an unreadable list is `None` as well.
+    pub fn items(&self) -> Option<Array<'a, T>> {
+        Array::from_ptr(self.checked_ref().ok()?.items)
+    }
+
+    pub fn as_slice(&self) -> &'a [T] {
+        match self.items() {
+            Some(items) => &items.as_slice()[..self.len().min(items.len())],
+            None => &[],
+        }
+    }
+
+    /// `as_slice` that first checks the list and its backing array are readable memory
+    pub fn try_as_slice(&self) -> Result<&'a [T], String> {
+        let list = self.try_as_ref()?;
+        let Some(items) = Array::from_ptr(list.items) else {
+            return Ok(&[]);
+        };
+        let items = items.try_as_slice()?;
+        Ok(&items[..(list.size.max(0) as usize).min(items.len())])
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.as_slice().get(index)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ListView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::fixture;
+    use crate::il2cpp::probe::CheckedReads;
+
+    #[test]
+    fn elements_are_bounded_by_size_not_capacity() {
+        let _reads = CheckedReads::set(false);
+        let mut items = fixture::array(vec![1u16, 2, 3, 0, 0, 0]);
+        let inner = ListInner {
+            obj: fixture::header(),
+            items: items.as_mut_ptr() as *mut ArrayInner<u16>,
+            size: 3,
+            version: 0,
+            sync_root: std::ptr::null_mut(),
+        };
+        let list = ListView::from_ref(&inner);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.items().map(|items| items.len()), Some(6));
+        assert_eq!(list.as_slice(), &[1, 2, 3]);
+        assert_eq!(list.try_as_slice(), Ok(&[1u16, 2, 3][..]));
+        assert_eq!(list.get(3), None);
+    }
+}
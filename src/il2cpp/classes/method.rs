@@ -1,9 +1,23 @@
 use crate::il2cpp::classes::arg::Arg;
 use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::exception::{Il2CppException, Il2CppExceptionInner, InvokeError};
 use crate::il2cpp::classes::itype::Type;
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp::classes::thunk::{NativeArgs, NativeType, Thunk};
+use crate::il2cpp::classes::value::Il2CppValue;
+use crate::il2cpp::runtime_invoke;
 use parking_lot::RwLock;
 use std::sync::{Arc, Weak};
 
+// MethodAttributes from the ECMA-335 metadata tables
+pub const METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK: i32 = 0x0007;
+pub const METHOD_ATTRIBUTE_STATIC: i32 = 0x0010;
+pub const METHOD_ATTRIBUTE_FINAL: i32 = 0x0020;
+pub const METHOD_ATTRIBUTE_VIRTUAL: i32 = 0x0040;
+pub const METHOD_ATTRIBUTE_NEW_SLOT: i32 = 0x0100;
+pub const METHOD_ATTRIBUTE_ABSTRACT: i32 = 0x0400;
+pub const METHOD_ATTRIBUTE_PINVOKE_IMPL: i32 = 0x2000;
+
 pub struct MethodInner {
     pub address: *mut u8,
     pub name: String,
@@ -11,7 +25,12 @@ pub struct MethodInner {
     pub return_type: Type,
     pub flags: i32,
     pub is_static: bool,
+    /// Native code callable in this process, null without code or for offline caches
     pub function: *mut u8,
+    /// Where the native code is in the address space of the cache's `module_base`: the
+    /// function pointer for live caches, an address in the game binary or another process
+    /// for offline ones and an RVA for snapshots. 0 without native code.
+    pub code_address: usize,
     pub args: RwLock<Vec<Arg>>,
 }
 
@@ -36,20 +55,127 @@ impl MethodInner {
             flags,
             is_static,
             function,
+            code_address: function as usize,
+            args,
+        })
+    }
+
+    /// A method read from files or another process, whose code at `code_address` must not
+    /// be called from here. `callable` stays null and `thunk` refuses to build.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_offline(
+        address: *mut u8,
+        name: String,
+        class: Weak<ClassInner>,
+        return_type: Type,
+        flags: i32,
+        is_static: bool,
+        code_address: usize,
+        args: RwLock<Vec<Arg>>,
+    ) -> Method {
+        Arc::new(Self {
+            address,
+            name,
+            class,
+            return_type,
+            flags,
+            is_static,
+            function: std::ptr::null_mut(),
+            code_address,
             args,
         })
     }
 
+    // null function pointers are rejected by the callers, this names the offline case
+    fn check_callable(&self) -> Result<(), String> {
+        if self.function.is_null() && self.code_address != 0 {
+            return Err(format!(
+                "{} was loaded offline, its code at 0x{:X} is not mapped in this process",
+                self.name, self.code_address
+            ));
+        }
+        Ok(())
+    }
+
+    /// Raw method pointer reinterpreted as `T`, the hidden MethodInfo argument is not passed.
+    /// Prefer `thunk`, which checks the signature and appends it.
     pub unsafe fn callable<T>(&self) -> T {
         unsafe { std::mem::transmute_copy(&self.function) }
     }
 
     pub fn try_callable<T>(&self) -> Result<T, String> {
+        self.check_callable()?;
         if self.function == std::ptr::null_mut() {
             return Err("invalid ptr".to_string());
         }
         Ok(unsafe { self.callable::<T>() })
     }
+
+    /// Build a typed native thunk, checking `Args`/`Ret` against the method metadata.
+    /// The thunk passes the hidden MethodInfo pointer, unlike `callable`.
+    /// Managed exceptions are not caught by thunk calls, use `invoke` when the callee may throw.
+    pub fn thunk<Args: NativeArgs, Ret: NativeType>(&self) -> Result<Thunk<Args, Ret>, String> {
+        self.check_callable()?;
+        Thunk::new(
+            self.function,
+            self.address,
+            self.is_static,
+            &self.args.read(),
+            &self.return_type,
+        )
+        .map_err(|e| format!("Signature mismatch for {}: {}", self.name, e))
+    }
+
+    /// Call the method through il2cpp_runtime_invoke.
+    /// `this` is the instance (boxed for value types) or null for static methods.
+    /// Arguments are boxed/unboxed to match the declared parameter types and a
+    /// managed exception thrown by the callee is returned as `InvokeError::Exception`.
+    /// Values the callee writes to `ref`/`out` parameters are stored back into `args`.
+    pub fn invoke(
+        &self,
+        this: *mut ObjectInner,
+        args: &mut [Il2CppValue],
+    ) -> Result<Il2CppValue, InvokeError<'_>> {
+        if !self.is_static && this.is_null() {
+            return Err(InvokeError::Failed(format!(
+                "Instance method {} invoked without an instance",
+                self.name
+            )));
+        }
+
+        let params = self.args.read();
+        if params.len() != args.len() {
+            return Err(InvokeError::Failed(format!(
+                "Method {} expects {} arguments, got {}",
+                self.name,
+                params.len(),
+                args.len()
+            )));
+        }
+
+        // by-reference parameters point into `args`, so the callee writes straight to them
+        let mut raw_params = Vec::with_capacity(args.len());
+        for (param, value) in params.iter().zip(args.iter_mut()) {
+            raw_params.push(value.as_param(&param.itype)?);
+        }
+
+        let mut exc: *mut u8 = std::ptr::null_mut();
+        let ret = runtime_invoke(
+            self.address,
+            this as *mut u8,
+            raw_params.as_mut_ptr(),
+            &mut exc,
+        )?;
+
+        if let Some(exception) = Il2CppException::from_ptr(exc as *mut Il2CppExceptionInner) {
+            return Err(InvokeError::Exception(exception));
+        }
+
+        Ok(Il2CppValue::from_return(
+            ret as *mut ObjectInner,
+            &self.return_type,
+        )?)
+    }
 }
 
 unsafe impl Send for MethodInner {}
@@ -3,12 +3,23 @@ pub mod array;
 pub mod assembly;
 pub mod class;
 pub mod component;
+pub mod dictionary;
+pub mod exception;
 pub mod field;
+#[cfg(test)]
+pub(crate) mod fixture;
 pub mod gameobject;
+pub mod gc_handle;
+pub mod hash_set;
 pub mod il2cpp_view;
 pub mod itype;
+pub mod layout;
+pub mod list;
 pub mod method;
 pub mod object;
+pub mod queue;
 pub mod string;
+pub mod thunk;
 pub mod transform;
 pub mod unity_object;
+pub mod value;
@@ -1,4 +1,9 @@
-use crate::il2cpp::classes::array::ArrayInner;
+use crate::il2cpp::classes::array::{ArrayInner, ElementType};
+use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::il2cpp_view::Il2CppView;
+use crate::il2cpp::{
+    class_get_full_name, class_is_valuetype, class_value_size, object_unbox, value_box,
+};
 use crate::il2cpp_cache::Il2CppCacheTrait;
 use crate::il2cpp_view;
 use crate::{
@@ -21,6 +26,7 @@ unsafe impl Sync for MonitorData {}
 unsafe impl Send for MonitorData {}
 
 il2cpp_view! {
+    #[il2cpp(class = "System.Object", assembly = "mscorlib.dll")]
     pub struct Object {
         pub klass: Il2CppClass,
         pub monitor: *mut MonitorData,
@@ -39,9 +45,16 @@ impl<'a> ObjectView<'a> {
         self.as_ref().monitor
     }
 
-    pub fn get_name(&self, cache: &il2cpp_cache::Cache) -> Result<UnityString<'a>, String> {
-        type GetNameMethod = unsafe extern "C" fn(this: *mut ObjectInner) -> *mut UnityStringInner;
+    /// Copy the value out of a boxed value type, checking its class against `T`
+    /// (the same primitive, or a value type of `T`'s size, see `ElementType`).
+    /// A boxed `System.Boolean` unboxes as `u8`.
+    pub fn unbox<T: ElementType>(&self) -> Result<T, String> {
+        check_value_type::<T>(self.checked_ref()?.klass)?;
+        let data = object_unbox(self.as_ptr() as *mut u8)?;
+        Ok(unsafe { std::ptr::read(data as *const T) })
+    }
 
+    pub fn get_name(&self, cache: &il2cpp_cache::Cache) -> Result<UnityString<'a>, String> {
         let get_name_method = cache
             .get_assembly("UnityEngine.CoreModule.dll")
             .expect("Failed to get UnityEngine.CoreModule.dll")
@@ -49,10 +62,10 @@ impl<'a> ObjectView<'a> {
             .expect("Failed to get Object")
             .get_method_with_args("get_name", Vec::new())
             .expect("Failed to find get_name method")
-            .try_callable::<GetNameMethod>()
-            .expect("Failed to cast get_name to GetNameMethod");
+            .thunk::<(), *mut UnityStringInner>()
+            .expect("Failed to bind get_name");
 
-        let name = unsafe { get_name_method(self.as_ptr()) };
+        let name = unsafe { get_name_method.call(self.as_ptr(), ()) };
         Ok(UnityString::from_ptr(name).expect("Failed to build string view"))
     }
 
@@ -61,12 +74,6 @@ impl<'a> ObjectView<'a> {
         obj_type: Object,
         include_inactve: bool,
     ) -> Vec<Object<'a>> {
-        type FindObjectsOfTypeFn = unsafe extern "C" fn(
-            obj_type: *mut ObjectInner,
-            include_inactve: bool,
-        )
-            -> *mut ArrayInner<*mut ObjectInner>;
-
         let mut arg_types = Vec::new();
         arg_types.push("System.Type");
         arg_types.push("System.Boolean");
@@ -78,10 +85,12 @@ impl<'a> ObjectView<'a> {
             .expect("Failed to get Object")
             .get_method_with_args("FindObjectsOfType", arg_types)
             .expect("Failed to find FindObjectsOfType method")
-            .try_callable::<FindObjectsOfTypeFn>()
-            .expect("Failed to cast FindObjectsOfType to FindObjectsOfTypeFn");
+            .thunk::<(*mut ObjectInner, bool), *mut ArrayInner<*mut ObjectInner>>()
+            .expect("Failed to bind FindObjectsOfType");
 
-        let array = unsafe { find_objects_of_type(obj_type.as_ptr(), include_inactve) };
+        let array = unsafe {
+            find_objects_of_type.call(std::ptr::null_mut(), (obj_type.as_ptr(), include_inactve))
+        };
         let array_view =
             Array::<*mut ObjectInner>::from_ptr(array).expect("Failed to build array view");
         array_view
@@ -91,4 +100,33 @@ impl<'a> ObjectView<'a> {
     }
 }
 
+/// Box `value` into an instance of the value type `class`.
+/// Fails unless `class` is a value type `T` stands in for, see `ElementType`
+/// (`u8` for `System.Boolean`).
+pub fn box_value<'a, T: ElementType>(class: &ClassInner, value: &T) -> Result<ObjectView<'a>, String> {
+    check_value_type::<T>(class.address)?;
+    let obj = value_box(class.address, value as *const T as *mut u8)?;
+    ObjectView::from_ptr(obj as *mut ObjectInner)
+        .ok_or_else(|| format!("Failed to box {}", class.name))
+}
+
+fn check_value_type<T: ElementType>(klass: Il2CppClass) -> Result<(), String> {
+    if !class_is_valuetype(klass)? {
+        return Err(format!(
+            "{} is not a value type",
+            class_get_full_name(klass)?
+        ));
+    }
+    let size = class_value_size(klass)? as usize;
+    if size != std::mem::size_of::<T>() {
+        return Err(format!(
+            "{} is {} bytes, not {}",
+            class_get_full_name(klass)?,
+            size,
+            std::mem::size_of::<T>()
+        ));
+    }
+    T::check_element_class(klass)
+}
+
 pub type Object<'a> = ObjectView<'a>;
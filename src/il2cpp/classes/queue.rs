@@ -0,0 +1,135 @@
+use crate::{
+    il2cpp::classes::{
+        array::{Array, ArrayInner},
+        il2cpp_view::Il2CppView,
+        object::ObjectInner,
+    },
+    il2cpp_view_generic,
+};
+
+// System.Collections.Generic.Queue<T>, a ring buffer over `_array`
+il2cpp_view_generic! {
+    pub struct Queue<T> {
+        pub obj: ObjectInner,
+        pub array: *mut ArrayInner<T>,
+        pub head: i32,
+        pub tail: i32,
+        pub size: i32,
+        pub version: i32,
+        pub sync_root: *mut ObjectInner,
+    }
+}
+
+impl<'a, T> QueueView<'a, T> {
+    pub fn len(&self) -> usize {
+        self.checked_ref()
+            .map_or(0, |queue| queue.size.max(0) as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Element at `index` counted from the front of the queue
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index >= self.len() {
+            return None;
+        }
+        let queue = self.checked_ref().ok()?;
+        let array = Array::from_ptr(queue.array)?;
+        if array.is_empty() {
+            return None;
+        }
+        let head = queue.head.max(0) as usize;
+        array.at((head + index) % array.len())
+    }
+
+    /// The element `Dequeue` would return
+    pub fn peek(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    /// Iterate from the front to the back of the queue
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let (items, head) = match self.checked_ref() {
+            Ok(queue) => (
+                Array::from_ptr(queue.array).map_or(&[][..], |array| array.as_slice()),
+                queue.head.max(0) as usize,
+            ),
+            Err(_) => (&[][..], 0),
+        };
+        let len = if items.is_empty() { 0 } else { self.len() };
+        (0..len).map(move |i| &items[(head + i) % items.len()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::fixture;
+    use crate::il2cpp::probe::CheckedReads;
+
+    fn with_array(array: &mut [u64], head: i32, size: i32) -> QueueInner<i32> {
+        QueueInner {
+            obj: fixture::header(),
+            array: array.as_mut_ptr() as *mut ArrayInner<i32>,
+            head,
+            tail: (head + size) % 4,
+            size,
+            version: 0,
+            sync_root: std::ptr::null_mut(),
+        }
+    }
+
+    #[test]
+    fn reads_wrap_around_the_end_of_the_buffer() {
+        let _reads = CheckedReads::set(false);
+        let mut array = fixture::array(vec![10, 20, 30, 40]);
+        let inner = with_array(&mut array, 3, 3);
+        let queue = QueueView::from_ref(&inner);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![40, 10, 20]);
+        assert_eq!(queue.peek(), Some(&40));
+        assert_eq!(queue.get(2), Some(&20));
+        assert_eq!(queue.get(3), None);
+
+        // a full queue, head and tail at the same slot
+        let inner = with_array(&mut array, 2, 4);
+        let full = QueueView::from_ref(&inner);
+        assert_eq!(
+            full.iter().copied().collect::<Vec<_>>(),
+            vec![30, 40, 10, 20]
+        );
+    }
+
+    #[test]
+    fn queues_without_storage_are_empty() {
+        let _reads = CheckedReads::set(false);
+        let mut array = fixture::array::<i32>(vec![]);
+        let inner = with_array(&mut array, 0, 0);
+        let queue = QueueView::from_ref(&inner);
+        assert!(queue.is_empty() && queue.peek().is_none());
+        assert_eq!(queue.iter().count(), 0);
+
+        let inner = QueueInner::<i32> {
+            array: std::ptr::null_mut(),
+            size: 2,
+            ..queue_without_array()
+        };
+        let queue = QueueView::from_ref(&inner);
+        assert!(queue.get(0).is_none());
+        assert_eq!(queue.iter().count(), 0);
+    }
+
+    fn queue_without_array() -> QueueInner<i32> {
+        QueueInner {
+            obj: fixture::header(),
+            array: std::ptr::null_mut(),
+            head: 0,
+            tail: 0,
+            size: 0,
+            version: 0,
+            sync_root: std::ptr::null_mut(),
+        }
+    }
+}
@@ -1,39 +1,175 @@
-use crate::{il2cpp::classes::object::ObjectInner, il2cpp_view};
-use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+use crate::{
+    il2cpp::{
+        classes::{il2cpp_view::Il2CppView, object::ObjectInner},
+        probe, string_new,
+    },
+    il2cpp_view,
+};
+use std::fmt::{Debug, Display, Formatter, Write};
 
 il2cpp_view! {
+    #[il2cpp(class = "System.String", assembly = "mscorlib.dll")]
     pub struct Il2CppString {
         pub obj: ObjectInner,
         pub m_string_length: i32,
-        pub m_first_char: [u16; 32],
+        // flexible array member, the characters continue past the end of the struct
+        pub m_first_char: [u16; 0],
     }
 }
 
 impl<'a> Il2CppStringView<'a> {
-    /// Length in characters
+    /// Allocate a new managed string
+    pub fn new(text: &str) -> Result<Self, String> {
+        let ptr = string_new(text)?;
+        Self::from_ptr(ptr as *mut Il2CppStringInner)
+            .ok_or_else(|| format!("Failed to allocate string {:?}", text))
+    }
+
+    /// Length in characters, 0 for an unreadable string with checked reads enabled
     pub fn len(&self) -> usize {
-        unsafe { self.ptr.as_ref().m_string_length as usize }
+        self.checked_ref()
+            .map_or(0, |string| string.m_string_length.max(0) as usize)
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// The UTF-16 code units of the string. With checked reads enabled a string that is
+    /// not entirely readable is empty.
+    pub fn as_utf16(&self) -> &'a [u16] {
+        if probe::checked_reads() {
+            return self.try_as_utf16().unwrap_or(&[]);
+        }
+        self.chars(self.len())
+    }
+
+    // the `len` code units following the header
+    fn chars(&self, len: usize) -> &'a [u16] {
+        unsafe {
+            let chars = std::ptr::addr_of!((*self.ptr.as_ptr()).m_first_char) as *const u16;
+            std::slice::from_raw_parts(chars, len)
+        }
+    }
+
+    /// `len` that first checks the header is readable memory
+    pub fn try_len(&self) -> Result<usize, String> {
+        Ok(self.try_as_ref()?.m_string_length.max(0) as usize)
+    }
+
+    /// `as_utf16` that first checks the header and characters are readable memory
+    pub fn try_as_utf16(&self) -> Result<&'a [u16], String> {
+        let len = self.try_len()?;
+        let chars = unsafe { std::ptr::addr_of!((*self.ptr.as_ptr()).m_first_char) };
+        probe::check_readable(chars as usize, len * 2)?;
+        Ok(self.chars(len))
+    }
+
     /// Index into the string (UTF-16 code unit)
     pub fn char_at(&self, idx: usize) -> Option<u16> {
-        if idx >= self.len() {
-            None
-        } else {
-            unsafe { Some(self.ptr.as_ref().m_first_char[idx]) }
+        self.as_utf16().get(idx).copied()
+    }
+}
+
+// UTF-16 -> UTF-8, unpaired surrogates become U+FFFD
+impl Display for Il2CppStringView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for c in char::decode_utf16(self.as_utf16().iter().copied()) {
+            f.write_char(c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
         }
+        Ok(())
     }
+}
+
+impl Debug for Il2CppStringView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
 
-    /// Convert to a Rust `String` (UTF-16 -> UTF-8) if needed
-    pub fn to_string(&self) -> String {
-        let slice = unsafe { &self.ptr.as_ref().m_first_char[..self.len()] };
-        OsString::from_wide(slice).to_string_lossy().into_owned()
+impl PartialEq<str> for Il2CppStringView<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_utf16().iter().copied().eq(other.encode_utf16())
+    }
+}
+
+impl PartialEq<&str> for Il2CppStringView<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
     }
 }
 
 pub type UnityStringInner = Il2CppStringInner;
 pub type UnityString<'a> = Il2CppStringView<'a>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::probe::CheckedReads;
+    use std::mem::offset_of;
+
+    // A string object laid out like il2cpp's, backed by u64s for the header alignment
+    fn managed(units: &[u16]) -> Vec<u64> {
+        let chars = offset_of!(Il2CppStringInner, m_first_char);
+        let mut storage = vec![0u64; (chars + units.len() * 2).div_ceil(8)];
+        let bytes = storage.as_mut_ptr() as *mut u8;
+        unsafe {
+            let length = bytes.add(offset_of!(Il2CppStringInner, m_string_length)) as *mut i32;
+            length.write(units.len() as i32);
+            std::ptr::copy_nonoverlapping(
+                units.as_ptr(),
+                bytes.add(chars) as *mut u16,
+                units.len(),
+            );
+        }
+        storage
+    }
+
+    fn view(storage: &mut [u64]) -> UnityString<'_> {
+        UnityString::from_ptr(storage.as_mut_ptr() as *mut Il2CppStringInner).unwrap()
+    }
+
+    #[test]
+    fn strings_past_32_characters_are_read_whole() {
+        let _reads = CheckedReads::set(false);
+        let text = "The quick brown fox jumps over the lazy dog, twice over";
+        let mut storage = managed(&text.encode_utf16().collect::<Vec<_>>());
+        let string = view(&mut storage);
+        assert_eq!(string.len(), text.len());
+        assert_eq!(string.to_string(), text);
+        assert_eq!(string.char_at(50), Some(text.as_bytes()[50] as u16));
+        assert_eq!(string.char_at(text.len()), None);
+        assert_eq!(format!("{:?}", string), format!("{:?}", text));
+    }
+
+    #[test]
+    fn surrogate_pairs_are_decoded() {
+        let _reads = CheckedReads::set(false);
+        let mut storage = managed(&"héllo 🌍".encode_utf16().collect::<Vec<_>>());
+        let string = view(&mut storage);
+        // the globe is two code units
+        assert_eq!(string.len(), 8);
+        assert_eq!(string.to_string(), "héllo 🌍");
+
+        // a lone high surrogate becomes U+FFFD
+        let mut storage = managed(&[b'a' as u16, 0xD83C, b'b' as u16]);
+        assert_eq!(view(&mut storage).to_string(), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn strings_compare_with_str() {
+        let _reads = CheckedReads::set(false);
+        let mut storage = managed(&"Player 🌍".encode_utf16().collect::<Vec<_>>());
+        let string = view(&mut storage);
+        assert!(string == "Player 🌍");
+        assert!(string == *"Player 🌍");
+        assert!(string != "Player");
+        assert!(string != "Player 🌍!");
+
+        let mut storage = managed(&[]);
+        let empty = view(&mut storage);
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_string(), "");
+        assert!(empty != "a");
+    }
+}
@@ -0,0 +1,211 @@
+use crate::il2cpp::classes::arg::Arg;
+use crate::il2cpp::classes::array::ArrayInner;
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp::classes::string::UnityStringInner;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+
+// A Rust type that can stand in for a managed parameter or return type in a native call
+pub trait NativeType: Sized {
+    /// Whether values of this Rust type can occupy a slot of the given managed type
+    fn matches(itype: &TypeInner) -> bool;
+    /// Human readable name used in signature mismatch errors
+    fn describe() -> &'static str;
+}
+
+macro_rules! impl_native_primitive {
+    ($($ty:ty => $($kind:ident)|+),* $(,)?) => {
+        $(
+            impl NativeType for $ty {
+                fn matches(itype: &TypeInner) -> bool {
+                    !itype.byref && matches!(itype.kind, $(TypeKind::$kind)|+)
+                }
+                fn describe() -> &'static str {
+                    stringify!($ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_native_primitive! {
+    () => Void,
+    bool => Boolean,
+    u16 => Char | U2,
+    i8 => I1,
+    u8 => U1,
+    i16 => I2,
+    i32 => I4,
+    u32 => U4,
+    i64 => I8,
+    u64 => U8,
+    f32 => R4,
+    f64 => R8,
+    isize => I,
+    usize => U,
+}
+
+impl NativeType for *mut ObjectInner {
+    fn matches(itype: &TypeInner) -> bool {
+        !itype.byref && !itype.value_type && itype.kind != TypeKind::Void
+    }
+    fn describe() -> &'static str {
+        "*mut ObjectInner"
+    }
+}
+
+impl NativeType for *mut UnityStringInner {
+    fn matches(itype: &TypeInner) -> bool {
+        !itype.byref && itype.kind == TypeKind::String
+    }
+    fn describe() -> &'static str {
+        "*mut UnityStringInner"
+    }
+}
+
+impl<T> NativeType for *mut ArrayInner<T> {
+    fn matches(itype: &TypeInner) -> bool {
+        !itype.byref && matches!(itype.kind, TypeKind::SzArray | TypeKind::Array)
+    }
+    fn describe() -> &'static str {
+        "*mut ArrayInner<T>"
+    }
+}
+
+// Escape hatch for any pointer sized slot: references, ref/out parameters and native pointers
+impl NativeType for *mut c_void {
+    fn matches(itype: &TypeInner) -> bool {
+        itype.byref
+            || !itype.value_type && itype.kind != TypeKind::Void
+            || matches!(itype.kind, TypeKind::Ptr | TypeKind::I | TypeKind::U)
+    }
+    fn describe() -> &'static str {
+        "*mut c_void"
+    }
+}
+
+// A tuple of NativeType arguments, checked against the method parameters
+pub trait NativeArgs {
+    const LEN: usize;
+    fn check(params: &[Arg]) -> Result<(), String>;
+}
+
+fn check_param<T: NativeType>(param: &Arg) -> Result<(), String> {
+    if T::matches(&param.itype) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Parameter {} of type {} cannot be passed as {}",
+            param.name,
+            param.itype.name,
+            T::describe()
+        ))
+    }
+}
+
+/// A method pointer validated against a Rust signature.
+/// Calls append the hidden `const MethodInfo*` argument il2cpp passes to every method.
+pub struct Thunk<Args, Ret> {
+    function: *mut u8,
+    method: *mut u8,
+    is_static: bool,
+    _marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args: NativeArgs, Ret: NativeType> Thunk<Args, Ret> {
+    pub(crate) fn new(
+        function: *mut u8,
+        method: *mut u8,
+        is_static: bool,
+        params: &[Arg],
+        return_type: &TypeInner,
+    ) -> Result<Self, String> {
+        if function.is_null() {
+            return Err("invalid ptr".to_string());
+        }
+        if params.len() != Args::LEN {
+            return Err(format!(
+                "Signature has {} arguments but the method has {}",
+                Args::LEN,
+                params.len()
+            ));
+        }
+        Args::check(params)?;
+        if !Ret::matches(return_type) {
+            return Err(format!(
+                "Return type {} cannot be read as {}",
+                return_type.name,
+                Ret::describe()
+            ));
+        }
+        Ok(Self {
+            function,
+            method,
+            is_static,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+}
+
+impl<Args, Ret> Clone for Thunk<Args, Ret> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args, Ret> Copy for Thunk<Args, Ret> {}
+
+macro_rules! impl_thunk_arity {
+    ($($A:ident $idx:tt),*) => {
+        impl<$($A: NativeType),*> NativeArgs for ($($A,)*) {
+            const LEN: usize = <[&str]>::len(&[$(stringify!($A)),*]);
+
+            #[allow(unused_variables)]
+            fn check(params: &[Arg]) -> Result<(), String> {
+                $( check_param::<$A>(&params[$idx])?; )*
+                Ok(())
+            }
+        }
+
+        impl<Ret: NativeType, $($A: NativeType),*> Thunk<($($A,)*), Ret> {
+            /// Call the native method. `this` is ignored for static methods.
+            ///
+            /// # Safety
+            /// `this` must point to a live instance of the declaring class for
+            /// instance methods, and the arguments must be valid for the callee.
+            #[allow(unused_variables)]
+            pub unsafe fn call(&self, this: *mut ObjectInner, args: ($($A,)*)) -> Ret {
+                let method = self.method as *const u8;
+                unsafe {
+                    if self.is_static {
+                        let f: unsafe extern "C" fn($($A,)* *const u8) -> Ret =
+                            std::mem::transmute(self.function);
+                        f($(args.$idx,)* method)
+                    } else {
+                        let f: unsafe extern "C" fn(*mut ObjectInner, $($A,)* *const u8) -> Ret =
+                            std::mem::transmute(self.function);
+                        f(this, $(args.$idx,)* method)
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_thunk_arity!();
+impl_thunk_arity!(A0 0);
+impl_thunk_arity!(A0 0, A1 1);
+impl_thunk_arity!(A0 0, A1 1, A2 2);
+impl_thunk_arity!(A0 0, A1 1, A2 2, A3 3);
+impl_thunk_arity!(A0 0, A1 1, A2 2, A3 3, A4 4);
+impl_thunk_arity!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5);
+impl_thunk_arity!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6);
+impl_thunk_arity!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7);
+
+unsafe impl<Args, Ret> Send for Thunk<Args, Ret> {}
+unsafe impl<Args, Ret> Sync for Thunk<Args, Ret> {}
@@ -1,6 +1,7 @@
 use crate::{il2cpp::classes::component::ComponentInner, il2cpp_view};
 
 il2cpp_view! {
+    #[il2cpp(class = "UnityEngine.Transform", assembly = "UnityEngine.CoreModule.dll")]
     pub struct Transform {
         pub component: ComponentInner,
     }
@@ -3,6 +3,7 @@ use crate::{il2cpp::classes::object::{ObjectInner}, il2cpp_view};
 
 
 il2cpp_view! {
+    #[il2cpp(class = "UnityEngine.Object", assembly = "UnityEngine.CoreModule.dll")]
     pub struct UnityObject {
         pub obj: ObjectInner,
         pub m_cached_ptr: *mut std::ffi::c_void,
@@ -0,0 +1,334 @@
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp::classes::string::UnityString;
+use crate::il2cpp::{
+    class_from_name, class_from_type, class_get_full_name, class_is_valuetype, class_value_size,
+    get_corlib, object_unbox, value_box,
+};
+
+// A managed value passed to or returned from `MethodInner::invoke`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Il2CppValue {
+    Void,
+    Boolean(bool),
+    Char(u16),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    IntPtr(isize),
+    UIntPtr(usize),
+    /// Any reference type (classes, strings, arrays, boxed values), may be null
+    Object(*mut ObjectInner),
+    /// Raw bytes of a struct or enum, laid out exactly as il2cpp stores it
+    ValueType(Vec<u8>),
+}
+
+macro_rules! impl_from_primitive {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Il2CppValue {
+                fn from(value: $ty) -> Self {
+                    Il2CppValue::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive! {
+    bool => Boolean,
+    i8 => I8,
+    u8 => U8,
+    i16 => I16,
+    u16 => U16,
+    i32 => I32,
+    u32 => U32,
+    i64 => I64,
+    u64 => U64,
+    f32 => F32,
+    f64 => F64,
+    isize => IntPtr,
+    usize => UIntPtr,
+    *mut ObjectInner => Object,
+}
+
+impl From<UnityString<'_>> for Il2CppValue {
+    fn from(value: UnityString<'_>) -> Self {
+        Il2CppValue::Object(value.as_il2cpp_object())
+    }
+}
+
+impl Il2CppValue {
+    /// Name of the `System` class this value boxes into, for primitives only
+    pub fn boxed_class_name(&self) -> Option<&'static str> {
+        match self {
+            Il2CppValue::Boolean(_) => Some("Boolean"),
+            Il2CppValue::Char(_) => Some("Char"),
+            Il2CppValue::I8(_) => Some("SByte"),
+            Il2CppValue::U8(_) => Some("Byte"),
+            Il2CppValue::I16(_) => Some("Int16"),
+            Il2CppValue::U16(_) => Some("UInt16"),
+            Il2CppValue::I32(_) => Some("Int32"),
+            Il2CppValue::U32(_) => Some("UInt32"),
+            Il2CppValue::I64(_) => Some("Int64"),
+            Il2CppValue::U64(_) => Some("UInt64"),
+            Il2CppValue::F32(_) => Some("Single"),
+            Il2CppValue::F64(_) => Some("Double"),
+            Il2CppValue::IntPtr(_) => Some("IntPtr"),
+            Il2CppValue::UIntPtr(_) => Some("UIntPtr"),
+            _ => None,
+        }
+    }
+
+    /// Managed type name as reported by il2cpp_type_get_name, e.g. `System.Int32`.
+    /// `None` for null objects and raw value types, whose type cannot be known.
+    pub fn type_name(&self) -> Option<String> {
+        match self {
+            Il2CppValue::Object(obj) if obj.is_null() => None,
+            Il2CppValue::Object(obj) => class_get_full_name(unsafe { (**obj).klass }).ok(),
+            other => other.boxed_class_name().map(|n| format!("System.{}", n)),
+        }
+    }
+
+    /// Whether the value can be passed for a parameter of type `itype`, see `check_param`
+    pub fn fits(&self, itype: &TypeInner) -> bool {
+        self.check_param(itype).is_ok()
+    }
+
+    /// Check the value against a parameter type before il2cpp reads it: primitives must
+    /// be the exact primitive, structs (by value or `ref`/`out`) exactly as large as the
+    /// type, and only `object`, `System.ValueType` and `System.Enum` parameters box
+    /// primitives.
+    pub fn check_param(&self, itype: &TypeInner) -> Result<(), String> {
+        let mismatch = || format!("Cannot pass {:?} as {}", self, itype.name);
+        if matches!(self, Il2CppValue::Void) {
+            return Err(mismatch());
+        }
+        if !itype.value_type {
+            // reference slots, by value or by reference
+            return match self {
+                Il2CppValue::Object(_) => Ok(()),
+                _ if !itype.byref && self.boxed_class_name().is_some() && accepts_boxed(itype) => {
+                    Ok(())
+                }
+                _ => Err(mismatch()),
+            };
+        }
+        if itype.kind.is_primitive() {
+            return match self {
+                Il2CppValue::Object(obj) if !itype.byref => check_boxed(*obj, itype),
+                _ if primitive_kind(self) == Some(itype.kind) => Ok(()),
+                _ => Err(mismatch()),
+            };
+        }
+        // structs and enums, by reference the callee may write the whole struct pointed to
+        match self {
+            Il2CppValue::Object(obj) if !itype.byref => check_boxed(*obj, itype),
+            Il2CppValue::Object(_) => Err(mismatch()),
+            _ => {
+                let size = if itype.byref {
+                    referenced_size(itype)?
+                } else {
+                    usize::try_from(itype.size)
+                        .ok()
+                        .filter(|size| *size > 0)
+                        .ok_or_else(|| format!("Size of {} is unknown", itype.name))?
+                };
+                match self.inline_size() {
+                    Some(len) if len == size => Ok(()),
+                    Some(len) => Err(format!(
+                        "{} is {} bytes, the value passed is {}",
+                        itype.name, size, len
+                    )),
+                    None => Err(mismatch()),
+                }
+            }
+        }
+    }
+
+    /// Bytes of inline storage, `None` for references and `Void`
+    fn inline_size(&self) -> Option<usize> {
+        Some(match self {
+            Il2CppValue::Boolean(_) | Il2CppValue::I8(_) | Il2CppValue::U8(_) => 1,
+            Il2CppValue::Char(_) | Il2CppValue::I16(_) | Il2CppValue::U16(_) => 2,
+            Il2CppValue::I32(_) | Il2CppValue::U32(_) | Il2CppValue::F32(_) => 4,
+            Il2CppValue::I64(_) | Il2CppValue::U64(_) | Il2CppValue::F64(_) => 8,
+            Il2CppValue::IntPtr(_) | Il2CppValue::UIntPtr(_) => std::mem::size_of::<usize>(),
+            Il2CppValue::ValueType(bytes) => bytes.len(),
+            Il2CppValue::Void | Il2CppValue::Object(_) => return None,
+        })
+    }
+
+    pub fn as_object(&self) -> Option<*mut ObjectInner> {
+        match self {
+            Il2CppValue::Object(obj) => Some(*obj),
+            _ => None,
+        }
+    }
+
+    /// Pointer to the inline storage of this value, what il2cpp expects for value type slots
+    fn data_ptr(&mut self) -> Option<*mut u8> {
+        Some(match self {
+            Il2CppValue::Boolean(v) => v as *mut bool as *mut u8,
+            Il2CppValue::Char(v) => v as *mut u16 as *mut u8,
+            Il2CppValue::I8(v) => v as *mut i8 as *mut u8,
+            Il2CppValue::U8(v) => v as *mut u8,
+            Il2CppValue::I16(v) => v as *mut i16 as *mut u8,
+            Il2CppValue::U16(v) => v as *mut u16 as *mut u8,
+            Il2CppValue::I32(v) => v as *mut i32 as *mut u8,
+            Il2CppValue::U32(v) => v as *mut u32 as *mut u8,
+            Il2CppValue::I64(v) => v as *mut i64 as *mut u8,
+            Il2CppValue::U64(v) => v as *mut u64 as *mut u8,
+            Il2CppValue::F32(v) => v as *mut f32 as *mut u8,
+            Il2CppValue::F64(v) => v as *mut f64 as *mut u8,
+            Il2CppValue::IntPtr(v) => v as *mut isize as *mut u8,
+            Il2CppValue::UIntPtr(v) => v as *mut usize as *mut u8,
+            Il2CppValue::ValueType(bytes) => bytes.as_mut_ptr(),
+            Il2CppValue::Void | Il2CppValue::Object(_) => return None,
+        })
+    }
+
+    /// Box a primitive into its `System` class, objects are returned as-is
+    pub fn boxed(&mut self) -> Result<*mut ObjectInner, String> {
+        if let Il2CppValue::Object(obj) = self {
+            return Ok(*obj);
+        }
+        let class_name = self
+            .boxed_class_name()
+            .ok_or_else(|| format!("Cannot box {:?} without its class", self))?;
+        let klass = class_from_name(get_corlib()?, "System", class_name)?;
+        if klass.is_null() {
+            return Err(format!("Failed to find System.{}", class_name));
+        }
+        let data = self.data_ptr().unwrap();
+        Ok(value_box(klass, data)? as *mut ObjectInner)
+    }
+
+    /// Build the pointer il2cpp_runtime_invoke expects for a parameter of type `itype`,
+    /// after `check_param`. The returned pointer may point into `self`, which must outlive
+    /// the call; values written through `ref`/`out` parameters land in `self`.
+    pub fn as_param(&mut self, itype: &TypeInner) -> Result<*mut u8, String> {
+        self.check_param(itype)?;
+        if itype.byref {
+            // ref/out parameters always receive the address of the storage slot
+            return Ok(match self {
+                Il2CppValue::Object(obj) => obj as *mut *mut ObjectInner as *mut u8,
+                other => other
+                    .data_ptr()
+                    .ok_or_else(|| format!("Cannot pass {:?} by reference", other))?,
+            });
+        }
+
+        if itype.value_type {
+            return match self {
+                // a boxed value can be passed where the unboxed value is expected
+                Il2CppValue::Object(obj) => object_unbox(*obj as *mut u8),
+                other => other
+                    .data_ptr()
+                    .ok_or_else(|| format!("Cannot pass {:?} as {}", other, itype.name)),
+            };
+        }
+
+        Ok(self.boxed()? as *mut u8)
+    }
+
+    /// Decode the object returned by il2cpp_runtime_invoke for a method returning `itype`
+    pub fn from_return(ret: *mut ObjectInner, itype: &TypeInner) -> Result<Self, String> {
+        if itype.is_void() {
+            return Ok(Il2CppValue::Void);
+        }
+        if !itype.value_type || itype.byref {
+            return Ok(Il2CppValue::Object(ret));
+        }
+
+        let data = object_unbox(ret as *mut u8)?;
+        unsafe {
+            Ok(match itype.kind {
+                TypeKind::Boolean => Il2CppValue::Boolean(*data != 0),
+                TypeKind::Char => Il2CppValue::Char(*(data as *const u16)),
+                TypeKind::I1 => Il2CppValue::I8(*(data as *const i8)),
+                TypeKind::U1 => Il2CppValue::U8(*data),
+                TypeKind::I2 => Il2CppValue::I16(*(data as *const i16)),
+                TypeKind::U2 => Il2CppValue::U16(*(data as *const u16)),
+                TypeKind::I4 => Il2CppValue::I32(*(data as *const i32)),
+                TypeKind::U4 => Il2CppValue::U32(*(data as *const u32)),
+                TypeKind::I8 => Il2CppValue::I64(*(data as *const i64)),
+                TypeKind::U8 => Il2CppValue::U64(*(data as *const u64)),
+                TypeKind::R4 => Il2CppValue::F32(*(data as *const f32)),
+                TypeKind::R8 => Il2CppValue::F64(*(data as *const f64)),
+                TypeKind::I => Il2CppValue::IntPtr(*(data as *const isize)),
+                TypeKind::U => Il2CppValue::UIntPtr(*(data as *const usize)),
+                _ => {
+                    let size = usize::try_from(itype.size)
+                        .map_err(|_| format!("Unknown size for {}", itype.name))?;
+                    Il2CppValue::ValueType(std::slice::from_raw_parts(data, size).to_vec())
+                }
+            })
+        }
+    }
+}
+
+// The primitive kind a value is, `None` for objects, structs and `Void`
+fn primitive_kind(value: &Il2CppValue) -> Option<TypeKind> {
+    Some(match value {
+        Il2CppValue::Boolean(_) => TypeKind::Boolean,
+        Il2CppValue::Char(_) => TypeKind::Char,
+        Il2CppValue::I8(_) => TypeKind::I1,
+        Il2CppValue::U8(_) => TypeKind::U1,
+        Il2CppValue::I16(_) => TypeKind::I2,
+        Il2CppValue::U16(_) => TypeKind::U2,
+        Il2CppValue::I32(_) => TypeKind::I4,
+        Il2CppValue::U32(_) => TypeKind::U4,
+        Il2CppValue::I64(_) => TypeKind::I8,
+        Il2CppValue::U64(_) => TypeKind::U8,
+        Il2CppValue::F32(_) => TypeKind::R4,
+        Il2CppValue::F64(_) => TypeKind::R8,
+        Il2CppValue::IntPtr(_) => TypeKind::I,
+        Il2CppValue::UIntPtr(_) => TypeKind::U,
+        _ => return None,
+    })
+}
+
+// Size of the struct a `ref`/`out` parameter points to, the slot itself is a pointer.
+// Types without a runtime handle (snapshots, offline caches) cannot be resolved.
+fn referenced_size(itype: &TypeInner) -> Result<usize, String> {
+    let unknown = || format!("Size of {} is unknown", itype.name);
+    if itype.address.is_null() {
+        return Err(unknown());
+    }
+    let size = class_value_size(class_from_type(itype.address)?)?;
+    usize::try_from(size)
+        .ok()
+        .filter(|size| *size > 0)
+        .ok_or_else(unknown)
+}
+
+// Reference parameters a primitive may be boxed for
+fn accepts_boxed(itype: &TypeInner) -> bool {
+    itype.kind == TypeKind::Object
+        || itype.name == "System.ValueType"
+        || itype.name == "System.Enum"
+}
+
+// A boxed value passed where the unboxed value is expected must hold as many bytes
+fn check_boxed(obj: *mut ObjectInner, itype: &TypeInner) -> Result<(), String> {
+    if obj.is_null() {
+        return Err(format!("Cannot pass null as {}", itype.name));
+    }
+    let klass = unsafe { (*obj).klass };
+    if !class_is_valuetype(klass)? || class_value_size(klass)? as isize != itype.size {
+        return Err(format!(
+            "Cannot pass a boxed {} as {}",
+            class_get_full_name(klass)?,
+            itype.name
+        ));
+    }
+    Ok(())
+}
@@ -37,3 +37,45 @@ pub type Il2CppMethodGetParamFn =
     unsafe extern "C" fn(method: Il2CppMethodInfo, index: u32) -> Il2CppType;
 pub type Il2CppTypeGetNameFn = unsafe extern "C" fn(itype: Il2CppType) -> *const i8;
 pub type Il2CppTypeGetObjectFn = unsafe extern "C" fn(itype: Il2CppType) -> Il2CppObject;
+pub type Il2CppFieldGetFlagsFn = unsafe extern "C" fn(field: Il2CppFieldInfo) -> i32;
+pub type Il2CppFieldStaticGetValueFn = unsafe extern "C" fn(field: Il2CppFieldInfo, value: *mut u8);
+pub type Il2CppFieldStaticSetValueFn = unsafe extern "C" fn(field: Il2CppFieldInfo, value: *mut u8);
+pub type Il2CppRuntimeClassInitFn = unsafe extern "C" fn(klass: Il2CppClass);
+pub type Il2CppTypeGetTypeFn = unsafe extern "C" fn(itype: Il2CppType) -> i32;
+pub type Il2CppTypeIsByrefFn = unsafe extern "C" fn(itype: Il2CppType) -> bool;
+pub type Il2CppClassFromIl2CppTypeFn = unsafe extern "C" fn(itype: Il2CppType) -> Il2CppClass;
+pub type Il2CppClassIsValueTypeFn = unsafe extern "C" fn(klass: Il2CppClass) -> bool;
+pub type Il2CppClassValueSizeFn = unsafe extern "C" fn(klass: Il2CppClass, align: *mut u32) -> i32;
+pub type Il2CppGetCorlibFn = unsafe extern "C" fn() -> Il2CppImage;
+pub type Il2CppRuntimeInvokeFn = unsafe extern "C" fn(
+    method: Il2CppMethodInfo,
+    obj: *mut u8,
+    params: *mut *mut u8,
+    exc: *mut Il2CppObject,
+) -> Il2CppObject;
+pub type Il2CppObjectUnboxFn = unsafe extern "C" fn(obj: Il2CppObject) -> *mut u8;
+pub type Il2CppValueBoxFn = unsafe extern "C" fn(klass: Il2CppClass, data: *mut u8) -> Il2CppObject;
+pub type Il2CppFormatExceptionFn =
+    unsafe extern "C" fn(ex: Il2CppObject, message: *mut i8, message_size: i32);
+pub type Il2CppFormatStackTraceFn =
+    unsafe extern "C" fn(ex: Il2CppObject, output: *mut i8, output_size: i32);
+pub type Il2CppObjectNewFn = unsafe extern "C" fn(klass: Il2CppClass) -> Il2CppObject;
+pub type Il2CppStringNewFn = unsafe extern "C" fn(text: *const i8) -> Il2CppString;
+pub type Il2CppArrayNewFn =
+    unsafe extern "C" fn(element_class: Il2CppClass, length: usize) -> Il2CppArray;
+pub type Il2CppArrayElementSizeFn = unsafe extern "C" fn(array_class: Il2CppClass) -> i32;
+pub type Il2CppClassGetElementClassFn = unsafe extern "C" fn(klass: Il2CppClass) -> Il2CppClass;
+pub type Il2CppClassGetDeclaringTypeFn = unsafe extern "C" fn(klass: Il2CppClass) -> Il2CppClass;
+pub type Il2CppClassGetRankFn = unsafe extern "C" fn(klass: Il2CppClass) -> i32;
+pub type Il2CppGcWbarrierSetFieldFn =
+    unsafe extern "C" fn(obj: Il2CppObject, target: *mut *mut u8, value: *mut u8);
+pub type Il2CppGcHandleNewFn = unsafe extern "C" fn(obj: Il2CppObject, pinned: bool) -> u32;
+pub type Il2CppGcHandleNewWeakRefFn =
+    unsafe extern "C" fn(obj: Il2CppObject, track_resurrection: bool) -> u32;
+pub type Il2CppGcHandleGetTargetFn = unsafe extern "C" fn(gchandle: u32) -> Il2CppObject;
+pub type Il2CppGcHandleFreeFn = unsafe extern "C" fn(gchandle: u32);
+pub type Il2CppClassIsAssignableFromFn =
+    unsafe extern "C" fn(klass: Il2CppClass, oklass: Il2CppClass) -> bool;
+pub type Il2CppArrayObjectHeaderSizeFn = unsafe extern "C" fn() -> u32;
+pub type Il2CppClassGetFlagsFn = unsafe extern "C" fn(klass: Il2CppClass) -> i32;
+pub type Il2CppClassIsEnumFn = unsafe extern "C" fn(klass: Il2CppClass) -> bool;
@@ -1,8 +1,7 @@
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::{Arc, LazyLock};
-use win32_sys::{get_module_from_name, resolve_function_ptr_from_name};
-use windows::Win32::Foundation::{FARPROC, HMODULE};
+use win32_sys::{FARPROC, HMODULE, get_module_from_name, resolve_function_ptr_from_name};
 
 pub mod c_types;
 pub mod fn_types;
@@ -41,6 +40,37 @@ struct Il2CppFunctions {
     pub method_get_param: Option<Il2CppMethodGetParamFn>,
     pub type_get_name: Option<Il2CppTypeGetNameFn>,
     pub type_get_object: Option<Il2CppTypeGetObjectFn>,
+    pub field_get_flags: Option<Il2CppFieldGetFlagsFn>,
+    pub field_static_get_value: Option<Il2CppFieldStaticGetValueFn>,
+    pub field_static_set_value: Option<Il2CppFieldStaticSetValueFn>,
+    pub runtime_class_init: Option<Il2CppRuntimeClassInitFn>,
+    pub type_get_type: Option<Il2CppTypeGetTypeFn>,
+    pub type_is_byref: Option<Il2CppTypeIsByrefFn>,
+    pub class_from_il2cpp_type: Option<Il2CppClassFromIl2CppTypeFn>,
+    pub class_is_valuetype: Option<Il2CppClassIsValueTypeFn>,
+    pub class_value_size: Option<Il2CppClassValueSizeFn>,
+    pub get_corlib: Option<Il2CppGetCorlibFn>,
+    pub runtime_invoke: Option<Il2CppRuntimeInvokeFn>,
+    pub object_unbox: Option<Il2CppObjectUnboxFn>,
+    pub value_box: Option<Il2CppValueBoxFn>,
+    pub format_exception: Option<Il2CppFormatExceptionFn>,
+    pub format_stack_trace: Option<Il2CppFormatStackTraceFn>,
+    pub object_new: Option<Il2CppObjectNewFn>,
+    pub string_new: Option<Il2CppStringNewFn>,
+    pub array_new: Option<Il2CppArrayNewFn>,
+    pub array_element_size: Option<Il2CppArrayElementSizeFn>,
+    pub class_get_element_class: Option<Il2CppClassGetElementClassFn>,
+    pub class_get_declaring_type: Option<Il2CppClassGetDeclaringTypeFn>,
+    pub class_get_rank: Option<Il2CppClassGetRankFn>,
+    pub gc_wbarrier_set_field: Option<Il2CppGcWbarrierSetFieldFn>,
+    pub gchandle_new: Option<Il2CppGcHandleNewFn>,
+    pub gchandle_new_weakref: Option<Il2CppGcHandleNewWeakRefFn>,
+    pub gchandle_get_target: Option<Il2CppGcHandleGetTargetFn>,
+    pub gchandle_free: Option<Il2CppGcHandleFreeFn>,
+    pub class_is_assignable_from: Option<Il2CppClassIsAssignableFromFn>,
+    pub array_object_header_size: Option<Il2CppArrayObjectHeaderSizeFn>,
+    pub class_get_flags: Option<Il2CppClassGetFlagsFn>,
+    pub class_is_enum: Option<Il2CppClassIsEnumFn>,
 }
 
 impl Il2CppFunctions {
@@ -75,6 +105,37 @@ impl Il2CppFunctions {
             method_get_param: None,
             type_get_name: None,
             type_get_object: None,
+            field_get_flags: None,
+            field_static_get_value: None,
+            field_static_set_value: None,
+            runtime_class_init: None,
+            type_get_type: None,
+            type_is_byref: None,
+            class_from_il2cpp_type: None,
+            class_is_valuetype: None,
+            class_value_size: None,
+            get_corlib: None,
+            runtime_invoke: None,
+            object_unbox: None,
+            value_box: None,
+            format_exception: None,
+            format_stack_trace: None,
+            object_new: None,
+            string_new: None,
+            array_new: None,
+            array_element_size: None,
+            class_get_element_class: None,
+            class_get_declaring_type: None,
+            class_get_rank: None,
+            gc_wbarrier_set_field: None,
+            gchandle_new: None,
+            gchandle_new_weakref: None,
+            gchandle_get_target: None,
+            gchandle_free: None,
+            class_is_assignable_from: None,
+            array_object_header_size: None,
+            class_get_flags: None,
+            class_is_enum: None,
         }
     }
 }
@@ -245,6 +306,73 @@ impl Il2CppDll {
             Some(self.invoke_mut::<Il2CppTypeGetNameFn>("il2cpp_type_get_name")?);
         self.functions.type_get_object =
             Some(self.invoke_mut::<Il2CppTypeGetObjectFn>("il2cpp_type_get_object")?);
+        self.functions.field_get_flags =
+            Some(self.invoke_mut::<Il2CppFieldGetFlagsFn>("il2cpp_field_get_flags")?);
+        self.functions.field_static_get_value =
+            Some(self.invoke_mut::<Il2CppFieldStaticGetValueFn>("il2cpp_field_static_get_value")?);
+        self.functions.field_static_set_value =
+            Some(self.invoke_mut::<Il2CppFieldStaticSetValueFn>("il2cpp_field_static_set_value")?);
+        self.functions.runtime_class_init =
+            Some(self.invoke_mut::<Il2CppRuntimeClassInitFn>("il2cpp_runtime_class_init")?);
+        self.functions.type_get_type =
+            Some(self.invoke_mut::<Il2CppTypeGetTypeFn>("il2cpp_type_get_type")?);
+        self.functions.type_is_byref =
+            Some(self.invoke_mut::<Il2CppTypeIsByrefFn>("il2cpp_type_is_byref")?);
+        self.functions.class_from_il2cpp_type =
+            Some(self.invoke_mut::<Il2CppClassFromIl2CppTypeFn>("il2cpp_class_from_il2cpp_type")?);
+        self.functions.class_is_valuetype =
+            Some(self.invoke_mut::<Il2CppClassIsValueTypeFn>("il2cpp_class_is_valuetype")?);
+        self.functions.class_value_size =
+            Some(self.invoke_mut::<Il2CppClassValueSizeFn>("il2cpp_class_value_size")?);
+        self.functions.get_corlib =
+            Some(self.invoke_mut::<Il2CppGetCorlibFn>("il2cpp_get_corlib")?);
+        self.functions.runtime_invoke =
+            Some(self.invoke_mut::<Il2CppRuntimeInvokeFn>("il2cpp_runtime_invoke")?);
+        self.functions.object_unbox =
+            Some(self.invoke_mut::<Il2CppObjectUnboxFn>("il2cpp_object_unbox")?);
+        self.functions.value_box = Some(self.invoke_mut::<Il2CppValueBoxFn>("il2cpp_value_box")?);
+        self.functions.format_exception =
+            Some(self.invoke_mut::<Il2CppFormatExceptionFn>("il2cpp_format_exception")?);
+        self.functions.format_stack_trace =
+            Some(self.invoke_mut::<Il2CppFormatStackTraceFn>("il2cpp_format_stack_trace")?);
+        self.functions.object_new =
+            Some(self.invoke_mut::<Il2CppObjectNewFn>("il2cpp_object_new")?);
+        self.functions.string_new =
+            Some(self.invoke_mut::<Il2CppStringNewFn>("il2cpp_string_new")?);
+        self.functions.array_new = Some(self.invoke_mut::<Il2CppArrayNewFn>("il2cpp_array_new")?);
+        self.functions.array_element_size =
+            Some(self.invoke_mut::<Il2CppArrayElementSizeFn>("il2cpp_array_element_size")?);
+        self.functions.class_get_element_class = Some(
+            self.invoke_mut::<Il2CppClassGetElementClassFn>("il2cpp_class_get_element_class")?,
+        );
+        self.functions.class_get_declaring_type = Some(
+            self.invoke_mut::<Il2CppClassGetDeclaringTypeFn>("il2cpp_class_get_declaring_type")?,
+        );
+        self.functions.class_get_rank =
+            Some(self.invoke_mut::<Il2CppClassGetRankFn>("il2cpp_class_get_rank")?);
+        // optional, only exported by some il2cpp versions
+        self.functions.gc_wbarrier_set_field =
+            self.invoke_mut::<Option<Il2CppGcWbarrierSetFieldFn>>("il2cpp_gc_wbarrier_set_field")?;
+        self.functions.gchandle_new =
+            Some(self.invoke_mut::<Il2CppGcHandleNewFn>("il2cpp_gchandle_new")?);
+        self.functions.gchandle_new_weakref =
+            Some(self.invoke_mut::<Il2CppGcHandleNewWeakRefFn>("il2cpp_gchandle_new_weakref")?);
+        self.functions.gchandle_get_target =
+            Some(self.invoke_mut::<Il2CppGcHandleGetTargetFn>("il2cpp_gchandle_get_target")?);
+        self.functions.gchandle_free =
+            Some(self.invoke_mut::<Il2CppGcHandleFreeFn>("il2cpp_gchandle_free")?);
+        self.functions.class_is_assignable_from = Some(
+            self.invoke_mut::<Il2CppClassIsAssignableFromFn>("il2cpp_class_is_assignable_from")?,
+        );
+        // optional, only exported by some il2cpp versions
+        self.functions.array_object_header_size = self
+            .invoke_mut::<Option<Il2CppArrayObjectHeaderSizeFn>>(
+                "il2cpp_array_object_header_size",
+            )?;
+        self.functions.class_get_flags =
+            Some(self.invoke_mut::<Il2CppClassGetFlagsFn>("il2cpp_class_get_flags")?);
+        self.functions.class_is_enum =
+            Some(self.invoke_mut::<Il2CppClassIsEnumFn>("il2cpp_class_is_enum")?);
         Ok(())
     }
 
@@ -316,6 +444,91 @@ impl Il2CppDll {
             "il2cpp_type_get_object: {:?}",
             self.functions.type_get_object
         );
+        println!(
+            "il2cpp_field_get_flags: {:?}",
+            self.functions.field_get_flags
+        );
+        println!(
+            "il2cpp_field_static_get_value: {:?}",
+            self.functions.field_static_get_value
+        );
+        println!(
+            "il2cpp_field_static_set_value: {:?}",
+            self.functions.field_static_set_value
+        );
+        println!(
+            "il2cpp_runtime_class_init: {:?}",
+            self.functions.runtime_class_init
+        );
+        println!("il2cpp_type_get_type: {:?}", self.functions.type_get_type);
+        println!("il2cpp_type_is_byref: {:?}", self.functions.type_is_byref);
+        println!(
+            "il2cpp_class_from_il2cpp_type: {:?}",
+            self.functions.class_from_il2cpp_type
+        );
+        println!(
+            "il2cpp_class_is_valuetype: {:?}",
+            self.functions.class_is_valuetype
+        );
+        println!(
+            "il2cpp_class_value_size: {:?}",
+            self.functions.class_value_size
+        );
+        println!("il2cpp_get_corlib: {:?}", self.functions.get_corlib);
+        println!("il2cpp_runtime_invoke: {:?}", self.functions.runtime_invoke);
+        println!("il2cpp_object_unbox: {:?}", self.functions.object_unbox);
+        println!("il2cpp_value_box: {:?}", self.functions.value_box);
+        println!(
+            "il2cpp_format_exception: {:?}",
+            self.functions.format_exception
+        );
+        println!(
+            "il2cpp_format_stack_trace: {:?}",
+            self.functions.format_stack_trace
+        );
+        println!("il2cpp_object_new: {:?}", self.functions.object_new);
+        println!("il2cpp_string_new: {:?}", self.functions.string_new);
+        println!("il2cpp_array_new: {:?}", self.functions.array_new);
+        println!(
+            "il2cpp_array_element_size: {:?}",
+            self.functions.array_element_size
+        );
+        println!(
+            "il2cpp_class_get_element_class: {:?}",
+            self.functions.class_get_element_class
+        );
+        println!(
+            "il2cpp_class_get_declaring_type: {:?}",
+            self.functions.class_get_declaring_type
+        );
+        println!("il2cpp_class_get_rank: {:?}", self.functions.class_get_rank);
+        println!(
+            "il2cpp_gc_wbarrier_set_field: {:?}",
+            self.functions.gc_wbarrier_set_field
+        );
+        println!("il2cpp_gchandle_new: {:?}", self.functions.gchandle_new);
+        println!(
+            "il2cpp_gchandle_new_weakref: {:?}",
+            self.functions.gchandle_new_weakref
+        );
+        println!(
+            "il2cpp_gchandle_get_target: {:?}",
+            self.functions.gchandle_get_target
+        );
+        println!("il2cpp_gchandle_free: {:?}", self.functions.gchandle_free);
+        println!(
+            "il2cpp_class_is_assignable_from: {:?}",
+            self.functions.class_is_assignable_from
+        );
+        println!(
+            "il2cpp_array_object_header_size: {:?}",
+            self.functions.array_object_header_size
+        );
+        println!(
+            "il2cpp_class_get_flags: {:?}",
+            self.functions.class_get_flags
+        );
+        println!("il2cpp_class_is_enum: {:?}", self.functions.class_is_enum);
     }
 
     pub fn il2cpp_init(&self) -> Result<(), String> {
@@ -672,6 +885,477 @@ impl Il2CppDll {
             },
         }
     }
+
+    pub fn il2cpp_field_get_flags(&self, field: Il2CppFieldInfo) -> Result<i32, String> {
+        match self.functions.field_get_flags {
+            Some(field_get_flags) => Ok(unsafe { field_get_flags(field) }),
+            None => match self.invoke::<Il2CppFieldGetFlagsFn>("il2cpp_field_get_flags") {
+                Ok(field_get_flags) => Ok(unsafe { field_get_flags(field) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_field_get_flags: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_field_static_get_value(
+        &self,
+        field: Il2CppFieldInfo,
+        value: *mut u8,
+    ) -> Result<(), String> {
+        match self.functions.field_static_get_value {
+            Some(field_static_get_value) => {
+                unsafe { field_static_get_value(field, value) };
+                Ok(())
+            }
+            None => match self
+                .invoke::<Il2CppFieldStaticGetValueFn>("il2cpp_field_static_get_value")
+            {
+                Ok(field_static_get_value) => {
+                    unsafe { field_static_get_value(field, value) };
+                    Ok(())
+                }
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_field_static_get_value: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_field_static_set_value(
+        &self,
+        field: Il2CppFieldInfo,
+        value: *mut u8,
+    ) -> Result<(), String> {
+        match self.functions.field_static_set_value {
+            Some(field_static_set_value) => {
+                unsafe { field_static_set_value(field, value) };
+                Ok(())
+            }
+            None => match self
+                .invoke::<Il2CppFieldStaticSetValueFn>("il2cpp_field_static_set_value")
+            {
+                Ok(field_static_set_value) => {
+                    unsafe { field_static_set_value(field, value) };
+                    Ok(())
+                }
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_field_static_set_value: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_runtime_class_init(&self, klass: Il2CppClass) -> Result<(), String> {
+        match self.functions.runtime_class_init {
+            Some(runtime_class_init) => {
+                unsafe { runtime_class_init(klass) };
+                Ok(())
+            }
+            None => match self.invoke::<Il2CppRuntimeClassInitFn>("il2cpp_runtime_class_init") {
+                Ok(runtime_class_init) => {
+                    unsafe { runtime_class_init(klass) };
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to invoke il2cpp_runtime_class_init: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_type_get_type(&self, itype: Il2CppType) -> Result<i32, String> {
+        match self.functions.type_get_type {
+            Some(type_get_type) => Ok(unsafe { type_get_type(itype) }),
+            None => match self.invoke::<Il2CppTypeGetTypeFn>("il2cpp_type_get_type") {
+                Ok(type_get_type) => Ok(unsafe { type_get_type(itype) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_type_get_type: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_type_is_byref(&self, itype: Il2CppType) -> Result<bool, String> {
+        match self.functions.type_is_byref {
+            Some(type_is_byref) => Ok(unsafe { type_is_byref(itype) }),
+            None => match self.invoke::<Il2CppTypeIsByrefFn>("il2cpp_type_is_byref") {
+                Ok(type_is_byref) => Ok(unsafe { type_is_byref(itype) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_type_is_byref: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_from_il2cpp_type(&self, itype: Il2CppType) -> Result<Il2CppClass, String> {
+        match self.functions.class_from_il2cpp_type {
+            Some(class_from_il2cpp_type) => Ok(unsafe { class_from_il2cpp_type(itype) }),
+            None => {
+                match self.invoke::<Il2CppClassFromIl2CppTypeFn>("il2cpp_class_from_il2cpp_type") {
+                    Ok(class_from_il2cpp_type) => Ok(unsafe { class_from_il2cpp_type(itype) }),
+                    Err(e) => Err(format!(
+                        "Failed to invoke il2cpp_class_from_il2cpp_type: {}",
+                        e
+                    )),
+                }
+            }
+        }
+    }
+
+    pub fn il2cpp_class_is_valuetype(&self, klass: Il2CppClass) -> Result<bool, String> {
+        match self.functions.class_is_valuetype {
+            Some(class_is_valuetype) => Ok(unsafe { class_is_valuetype(klass) }),
+            None => match self.invoke::<Il2CppClassIsValueTypeFn>("il2cpp_class_is_valuetype") {
+                Ok(class_is_valuetype) => Ok(unsafe { class_is_valuetype(klass) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_class_is_valuetype: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_value_size(
+        &self,
+        klass: Il2CppClass,
+        align: *mut u32,
+    ) -> Result<i32, String> {
+        match self.functions.class_value_size {
+            Some(class_value_size) => Ok(unsafe { class_value_size(klass, align) }),
+            None => match self.invoke::<Il2CppClassValueSizeFn>("il2cpp_class_value_size") {
+                Ok(class_value_size) => Ok(unsafe { class_value_size(klass, align) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_class_value_size: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_get_corlib(&self) -> Result<Il2CppImage, String> {
+        match self.functions.get_corlib {
+            Some(get_corlib) => Ok(unsafe { get_corlib() }),
+            None => match self.invoke::<Il2CppGetCorlibFn>("il2cpp_get_corlib") {
+                Ok(get_corlib) => Ok(unsafe { get_corlib() }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_get_corlib: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_runtime_invoke(
+        &self,
+        method: Il2CppMethodInfo,
+        obj: *mut u8,
+        params: *mut *mut u8,
+        exc: *mut Il2CppObject,
+    ) -> Result<Il2CppObject, String> {
+        match self.functions.runtime_invoke {
+            Some(runtime_invoke) => Ok(unsafe { runtime_invoke(method, obj, params, exc) }),
+            None => match self.invoke::<Il2CppRuntimeInvokeFn>("il2cpp_runtime_invoke") {
+                Ok(runtime_invoke) => Ok(unsafe { runtime_invoke(method, obj, params, exc) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_runtime_invoke: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_object_unbox(&self, obj: Il2CppObject) -> Result<*mut u8, String> {
+        match self.functions.object_unbox {
+            Some(object_unbox) => Ok(unsafe { object_unbox(obj) }),
+            None => match self.invoke::<Il2CppObjectUnboxFn>("il2cpp_object_unbox") {
+                Ok(object_unbox) => Ok(unsafe { object_unbox(obj) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_object_unbox: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_value_box(
+        &self,
+        klass: Il2CppClass,
+        data: *mut u8,
+    ) -> Result<Il2CppObject, String> {
+        match self.functions.value_box {
+            Some(value_box) => Ok(unsafe { value_box(klass, data) }),
+            None => match self.invoke::<Il2CppValueBoxFn>("il2cpp_value_box") {
+                Ok(value_box) => Ok(unsafe { value_box(klass, data) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_value_box: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_format_exception(
+        &self,
+        ex: Il2CppObject,
+        message: *mut i8,
+        message_size: i32,
+    ) -> Result<(), String> {
+        match self.functions.format_exception {
+            Some(format_exception) => {
+                unsafe { format_exception(ex, message, message_size) };
+                Ok(())
+            }
+            None => match self.invoke::<Il2CppFormatExceptionFn>("il2cpp_format_exception") {
+                Ok(format_exception) => {
+                    unsafe { format_exception(ex, message, message_size) };
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to invoke il2cpp_format_exception: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_format_stack_trace(
+        &self,
+        ex: Il2CppObject,
+        output: *mut i8,
+        output_size: i32,
+    ) -> Result<(), String> {
+        match self.functions.format_stack_trace {
+            Some(format_stack_trace) => {
+                unsafe { format_stack_trace(ex, output, output_size) };
+                Ok(())
+            }
+            None => match self.invoke::<Il2CppFormatStackTraceFn>("il2cpp_format_stack_trace") {
+                Ok(format_stack_trace) => {
+                    unsafe { format_stack_trace(ex, output, output_size) };
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to invoke il2cpp_format_stack_trace: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_object_new(&self, klass: Il2CppClass) -> Result<Il2CppObject, String> {
+        match self.functions.object_new {
+            Some(object_new) => Ok(unsafe { object_new(klass) }),
+            None => match self.invoke::<Il2CppObjectNewFn>("il2cpp_object_new") {
+                Ok(object_new) => Ok(unsafe { object_new(klass) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_object_new: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_string_new(&self, text: *const i8) -> Result<Il2CppString, String> {
+        match self.functions.string_new {
+            Some(string_new) => Ok(unsafe { string_new(text) }),
+            None => match self.invoke::<Il2CppStringNewFn>("il2cpp_string_new") {
+                Ok(string_new) => Ok(unsafe { string_new(text) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_string_new: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_array_new(
+        &self,
+        element_class: Il2CppClass,
+        length: usize,
+    ) -> Result<Il2CppArray, String> {
+        match self.functions.array_new {
+            Some(array_new) => Ok(unsafe { array_new(element_class, length) }),
+            None => match self.invoke::<Il2CppArrayNewFn>("il2cpp_array_new") {
+                Ok(array_new) => Ok(unsafe { array_new(element_class, length) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_array_new: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_array_element_size(&self, array_class: Il2CppClass) -> Result<i32, String> {
+        match self.functions.array_element_size {
+            Some(array_element_size) => Ok(unsafe { array_element_size(array_class) }),
+            None => match self.invoke::<Il2CppArrayElementSizeFn>("il2cpp_array_element_size") {
+                Ok(array_element_size) => Ok(unsafe { array_element_size(array_class) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_array_element_size: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_get_element_class(
+        &self,
+        klass: Il2CppClass,
+    ) -> Result<Il2CppClass, String> {
+        match self.functions.class_get_element_class {
+            Some(class_get_element_class) => Ok(unsafe { class_get_element_class(klass) }),
+            None => match self
+                .invoke::<Il2CppClassGetElementClassFn>("il2cpp_class_get_element_class")
+            {
+                Ok(class_get_element_class) => Ok(unsafe { class_get_element_class(klass) }),
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_class_get_element_class: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_get_declaring_type(
+        &self,
+        klass: Il2CppClass,
+    ) -> Result<Il2CppClass, String> {
+        match self.functions.class_get_declaring_type {
+            Some(class_get_declaring_type) => Ok(unsafe { class_get_declaring_type(klass) }),
+            None => match self
+                .invoke::<Il2CppClassGetDeclaringTypeFn>("il2cpp_class_get_declaring_type")
+            {
+                Ok(class_get_declaring_type) => Ok(unsafe { class_get_declaring_type(klass) }),
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_class_get_declaring_type: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_get_rank(&self, klass: Il2CppClass) -> Result<i32, String> {
+        match self.functions.class_get_rank {
+            Some(class_get_rank) => Ok(unsafe { class_get_rank(klass) }),
+            None => match self.invoke::<Il2CppClassGetRankFn>("il2cpp_class_get_rank") {
+                Ok(class_get_rank) => Ok(unsafe { class_get_rank(klass) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_class_get_rank: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_gc_wbarrier_set_field(
+        &self,
+        obj: Il2CppObject,
+        target: *mut *mut u8,
+        value: *mut u8,
+    ) -> Result<(), String> {
+        match self.functions.gc_wbarrier_set_field {
+            Some(gc_wbarrier_set_field) => {
+                unsafe { gc_wbarrier_set_field(obj, target, value) };
+                Ok(())
+            }
+            None => match self
+                .invoke::<Option<Il2CppGcWbarrierSetFieldFn>>("il2cpp_gc_wbarrier_set_field")
+            {
+                Ok(Some(gc_wbarrier_set_field)) => {
+                    unsafe { gc_wbarrier_set_field(obj, target, value) };
+                    Ok(())
+                }
+                Ok(None) => Err(format!(
+                    "il2cpp_gc_wbarrier_set_field is not exported by {}",
+                    self.name
+                )),
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_gc_wbarrier_set_field: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_gchandle_new(&self, obj: Il2CppObject, pinned: bool) -> Result<u32, String> {
+        match self.functions.gchandle_new {
+            Some(gchandle_new) => Ok(unsafe { gchandle_new(obj, pinned) }),
+            None => match self.invoke::<Il2CppGcHandleNewFn>("il2cpp_gchandle_new") {
+                Ok(gchandle_new) => Ok(unsafe { gchandle_new(obj, pinned) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_gchandle_new: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_gchandle_new_weakref(
+        &self,
+        obj: Il2CppObject,
+        track_resurrection: bool,
+    ) -> Result<u32, String> {
+        match self.functions.gchandle_new_weakref {
+            Some(gchandle_new_weakref) => {
+                Ok(unsafe { gchandle_new_weakref(obj, track_resurrection) })
+            }
+            None => {
+                match self.invoke::<Il2CppGcHandleNewWeakRefFn>("il2cpp_gchandle_new_weakref") {
+                    Ok(gchandle_new_weakref) => {
+                        Ok(unsafe { gchandle_new_weakref(obj, track_resurrection) })
+                    }
+                    Err(e) => Err(format!(
+                        "Failed to invoke il2cpp_gchandle_new_weakref: {}",
+                        e
+                    )),
+                }
+            }
+        }
+    }
+
+    pub fn il2cpp_gchandle_get_target(&self, gchandle: u32) -> Result<Il2CppObject, String> {
+        match self.functions.gchandle_get_target {
+            Some(gchandle_get_target) => Ok(unsafe { gchandle_get_target(gchandle) }),
+            None => match self.invoke::<Il2CppGcHandleGetTargetFn>("il2cpp_gchandle_get_target") {
+                Ok(gchandle_get_target) => Ok(unsafe { gchandle_get_target(gchandle) }),
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_gchandle_get_target: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_gchandle_free(&self, gchandle: u32) -> Result<(), String> {
+        match self.functions.gchandle_free {
+            Some(gchandle_free) => {
+                unsafe { gchandle_free(gchandle) };
+                Ok(())
+            }
+            None => match self.invoke::<Il2CppGcHandleFreeFn>("il2cpp_gchandle_free") {
+                Ok(gchandle_free) => {
+                    unsafe { gchandle_free(gchandle) };
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to invoke il2cpp_gchandle_free: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_is_assignable_from(
+        &self,
+        klass: Il2CppClass,
+        oklass: Il2CppClass,
+    ) -> Result<bool, String> {
+        match self.functions.class_is_assignable_from {
+            Some(class_is_assignable_from) => {
+                Ok(unsafe { class_is_assignable_from(klass, oklass) })
+            }
+            None => match self
+                .invoke::<Il2CppClassIsAssignableFromFn>("il2cpp_class_is_assignable_from")
+            {
+                Ok(class_is_assignable_from) => {
+                    Ok(unsafe { class_is_assignable_from(klass, oklass) })
+                }
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_class_is_assignable_from: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_array_object_header_size(&self) -> Result<u32, String> {
+        match self.functions.array_object_header_size {
+            Some(array_object_header_size) => Ok(unsafe { array_object_header_size() }),
+            None => match self
+                .invoke::<Option<Il2CppArrayObjectHeaderSizeFn>>("il2cpp_array_object_header_size")
+            {
+                Ok(Some(array_object_header_size)) => Ok(unsafe { array_object_header_size() }),
+                Ok(None) => Err(format!(
+                    "il2cpp_array_object_header_size is not exported by {}",
+                    self.name
+                )),
+                Err(e) => Err(format!(
+                    "Failed to invoke il2cpp_array_object_header_size: {}",
+                    e
+                )),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_get_flags(&self, klass: Il2CppClass) -> Result<i32, String> {
+        match self.functions.class_get_flags {
+            Some(class_get_flags) => Ok(unsafe { class_get_flags(klass) }),
+            None => match self.invoke::<Il2CppClassGetFlagsFn>("il2cpp_class_get_flags") {
+                Ok(class_get_flags) => Ok(unsafe { class_get_flags(klass) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_class_get_flags: {}", e)),
+            },
+        }
+    }
+
+    pub fn il2cpp_class_is_enum(&self, klass: Il2CppClass) -> Result<bool, String> {
+        match self.functions.class_is_enum {
+            Some(class_is_enum) => Ok(unsafe { class_is_enum(klass) }),
+            None => match self.invoke::<Il2CppClassIsEnumFn>("il2cpp_class_is_enum") {
+                Ok(class_is_enum) => Ok(unsafe { class_is_enum(klass) }),
+                Err(e) => Err(format!("Failed to invoke il2cpp_class_is_enum: {}", e)),
+            },
+        }
+    }
 }
 
 unsafe impl Send for Il2CppDll {}
@@ -699,6 +1383,15 @@ pub fn initialize_il2cpp(module_name: &str) -> Result<(), String> {
     }
 }
 
+/// Base address of the loaded il2cpp module, method RVAs are relative to it
+pub fn il2cpp_module_base() -> Result<usize, String> {
+    let dll = IL2CPP_MODULE.read();
+    if !dll.is_valid() {
+        return Err(format!("Module {} is not valid", dll.name));
+    }
+    Ok(dll.module.0 as usize)
+}
+
 // public functions
 pub fn il2cpp_init() -> Result<(), String> {
     IL2CPP_MODULE.read().il2cpp_init()
@@ -835,6 +1528,169 @@ pub fn il2cpp_type_get_object(itype: Il2CppType) -> Result<Il2CppObject, String>
     IL2CPP_MODULE.read().il2cpp_type_get_object(itype)
 }
 
+pub fn il2cpp_field_get_flags(field: Il2CppFieldInfo) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_field_get_flags(field)
+}
+
+pub fn il2cpp_field_static_get_value(field: Il2CppFieldInfo, value: *mut u8) -> Result<(), String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_field_static_get_value(field, value)
+}
+
+pub fn il2cpp_field_static_set_value(field: Il2CppFieldInfo, value: *mut u8) -> Result<(), String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_field_static_set_value(field, value)
+}
+
+pub fn il2cpp_runtime_class_init(klass: Il2CppClass) -> Result<(), String> {
+    IL2CPP_MODULE.read().il2cpp_runtime_class_init(klass)
+}
+
+pub fn il2cpp_type_get_type(itype: Il2CppType) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_type_get_type(itype)
+}
+
+pub fn il2cpp_type_is_byref(itype: Il2CppType) -> Result<bool, String> {
+    IL2CPP_MODULE.read().il2cpp_type_is_byref(itype)
+}
+
+pub fn il2cpp_class_from_il2cpp_type(itype: Il2CppType) -> Result<Il2CppClass, String> {
+    IL2CPP_MODULE.read().il2cpp_class_from_il2cpp_type(itype)
+}
+
+pub fn il2cpp_class_is_valuetype(klass: Il2CppClass) -> Result<bool, String> {
+    IL2CPP_MODULE.read().il2cpp_class_is_valuetype(klass)
+}
+
+pub fn il2cpp_class_value_size(klass: Il2CppClass, align: *mut u32) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_class_value_size(klass, align)
+}
+
+pub fn il2cpp_get_corlib() -> Result<Il2CppImage, String> {
+    IL2CPP_MODULE.read().il2cpp_get_corlib()
+}
+
+pub fn il2cpp_runtime_invoke(
+    method: Il2CppMethodInfo,
+    obj: *mut u8,
+    params: *mut *mut u8,
+    exc: *mut Il2CppObject,
+) -> Result<Il2CppObject, String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_runtime_invoke(method, obj, params, exc)
+}
+
+pub fn il2cpp_object_unbox(obj: Il2CppObject) -> Result<*mut u8, String> {
+    IL2CPP_MODULE.read().il2cpp_object_unbox(obj)
+}
+
+pub fn il2cpp_value_box(klass: Il2CppClass, data: *mut u8) -> Result<Il2CppObject, String> {
+    IL2CPP_MODULE.read().il2cpp_value_box(klass, data)
+}
+
+pub fn il2cpp_format_exception(
+    ex: Il2CppObject,
+    message: *mut i8,
+    message_size: i32,
+) -> Result<(), String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_format_exception(ex, message, message_size)
+}
+
+pub fn il2cpp_format_stack_trace(
+    ex: Il2CppObject,
+    output: *mut i8,
+    output_size: i32,
+) -> Result<(), String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_format_stack_trace(ex, output, output_size)
+}
+
+pub fn il2cpp_object_new(klass: Il2CppClass) -> Result<Il2CppObject, String> {
+    IL2CPP_MODULE.read().il2cpp_object_new(klass)
+}
+
+pub fn il2cpp_string_new(text: *const i8) -> Result<Il2CppString, String> {
+    IL2CPP_MODULE.read().il2cpp_string_new(text)
+}
+
+pub fn il2cpp_array_new(element_class: Il2CppClass, length: usize) -> Result<Il2CppArray, String> {
+    IL2CPP_MODULE.read().il2cpp_array_new(element_class, length)
+}
+
+pub fn il2cpp_array_element_size(array_class: Il2CppClass) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_array_element_size(array_class)
+}
+
+pub fn il2cpp_class_get_element_class(klass: Il2CppClass) -> Result<Il2CppClass, String> {
+    IL2CPP_MODULE.read().il2cpp_class_get_element_class(klass)
+}
+
+pub fn il2cpp_class_get_declaring_type(klass: Il2CppClass) -> Result<Il2CppClass, String> {
+    IL2CPP_MODULE.read().il2cpp_class_get_declaring_type(klass)
+}
+
+pub fn il2cpp_class_get_rank(klass: Il2CppClass) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_class_get_rank(klass)
+}
+
+pub fn il2cpp_gc_wbarrier_set_field(
+    obj: Il2CppObject,
+    target: *mut *mut u8,
+    value: *mut u8,
+) -> Result<(), String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_gc_wbarrier_set_field(obj, target, value)
+}
+
+pub fn il2cpp_gchandle_new(obj: Il2CppObject, pinned: bool) -> Result<u32, String> {
+    IL2CPP_MODULE.read().il2cpp_gchandle_new(obj, pinned)
+}
+
+pub fn il2cpp_gchandle_new_weakref(
+    obj: Il2CppObject,
+    track_resurrection: bool,
+) -> Result<u32, String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_gchandle_new_weakref(obj, track_resurrection)
+}
+
+pub fn il2cpp_gchandle_get_target(gchandle: u32) -> Result<Il2CppObject, String> {
+    IL2CPP_MODULE.read().il2cpp_gchandle_get_target(gchandle)
+}
+
+pub fn il2cpp_gchandle_free(gchandle: u32) -> Result<(), String> {
+    IL2CPP_MODULE.read().il2cpp_gchandle_free(gchandle)
+}
+
+pub fn il2cpp_class_is_assignable_from(
+    klass: Il2CppClass,
+    oklass: Il2CppClass,
+) -> Result<bool, String> {
+    IL2CPP_MODULE
+        .read()
+        .il2cpp_class_is_assignable_from(klass, oklass)
+}
+
+pub fn il2cpp_array_object_header_size() -> Result<u32, String> {
+    IL2CPP_MODULE.read().il2cpp_array_object_header_size()
+}
+
+pub fn il2cpp_class_get_flags(klass: Il2CppClass) -> Result<i32, String> {
+    IL2CPP_MODULE.read().il2cpp_class_get_flags(klass)
+}
+
+pub fn il2cpp_class_is_enum(klass: Il2CppClass) -> Result<bool, String> {
+    IL2CPP_MODULE.read().il2cpp_class_is_enum(klass)
+}
+
 pub fn il2cpp_print_all_function_ptrs() {
     IL2CPP_MODULE.read().print_all_functions();
 }
@@ -865,5 +1721,34 @@ static IL2CPP_FUNCTIONS_NAMES: LazyLock<Vec<String>> = LazyLock::new(|| {
         "il2cpp_method_get_return_type".to_string(),
         "il2cpp_method_get_flags".to_string(),
         "il2cpp_class_get_type".to_string(),
+        "il2cpp_field_get_flags".to_string(),
+        "il2cpp_field_static_get_value".to_string(),
+        "il2cpp_field_static_set_value".to_string(),
+        "il2cpp_runtime_class_init".to_string(),
+        "il2cpp_type_get_type".to_string(),
+        "il2cpp_type_is_byref".to_string(),
+        "il2cpp_class_from_il2cpp_type".to_string(),
+        "il2cpp_class_is_valuetype".to_string(),
+        "il2cpp_class_value_size".to_string(),
+        "il2cpp_get_corlib".to_string(),
+        "il2cpp_runtime_invoke".to_string(),
+        "il2cpp_object_unbox".to_string(),
+        "il2cpp_value_box".to_string(),
+        "il2cpp_format_exception".to_string(),
+        "il2cpp_format_stack_trace".to_string(),
+        "il2cpp_object_new".to_string(),
+        "il2cpp_string_new".to_string(),
+        "il2cpp_array_new".to_string(),
+        "il2cpp_array_element_size".to_string(),
+        "il2cpp_class_get_element_class".to_string(),
+        "il2cpp_class_get_declaring_type".to_string(),
+        "il2cpp_class_get_rank".to_string(),
+        "il2cpp_gchandle_new".to_string(),
+        "il2cpp_gchandle_new_weakref".to_string(),
+        "il2cpp_gchandle_get_target".to_string(),
+        "il2cpp_gchandle_free".to_string(),
+        "il2cpp_class_is_assignable_from".to_string(),
+        "il2cpp_class_get_flags".to_string(),
+        "il2cpp_class_is_enum".to_string(),
     ])
 });
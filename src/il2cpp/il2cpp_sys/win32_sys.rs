@@ -1,9 +1,17 @@
+#[cfg(windows)]
 use std::ffi::CString;
-use windows::Win32::Foundation::{FARPROC, HMODULE};
+#[cfg(windows)]
+pub use windows::Win32::Foundation::{FARPROC, HMODULE};
+#[cfg(windows)]
 use windows::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
+#[cfg(windows)]
 use windows::core::PCSTR;
 
+#[cfg(not(windows))]
+pub use unsupported::*;
+
 // Util to get module from name
+#[cfg(windows)]
 pub fn get_module_from_name(module_name: &str) -> Result<HMODULE, String> {
     let c_module_name = CString::new(module_name)
         .map_err(|e| format!("Failed to create CString for module name: {}", e))?;
@@ -17,6 +25,7 @@ pub fn get_module_from_name(module_name: &str) -> Result<HMODULE, String> {
 }
 
 // Utility to get exported function from il2cpp dll
+#[cfg(windows)]
 pub fn resolve_function_ptr_from_name(
     module: HMODULE,
     name: &str,
@@ -29,9 +38,47 @@ pub fn resolve_function_ptr_from_name(
         CString::new(name).map_err(|e| format!("Failed to create CString for name: {}", e))?;
 
     // Explicitly convert the *const i8 to PCSTR
-    let proc_address = unsafe {
-        GetProcAddress(module, PCSTR::from_raw(c_name.as_ptr() as *const u8))
-    };
+    let proc_address =
+        unsafe { GetProcAddress(module, PCSTR::from_raw(c_name.as_ptr() as *const u8)) };
     // windows::Win32::Foundation::FARPROC is an Option<extern "system" fn()>
     Ok(Some(proc_address))
 }
+
+// Only the offline parts of the crate work elsewhere, the live runtime is never found
+#[cfg(not(windows))]
+#[allow(clippy::upper_case_acronyms)] // named after the windows crate types they replace
+mod unsupported {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct HMODULE(pub *mut std::ffi::c_void);
+
+    impl Default for HMODULE {
+        fn default() -> Self {
+            Self(std::ptr::null_mut())
+        }
+    }
+
+    impl HMODULE {
+        pub fn is_invalid(&self) -> bool {
+            self.0.is_null()
+        }
+    }
+
+    pub type FARPROC = Option<unsafe extern "system" fn() -> isize>;
+
+    pub fn get_module_from_name(module_name: &str) -> Result<HMODULE, String> {
+        Err(format!(
+            "Failed to get module {}: the il2cpp runtime is only supported on Windows",
+            module_name
+        ))
+    }
+
+    pub fn resolve_function_ptr_from_name(
+        _module: HMODULE,
+        name: &str,
+    ) -> Result<Option<FARPROC>, String> {
+        Err(format!(
+            "Failed to resolve {}: the il2cpp runtime is only supported on Windows",
+            name
+        ))
+    }
+}
@@ -1,9 +1,11 @@
 pub mod classes;
 pub mod il2cpp_sys;
+pub mod pod;
+pub mod probe;
 
 use il2cpp_sys::c_types::{
-    Il2CppAssembly, Il2CppClass, Il2CppDomain, Il2CppImage, Il2CppMethodInfo, Il2CppThread,
-    Il2CppType,
+    Il2CppArray, Il2CppAssembly, Il2CppClass, Il2CppDomain, Il2CppImage, Il2CppMethodInfo,
+    Il2CppString, Il2CppThread, Il2CppType,
 };
 
 use std::ffi::{CStr, CString};
@@ -133,6 +135,48 @@ pub fn class_get_namespace(klass: Il2CppClass) -> Result<String, String> {
     }
 }
 
+/// `Namespace.Name`, or just `Name` for classes in the global namespace
+pub fn class_get_full_name(klass: Il2CppClass) -> Result<String, String> {
+    let name = class_get_name(klass)?;
+    match class_get_namespace(klass) {
+        Ok(namespace) if !namespace.is_empty() => Ok(format!("{}.{}", namespace, name)),
+        _ => Ok(name),
+    }
+}
+
+/// TypeAttributes of the class (visibility, abstract, sealed, interface, ...)
+pub fn class_get_flags(klass: Il2CppClass) -> Result<i32, String> {
+    il2cpp_sys::il2cpp_class_get_flags(klass)
+}
+
+pub fn class_is_enum(klass: Il2CppClass) -> Result<bool, String> {
+    il2cpp_sys::il2cpp_class_is_enum(klass)
+}
+
+/// True when an instance of `oklass` can be stored in a slot of type `klass`
+pub fn class_is_assignable_from(klass: Il2CppClass, oklass: Il2CppClass) -> Result<bool, String> {
+    il2cpp_sys::il2cpp_class_is_assignable_from(klass, oklass)
+}
+
+/// Resolve `Namespace.Name` in the loaded image called `assembly`, e.g. `UnityEngine.CoreModule.dll`
+pub fn find_class(assembly: &str, full_name: &str) -> Result<Il2CppClass, String> {
+    let (namespace, name) = full_name.rsplit_once('.').unwrap_or(("", full_name));
+    for asm in domain_get_assemblies(get_domain()?)? {
+        let image = assembly_get_image(asm)?;
+        if image.is_null() || image_get_name(image)? != assembly {
+            continue;
+        }
+        let klass = class_from_name(image, namespace, name)?;
+        if !klass.is_null() {
+            return Ok(klass);
+        }
+    }
+    Err(format!(
+        "Failed to find class {} in {}",
+        full_name, assembly
+    ))
+}
+
 pub fn class_get_parent(klass: Il2CppClass) -> Result<Il2CppClass, String> {
     il2cpp_sys::il2cpp_class_get_parent(klass)
 }
@@ -158,6 +202,18 @@ pub fn field_get_type(field: *mut u8) -> Result<Il2CppType, String> {
     il2cpp_sys::il2cpp_field_get_type(field)
 }
 
+pub fn field_get_flags(field: *mut u8) -> Result<i32, String> {
+    il2cpp_sys::il2cpp_field_get_flags(field)
+}
+
+pub fn field_static_get_value(field: *mut u8, value: *mut u8) -> Result<(), String> {
+    il2cpp_sys::il2cpp_field_static_get_value(field, value)
+}
+
+pub fn field_static_set_value(field: *mut u8, value: *mut u8) -> Result<(), String> {
+    il2cpp_sys::il2cpp_field_static_set_value(field, value)
+}
+
 pub fn class_get_methods(
     klass: Il2CppClass,
     iter: *mut *mut u8,
@@ -231,6 +287,136 @@ pub fn type_get_object(itype: Il2CppType) -> Result<Il2CppObject, String> {
     }
 }
 
+pub fn runtime_class_init(klass: Il2CppClass) -> Result<(), String> {
+    il2cpp_sys::il2cpp_runtime_class_init(klass)
+}
+
+pub fn type_get_type(itype: Il2CppType) -> Result<i32, String> {
+    il2cpp_sys::il2cpp_type_get_type(itype)
+}
+
+pub fn type_is_byref(itype: Il2CppType) -> Result<bool, String> {
+    il2cpp_sys::il2cpp_type_is_byref(itype)
+}
+
+pub fn class_from_type(itype: Il2CppType) -> Result<Il2CppClass, String> {
+    il2cpp_sys::il2cpp_class_from_il2cpp_type(itype)
+}
+
+pub fn class_is_valuetype(klass: Il2CppClass) -> Result<bool, String> {
+    il2cpp_sys::il2cpp_class_is_valuetype(klass)
+}
+
+pub fn class_value_size(klass: Il2CppClass) -> Result<i32, String> {
+    let mut align: u32 = 0;
+    il2cpp_sys::il2cpp_class_value_size(klass, &mut align as *mut u32)
+}
+
+pub fn get_corlib() -> Result<Il2CppImage, String> {
+    il2cpp_sys::il2cpp_get_corlib()
+}
+
+pub fn runtime_invoke(
+    method: Il2CppMethodInfo,
+    obj: *mut u8,
+    params: *mut *mut u8,
+    exc: *mut Il2CppObject,
+) -> Result<Il2CppObject, String> {
+    il2cpp_sys::il2cpp_runtime_invoke(method, obj, params, exc)
+}
+
+pub fn string_new(text: &str) -> Result<Il2CppString, String> {
+    let c_text = CString::new(text).map_err(|e| e.to_string())?;
+    il2cpp_sys::il2cpp_string_new(c_text.as_ptr())
+}
+
+pub fn object_new(klass: Il2CppClass) -> Result<Il2CppObject, String> {
+    il2cpp_sys::il2cpp_object_new(klass)
+}
+
+pub fn array_new(element_class: Il2CppClass, length: usize) -> Result<Il2CppArray, String> {
+    il2cpp_sys::il2cpp_array_new(element_class, length)
+}
+
+/// Size of the array object header, the elements start right after it
+pub fn array_object_header_size() -> Result<u32, String> {
+    il2cpp_sys::il2cpp_array_object_header_size()
+}
+
+pub fn array_element_size(array_class: Il2CppClass) -> Result<i32, String> {
+    il2cpp_sys::il2cpp_array_element_size(array_class)
+}
+
+pub fn class_get_element_class(klass: Il2CppClass) -> Result<Il2CppClass, String> {
+    il2cpp_sys::il2cpp_class_get_element_class(klass)
+}
+
+/// Enclosing class of a nested class, null otherwise
+pub fn class_get_declaring_type(klass: Il2CppClass) -> Result<Il2CppClass, String> {
+    il2cpp_sys::il2cpp_class_get_declaring_type(klass)
+}
+
+pub fn class_get_rank(klass: Il2CppClass) -> Result<i32, String> {
+    il2cpp_sys::il2cpp_class_get_rank(klass)
+}
+
+// Only exported by il2cpp versions with the incremental GC, which is the only case needing it
+pub fn gc_wbarrier_set_field(
+    obj: Il2CppObject,
+    target: *mut *mut u8,
+    value: *mut u8,
+) -> Result<(), String> {
+    il2cpp_sys::il2cpp_gc_wbarrier_set_field(obj, target, value)
+}
+
+pub fn object_unbox(obj: Il2CppObject) -> Result<*mut u8, String> {
+    if obj.is_null() {
+        return Err("Cannot unbox a null object".to_string());
+    }
+    il2cpp_sys::il2cpp_object_unbox(obj)
+}
+
+pub fn value_box(klass: Il2CppClass, data: *mut u8) -> Result<Il2CppObject, String> {
+    il2cpp_sys::il2cpp_value_box(klass, data)
+}
+
+// il2cpp truncates the formatted text to the buffer it is handed
+const FORMAT_BUFFER_SIZE: usize = 16 * 1024;
+
+pub fn format_exception(ex: Il2CppObject) -> Result<String, String> {
+    let mut buffer = vec![0i8; FORMAT_BUFFER_SIZE];
+    // the last byte stays 0 even when il2cpp fills the buffer without terminating it
+    il2cpp_sys::il2cpp_format_exception(ex, buffer.as_mut_ptr(), buffer.len() as i32 - 1)?;
+    Ok(unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_string_lossy()
+        .into_owned())
+}
+
+pub fn format_stack_trace(ex: Il2CppObject) -> Result<String, String> {
+    let mut buffer = vec![0i8; FORMAT_BUFFER_SIZE];
+    // the last byte stays 0 even when il2cpp fills the buffer without terminating it
+    il2cpp_sys::il2cpp_format_stack_trace(ex, buffer.as_mut_ptr(), buffer.len() as i32 - 1)?;
+    Ok(unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_string_lossy()
+        .into_owned())
+}
+
+pub fn gchandle_new(obj: Il2CppObject, pinned: bool) -> Result<u32, String> {
+    il2cpp_sys::il2cpp_gchandle_new(obj, pinned)
+}
+
+pub fn gchandle_new_weakref(obj: Il2CppObject, track_resurrection: bool) -> Result<u32, String> {
+    il2cpp_sys::il2cpp_gchandle_new_weakref(obj, track_resurrection)
+}
+
+pub fn gchandle_get_target(gchandle: u32) -> Result<Il2CppObject, String> {
+    il2cpp_sys::il2cpp_gchandle_get_target(gchandle)
+}
+
+pub fn gchandle_free(gchandle: u32) -> Result<(), String> {
+    il2cpp_sys::il2cpp_gchandle_free(gchandle)
+}
+
 pub fn print_all_function_ptrs() {
     il2cpp_sys::il2cpp_print_all_function_ptrs();
 }
@@ -238,3 +424,8 @@ pub fn print_all_function_ptrs() {
 pub fn init(module_name: &str) -> Result<(), String> {
     il2cpp_sys::initialize_il2cpp(module_name)
 }
+
+/// Base address of the il2cpp module passed to `init`
+pub fn module_base() -> Result<usize, String> {
+    il2cpp_sys::il2cpp_module_base()
+}
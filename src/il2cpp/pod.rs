@@ -0,0 +1,25 @@
+// Plain data that can be copied in and out of managed memory as raw bytes
+
+/// A type valid for every bit pattern of its size, so bytes il2cpp (or another process)
+/// wrote can be read as one without checks. `bool`, `char`, enums and references are not.
+///
+/// # Safety
+/// Implementors must be `#[repr(C)]` or `#[repr(transparent)]` plain data whose fields are
+/// all `Pod`, e.g. a `Vector3 { x: f32, y: f32, z: f32 }` mirror of a managed struct.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(
+    u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64
+);
+
+// managed references and native pointers are stored as plain addresses
+unsafe impl<T: 'static> Pod for *mut T {}
+unsafe impl<T: 'static> Pod for *const T {}
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
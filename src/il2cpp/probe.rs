@@ -0,0 +1,198 @@
+// Checking that memory is readable before a view dereferences it, so a stale pointer is an
+// error instead of an access violation in the game
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CHECKED_READS: AtomicBool = AtomicBool::new(false);
+
+/// Make view accessors validate addresses before reading them: the ones that can fail
+/// (`checked_ref` and the ones built on it) return an error, collection, string and
+/// exception accessors that cannot fail see unreadable memory as empty. Plain field reads
+/// through `as_ref` and generated getters stay unchecked. Off by default, every check
+/// costs a lookup.
+pub fn set_checked_reads(enabled: bool) {
+    CHECKED_READS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn checked_reads() -> bool {
+    CHECKED_READS.load(Ordering::Relaxed)
+}
+
+// Tests run in parallel and the setting is global: tests that read views hold it for their
+// duration, off or on, and get the previous value back when the guard drops
+#[cfg(test)]
+static TEST_SETTING: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+pub(crate) struct CheckedReads {
+    previous: bool,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl CheckedReads {
+    pub(crate) fn set(enabled: bool) -> Self {
+        // a failed test poisons the lock, its guard still restored the setting
+        let lock = TEST_SETTING.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = checked_reads();
+        set_checked_reads(enabled);
+        Self {
+            previous,
+            _lock: lock,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Drop for CheckedReads {
+    fn drop(&mut self) {
+        set_checked_reads(self.previous);
+    }
+}
+
+/// Whether all of `address..address + len` is mapped readable in this process.
+/// Linux keeps a snapshot of `/proc/self/maps` that is only re-read on a miss, so memory
+/// unmapped since the last miss still passes. Always true where the platform offers no
+/// way to tell.
+pub fn is_readable(address: usize, len: usize) -> bool {
+    if address == 0 {
+        return false;
+    }
+    let Some(end) = address.checked_add(len.max(1)) else {
+        return false;
+    };
+    platform::is_readable(address, end)
+}
+
+/// `is_readable` as an error naming the range
+pub fn check_readable(address: usize, len: usize) -> Result<(), String> {
+    if is_readable(address, len) {
+        Ok(())
+    } else {
+        Err(format!(
+            "0x{:X} bytes at 0x{:X} are not readable memory",
+            len, address
+        ))
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+    use crate::il2cpp_remote::maps::parse_maps;
+    use parking_lot::RwLock;
+    use std::sync::LazyLock;
+
+    // readable ranges of /proc/self/maps, adjacent ones merged, sorted by start
+    static READABLE: LazyLock<RwLock<Vec<(usize, usize)>>> =
+        LazyLock::new(|| RwLock::new(Vec::new()));
+
+    pub(super) fn is_readable(start: usize, end: usize) -> bool {
+        if covered(&READABLE.read(), start, end) {
+            return true;
+        }
+        // the snapshot may predate the allocation, read the maps again before failing
+        let Ok(text) = std::fs::read_to_string("/proc/self/maps") else {
+            return true;
+        };
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for mapping in parse_maps(&text).iter().filter(|m| m.readable) {
+            let (from, to) = (mapping.start as usize, mapping.end as usize);
+            match ranges.last_mut() {
+                Some(last) if last.1 == from => last.1 = to,
+                _ => ranges.push((from, to)),
+            }
+        }
+        let readable = covered(&ranges, start, end);
+        *READABLE.write() = ranges;
+        readable
+    }
+
+    fn covered(ranges: &[(usize, usize)], start: usize, end: usize) -> bool {
+        let index = ranges.partition_point(|range| range.0 <= start);
+        index > 0 && ranges[index - 1].1 >= end
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows::Win32::System::Memory::{
+        MEM_COMMIT, MEMORY_BASIC_INFORMATION, PAGE_EXECUTE, PAGE_GUARD, PAGE_NOACCESS, VirtualQuery,
+    };
+
+    // the access part of a protection, without the GUARD/NOCACHE/WRITECOMBINE modifiers
+    const PROTECTION_ACCESS_MASK: u32 = 0xFF;
+
+    // the protection of committed pages can change at any time, so every check queries
+    pub(super) fn is_readable(start: usize, end: usize) -> bool {
+        let mut address = start;
+        while address < end {
+            let mut info = MEMORY_BASIC_INFORMATION::default();
+            let written = unsafe {
+                VirtualQuery(
+                    Some(address as *const _),
+                    &mut info,
+                    std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+                )
+            };
+            if written == 0
+                || info.State != MEM_COMMIT
+                || info.Protect.0 == 0
+                || info.Protect.0 & (PAGE_NOACCESS.0 | PAGE_GUARD.0) != 0
+                // execute-only pages fault on reads
+                || info.Protect.0 & PROTECTION_ACCESS_MASK == PAGE_EXECUTE.0
+            {
+                return false;
+            }
+            address = info.BaseAddress as usize + info.RegionSize;
+        }
+        true
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+mod platform {
+    pub(super) fn is_readable(_start: usize, _end: usize) -> bool {
+        true
+    }
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::{
+        array::{Array, ArrayInner},
+        list::{ListInner, ListView},
+        object::ObjectInner,
+        string::UnityString,
+    };
+
+    // inside the first page, which is never mapped
+    const UNMAPPED: usize = 0x100;
+
+    #[test]
+    fn checked_reads_see_unreadable_views_as_empty() {
+        assert!(!is_readable(UNMAPPED, 8));
+        let array = Array::<i32>::from_ptr(UNMAPPED as *mut _).unwrap();
+        let string = UnityString::from_ptr(UNMAPPED as *mut _).unwrap();
+        // a readable list whose backing array is not
+        let list = ListInner::<i32> {
+            obj: ObjectInner {
+                klass: std::ptr::null_mut(),
+                monitor: std::ptr::null_mut(),
+            },
+            items: UNMAPPED as *mut ArrayInner<i32>,
+            size: 3,
+            version: 0,
+            sync_root: std::ptr::null_mut(),
+        };
+        let list = ListView::from_ref(&list);
+
+        let _reads = CheckedReads::set(true);
+        assert_eq!(array.len(), 0);
+        assert!(array.as_slice().is_empty() && array.at(0).is_none());
+        assert!(array.try_as_slice().is_err());
+        assert_eq!(string.len(), 0);
+        assert_eq!(string.to_string(), "");
+        assert_eq!(list.len(), 3);
+        assert!(list.as_slice().is_empty() && list.get(0).is_none());
+    }
+}
@@ -1,24 +1,43 @@
 use crate::il2cpp::{
-    assembly_get_image, class_get_fields, class_get_methods, class_get_name, class_get_namespace,
-    class_get_parent,
+    assembly_get_image, class_from_type, class_get_declaring_type, class_get_fields,
+    class_get_flags, class_get_methods, class_get_name, class_get_namespace, class_get_parent,
+    class_is_enum, class_is_valuetype, class_value_size,
     classes::{
         arg::ArgInner,
         assembly::Assembly,
         class::{Class, ClassInner},
         field::FieldInner,
-        itype::TypeInner,
+        itype::{Type, TypeInner, TypeKind},
         method::MethodInner,
     },
-    domain_get_assemblies, field_get_name, field_get_offset, field_get_type,
-    il2cpp_sys::c_types::{Il2CppDomain, Il2CppImage},
+    domain_get_assemblies, field_get_flags, field_get_name, field_get_offset, field_get_type,
+    il2cpp_sys::c_types::{Il2CppClass, Il2CppDomain, Il2CppImage, Il2CppType},
     image_get_class, image_get_class_count, image_get_filename, image_get_name, method_get_flags,
     method_get_name, method_get_param, method_get_param_count, method_get_param_name,
-    method_get_return_type, type_get_name,
+    method_get_return_type, module_base, type_get_name, type_get_type, type_is_byref,
 };
 
+use crate::il2cpp_dump::{
+    DumpFilter,
+    c_header::write_c_header,
+    csharp::{CSharpDumpOptions, write_csharp},
+    rust_bindings::write_rust_bindings,
+    script_json::{ScriptJson, ScriptJsonOptions},
+};
+
+use crate::il2cpp_offline::{
+    executable::Executable, metadata::GlobalMetadata, registration::BinaryMetadata,
+};
+#[cfg(target_os = "linux")]
+use crate::il2cpp_remote::linux::ProcessMemory;
+use crate::il2cpp_remote::maps::GameFiles;
+use crate::il2cpp_snapshot::{Snapshot, SnapshotFormat, diff::MetadataDiff};
+
 use parking_lot::RwLock;
 use std::{
     fmt::{Debug, Formatter},
+    io::Write,
+    path::Path,
     sync::Arc,
 };
 
@@ -28,12 +47,20 @@ pub trait Il2CppCacheTrait {
 
 pub struct Cache {
     pub assemblies: Vec<Assembly>,
+    /// What method code addresses are relative to: the il2cpp module when live, 0 when loaded
+    pub module_base: Option<usize>,
+    /// What `from_game` and `from_game_files` decoded from the binary beyond the model:
+    /// invoker addresses per method definition and the generic method instances.
+    /// `None` for live caches, snapshots and `from_metadata`
+    pub binary: Option<BinaryMetadata>,
 }
 impl Cache {
     #[allow(dead_code)]
     pub fn default() -> Self {
         Self {
             assemblies: Vec::new(),
+            module_base: None,
+            binary: None,
         }
     }
 
@@ -74,21 +101,25 @@ impl Cache {
                         continue;
                     }
 
-                    let parent_name = if let Ok(parent) = parent {
-                        if !parent.is_null() {
-                            match class_get_name(parent) {
-                                Ok(name) => name,
-                                Err(_) => "".to_string(),
-                            }
-                        } else {
-                            "".to_string()
-                        }
-                    } else {
-                        "".to_string()
-                    };
-
-                    let class =
-                        ClassInner::new(p_class, name.unwrap(), namespace.unwrap(), parent_name);
+                    // full name, `Object` alone is ambiguous between System and UnityEngine
+                    let parent_name = Cache::class_full_name(parent.unwrap());
+
+                    let flags = class_get_flags(p_class).unwrap_or_default();
+                    let value_type = class_is_valuetype(p_class).unwrap_or_default();
+                    let enum_type = class_is_enum(p_class).unwrap_or_default();
+
+                    let class = ClassInner::new(
+                        p_class,
+                        name.unwrap(),
+                        parent_name,
+                        namespace.unwrap(),
+                        flags,
+                        value_type,
+                        enum_type,
+                    );
+                    if let Ok(declaring_type) = class_get_declaring_type(p_class) {
+                        *class.declaring_type.write() = Cache::class_full_name(declaring_type);
+                    }
                     if let Err(e) = Cache::parse_fields(&class) {
                         return Err(format!("Failed to parse fields {}", e));
                     }
@@ -102,6 +133,44 @@ impl Cache {
         Ok(())
     }
 
+    // `Namespace.Name` of `klass`, empty for null
+    fn class_full_name(klass: Il2CppClass) -> String {
+        if klass.is_null() {
+            return String::new();
+        }
+        match (class_get_namespace(klass), class_get_name(klass)) {
+            (Ok(ns), Ok(name)) if !ns.is_empty() => format!("{}.{}", ns, name),
+            (_, Ok(name)) => name,
+            _ => String::new(),
+        }
+    }
+
+    pub fn parse_type(itype: Il2CppType) -> Result<Type, String> {
+        let name = type_get_name(itype)?;
+        let kind = TypeKind::from_raw(type_get_type(itype)?);
+        let byref = type_is_byref(itype)?;
+
+        let value_type = match kind {
+            TypeKind::Void => false,
+            kind if kind.is_primitive() => true,
+            TypeKind::ValueType | TypeKind::GenericInst => {
+                class_is_valuetype(class_from_type(itype)?)?
+            }
+            _ => false,
+        };
+
+        // size of the storage slot: the value itself for value types, a pointer otherwise
+        let size = if kind == TypeKind::Void && !byref {
+            0
+        } else if value_type && !byref {
+            class_value_size(class_from_type(itype)?)? as isize
+        } else {
+            std::mem::size_of::<usize>() as isize
+        };
+
+        Ok(TypeInner::new(itype, name, size, kind, byref, value_type))
+    }
+
     pub fn parse_fields(class: &Class) -> Result<(), String> {
         let mut iter: *mut u8 = std::ptr::null_mut();
         let mut field: *mut u8;
@@ -126,32 +195,30 @@ impl Cache {
                 continue;
             }
 
-            let itype = itype.unwrap();
-            let type_name = type_get_name(itype);
-            if type_name.is_err() {
+            let type_ = Cache::parse_type(itype.unwrap());
+            if type_.is_err() {
                 continue;
             }
-            let type_name = type_name.unwrap();
-            let type_ = TypeInner::new(itype, type_name, -1);
+            let type_ = type_.unwrap();
 
             let offset = field_get_offset(field);
             if offset.is_err() {
                 continue;
             }
             let offset = offset.unwrap();
-            let static_field = if offset <= 0 { true } else { false };
+
+            let flags = field_get_flags(field);
+            if flags.is_err() {
+                continue;
+            }
+            let flags = flags.unwrap();
 
             let name = name.unwrap();
             let weak_cls = Arc::downgrade(class);
-            class.fields.write().push(FieldInner::new(
-                field,
-                name,
-                type_,
-                weak_cls,
-                offset,
-                static_field,
-                std::ptr::null_mut(),
-            ));
+            class
+                .fields
+                .write()
+                .push(FieldInner::new(field, name, type_, weak_cls, offset, flags));
         }
         Ok(())
     }
@@ -183,13 +250,11 @@ impl Cache {
                 continue;
             }
 
-            let return_type = return_type.unwrap();
-            let return_type_name = type_get_name(return_type);
-            if return_type_name.is_err() {
+            let return_type = Cache::parse_type(return_type.unwrap());
+            if return_type.is_err() {
                 continue;
             }
-            let return_type_name = return_type_name.unwrap();
-            let return_type = TypeInner::new(return_type, return_type_name, -1);
+            let return_type = return_type.unwrap();
 
             let mut iflag: i32 = 0;
             let flags = method_get_flags(method, &mut iflag);
@@ -226,7 +291,7 @@ impl Cache {
                     }
                 };
 
-                let param_type_name = match type_get_name(param_type) {
+                let type_ = match Cache::parse_type(param_type) {
                     Ok(v) => v,
                     Err(_) => {
                         param_error = true;
@@ -234,7 +299,6 @@ impl Cache {
                     }
                 };
 
-                let type_ = TypeInner::new(param_type, param_type_name, -1);
                 args.write().push(ArgInner::new(param_name, type_));
             }
 
@@ -256,9 +320,142 @@ impl Cache {
         Ok(())
     }
 
+    /// Write C# pseudo-source in the Il2CppDumper `dump.cs` layout, see `il2cpp_dump::csharp`
+    pub fn dump_csharp(
+        &self,
+        writer: &mut impl Write,
+        options: &CSharpDumpOptions,
+    ) -> std::io::Result<()> {
+        let options = CSharpDumpOptions {
+            module_base: options.module_base.or(self.module_base),
+            ..options.clone()
+        };
+        write_csharp(&self.assemblies, writer, &options)
+    }
+
+    /// Write an il2cpp.h style C header for native reversing tools, see `il2cpp_dump::c_header`
+    pub fn dump_c_header(
+        &self,
+        writer: &mut impl Write,
+        filter: &DumpFilter,
+    ) -> std::io::Result<()> {
+        write_c_header(&self.assemblies, writer, filter)
+    }
+
+    /// Write Rust bindings for the classes selected by `filter`, see `il2cpp_dump::rust_bindings`
+    pub fn generate_rust_bindings(
+        &self,
+        writer: &mut impl Write,
+        filter: &DumpFilter,
+    ) -> std::io::Result<()> {
+        write_rust_bindings(&self.assemblies, writer, filter)
+    }
+
+    /// Write method symbols as an Il2CppDumper `script.json`, see `il2cpp_dump::script_json`
+    pub fn export_script_json(
+        &self,
+        writer: &mut impl Write,
+        options: &ScriptJsonOptions,
+    ) -> Result<(), String> {
+        let options = ScriptJsonOptions {
+            module_base: options.module_base.or(self.module_base),
+            ..options.clone()
+        };
+        ScriptJson::from_assemblies(&self.assemblies, &options)?
+            .write(writer)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Save the metadata as a snapshot for offline use, see `il2cpp_snapshot`
+    pub fn save(&self, path: impl AsRef<Path>, format: SnapshotFormat) -> Result<(), String> {
+        Snapshot::from_assemblies(&self.assemblies, self.module_base.unwrap_or(0))
+            .save(path, format)
+    }
+
+    /// Load a snapshot written by `save` in either format. The cache is based at 0: method
+    /// code addresses are RVAs, function pointers and runtime handles are null, so use it for
+    /// lookups, dumps and diffs; calling its methods fails.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Ok(Self {
+            assemblies: Snapshot::load(path)?.to_assemblies()?,
+            module_base: Some(0),
+            binary: None,
+        })
+    }
+
+    /// Build the cache from a `global-metadata.dat` alone, without a running game.
+    /// Types, field offsets and method pointers are left unresolved, see `from_game`
+    pub fn from_metadata(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::from_global_metadata(&GlobalMetadata::load(path)?, None)
+    }
+
+    /// Build the cache from `global-metadata.dat` and the game's compiled code (GameAssembly.dll,
+    /// libil2cpp.so or the Mach-O binary) without running it, with types, field offsets and
+    /// method addresses filled in. `module_base` is the binary's preferred load address.
+    pub fn from_game(
+        metadata_path: impl AsRef<Path>,
+        binary_path: impl AsRef<Path>,
+    ) -> Result<Self, String> {
+        let metadata = GlobalMetadata::load(metadata_path)?;
+        Self::from_global_metadata(&metadata, Some(&Executable::load(binary_path)?))
+    }
+
+    /// `from_metadata` or `from_game` for metadata that needed `GlobalMetadata::load_with`
+    pub fn from_global_metadata(
+        metadata: &GlobalMetadata,
+        executable: Option<&Executable>,
+    ) -> Result<Self, String> {
+        match executable {
+            Some(executable) => {
+                let binary = BinaryMetadata::analyze(executable, metadata)?;
+                Ok(Self {
+                    assemblies: metadata.to_assemblies_with(&binary)?,
+                    module_base: Some(executable.image_base as usize),
+                    binary: Some(binary),
+                })
+            }
+            None => Ok(Self {
+                assemblies: metadata.to_assemblies()?,
+                module_base: Some(0),
+                binary: None,
+            }),
+        }
+    }
+
+    /// `from_game` for a game running on this machine: the binary and metadata are found in
+    /// its memory map and method addresses point into the process.
+    /// Objects are then read with `il2cpp_remote::view`.
+    #[cfg(target_os = "linux")]
+    pub fn from_process(process: &ProcessMemory) -> Result<Self, String> {
+        Self::from_game_files(&process.game_files()?)
+    }
+
+    /// `from_game` with method addresses moved to where a process mapped the binary, e.g.
+    /// the files a core dump's module map names
+    pub fn from_game_files(files: &GameFiles) -> Result<Self, String> {
+        let metadata = GlobalMetadata::load(&files.metadata)?;
+        let executable = Executable::load(&files.binary)?;
+        let mut binary = BinaryMetadata::analyze(&executable, &metadata)?;
+        binary.rebase(executable.image_base, files.binary_base);
+        Ok(Self {
+            assemblies: metadata.to_assemblies_with(&binary)?,
+            module_base: Some(files.binary_base as usize),
+            binary: Some(binary),
+        })
+    }
+
+    /// Changes from this cache to `newer`, see `il2cpp_snapshot::diff`
+    pub fn diff(&self, newer: &Cache) -> MetadataDiff {
+        MetadataDiff::between(self, newer)
+    }
+
     pub fn new(domain: Il2CppDomain) -> Result<Self, String> {
         match Self::parse_assemblies(domain) {
-            Ok(assemblies) => Ok(Self { assemblies }),
+            Ok(assemblies) => Ok(Self {
+                assemblies,
+                module_base: module_base().ok(),
+                binary: None,
+            }),
             Err(e) => Err(e),
         }
     }
@@ -0,0 +1,499 @@
+use crate::il2cpp::classes::assembly::Assembly;
+use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::field::Field;
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::method::{METHOD_ATTRIBUTE_NEW_SLOT, METHOD_ATTRIBUTE_VIRTUAL};
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp_dump::{DumpFilter, class_index, sanitize_identifier, unique};
+use std::collections::{HashMap, HashSet};
+use std::io::{Result, Write};
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long",
+    "register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+    "true", "typedef", "union", "unsigned", "void", "volatile", "while",
+];
+
+const MAX_DEPTH: usize = 64;
+
+const PREAMBLE: &str = "\
+// il2cpp.h generated by il2cpp_rs from live il2cpp metadata.
+// Offsets are only valid for the game build they were generated from.
+#include <stdint.h>
+#include <stdbool.h>
+
+typedef void (*Il2CppMethodPointer)();
+typedef struct MethodInfo MethodInfo;
+typedef struct Il2CppClass Il2CppClass;
+
+typedef struct VirtualInvokeData {
+    Il2CppMethodPointer methodPtr;
+    const MethodInfo* method;
+} VirtualInvokeData;
+
+typedef struct Il2CppObject {
+    Il2CppClass* klass;
+    void* monitor;
+} Il2CppObject;
+
+typedef struct Il2CppArrayBounds {
+    uintptr_t length;
+    int32_t lower_bound;
+} Il2CppArrayBounds;
+
+typedef struct Il2CppArray {
+    Il2CppObject obj;
+    Il2CppArrayBounds* bounds;
+    uintptr_t max_length;
+} Il2CppArray;
+";
+
+/// Write C structs for the selected classes: `X_Fields` with every instance field (inherited
+/// ones included) padded to its offset, `X_o` for the object, `X_StaticFields` and `X_VTable`.
+/// Slot numbers are not exported by il2cpp, so vtables are rebuilt from the method flags:
+/// a class starts from its parent's vtable, new virtual slots are appended and overrides
+/// replace the slot of the same name. Interface slots may be off.
+pub fn write_c_header(
+    assemblies: &[Assembly],
+    writer: &mut impl Write,
+    filter: &DumpFilter,
+) -> Result<()> {
+    let names = ClassNames::new(assemblies);
+    let selected: Vec<&ClassInner> = filter
+        .classes(assemblies)
+        .map(|(_, class)| class)
+        .filter(|class| !class.is_interface() && !class.is_generic_definition())
+        .collect();
+    let emitted_value_types: HashSet<String> = selected
+        .iter()
+        .filter(|class| class.value_type && !class.enum_type)
+        .map(|class| class.qualified_name())
+        .collect();
+    let header = HeaderWriter {
+        names,
+        emitted_value_types,
+    };
+
+    writeln!(writer, "{}", PREAMBLE)?;
+
+    // structs embed value types by value, so those are written first, dependencies before users
+    let mut written = HashSet::new();
+    for class in selected.iter().filter(|c| c.value_type) {
+        header.write_value_type(writer, class, &mut written)?;
+    }
+    for class in selected.iter().filter(|c| !c.value_type) {
+        header.write_reference_type(writer, class)?;
+    }
+    Ok(())
+}
+
+/// C struct prefixes of every class in the cache. Nested classes include their declaring
+/// type (`Game_Player_Stats`), and classes that still sanitize to the same identifier,
+/// such as one defined in two assemblies, get a numeric suffix in cache order.
+pub(crate) struct ClassNames<'c> {
+    /// Classes by the name types refer to them with, see `class_index`
+    pub(crate) index: HashMap<String, &'c ClassInner>,
+    prefixes: HashMap<*const ClassInner, String>,
+}
+
+impl<'c> ClassNames<'c> {
+    pub(crate) fn new(assemblies: &'c [Assembly]) -> Self {
+        let mut taken = HashSet::new();
+        let prefixes = assemblies
+            .iter()
+            .flat_map(|assembly| assembly.classes.iter())
+            .map(|class| {
+                let prefix = unique(&mut taken, c_identifier(&class.qualified_name()));
+                (class.as_ref() as *const ClassInner, prefix)
+            })
+            .collect();
+        Self {
+            index: class_index(assemblies),
+            prefixes,
+        }
+    }
+
+    /// Prefix of the `_o`, `_Fields`, `_StaticFields` and `_VTable` structs of `class`
+    pub(crate) fn prefix(&self, class: &ClassInner) -> String {
+        match self.prefixes.get(&(class as *const ClassInner)) {
+            Some(prefix) => prefix.clone(),
+            None => c_identifier(&class.qualified_name()),
+        }
+    }
+
+    /// C spelling of `itype`, value types by value and references as pointers to their `_o`
+    /// struct. Pointers to incomplete structs are fine in C, the class need not be emitted.
+    pub(crate) fn c_type(&self, itype: &TypeInner) -> String {
+        if itype.is_void() {
+            return "void".to_string();
+        }
+        if itype.byref {
+            return "void*".to_string();
+        }
+        let primitive = match itype.kind {
+            TypeKind::Boolean => "bool",
+            TypeKind::Char | TypeKind::U2 => "uint16_t",
+            TypeKind::I1 => "int8_t",
+            TypeKind::U1 => "uint8_t",
+            TypeKind::I2 => "int16_t",
+            TypeKind::I4 => "int32_t",
+            TypeKind::U4 => "uint32_t",
+            TypeKind::I8 => "int64_t",
+            TypeKind::U8 => "uint64_t",
+            TypeKind::R4 => "float",
+            TypeKind::R8 => "double",
+            TypeKind::I => "intptr_t",
+            TypeKind::U => "uintptr_t",
+            TypeKind::Ptr | TypeKind::FnPtr => "void*",
+            TypeKind::SzArray | TypeKind::Array => "Il2CppArray*",
+            _ => {
+                let class = self.index.get(itype.name.as_str());
+                let prefix = match class {
+                    Some(class) => self.prefix(class),
+                    None => c_identifier(&itype.name),
+                };
+                return match class {
+                    // enums are stored as their underlying integer
+                    Some(class) if itype.value_type && class.enum_type => {
+                        format!("int{}_t", type_size(itype) * 8)
+                    }
+                    _ if itype.value_type => format!("struct {}_o", prefix),
+                    _ => format!("struct {}_o*", prefix),
+                };
+            }
+        };
+        primitive.to_string()
+    }
+}
+
+struct HeaderWriter<'c> {
+    names: ClassNames<'c>,
+    /// Qualified names of the value types whose structs this header defines
+    emitted_value_types: HashSet<String>,
+}
+
+impl HeaderWriter<'_> {
+    fn write_value_type(
+        &self,
+        writer: &mut impl Write,
+        class: &ClassInner,
+        written: &mut HashSet<String>,
+    ) -> Result<()> {
+        let name = self.names.prefix(class);
+        if !written.insert(name.clone()) {
+            return Ok(());
+        }
+        let fields = class.fields.read();
+        for field in fields.iter().filter(|f| !f.static_field) {
+            if let Some(dependency) = self.embedded_value_type(&field.itype) {
+                self.write_value_type(writer, dependency, written)?;
+            }
+        }
+
+        let header = std::mem::size_of::<ObjectInner>();
+        let has_fields = self.write_struct(
+            writer,
+            &format!("{}_Fields", name),
+            &instance_fields(&fields),
+            header,
+        )?;
+        writeln!(writer, "struct {}_o {{", name)?;
+        if has_fields {
+            writeln!(writer, "    struct {}_Fields fields;", name)?;
+        } else {
+            // an empty struct is one byte in il2cpp, standard C needs a member anyway
+            writeln!(writer, "    uint8_t _pad_0x0[0x1];")?;
+        }
+        writeln!(writer, "}};")?;
+        self.write_static_fields(writer, &name, &fields)?;
+        writeln!(writer)
+    }
+
+    fn write_reference_type(&self, writer: &mut impl Write, class: &ClassInner) -> Result<()> {
+        let name = self.names.prefix(class);
+        let header = std::mem::size_of::<ObjectInner>();
+
+        // flattened so every offset is exact, embedding parent structs would add tail padding
+        let mut inherited = Vec::new();
+        let mut ancestor = self.names.index.get(class.parent.as_str());
+        while let Some(parent) = ancestor {
+            // name lookups could loop on duplicate names, real hierarchies are far shallower
+            if inherited.len() == MAX_DEPTH {
+                break;
+            }
+            inherited.push(parent.fields.read().clone());
+            ancestor = self.names.index.get(parent.parent.as_str());
+        }
+        let fields = class.fields.read();
+        let mut all: Vec<&Field> = inherited.iter().flat_map(|f| instance_fields(f)).collect();
+        all.extend(instance_fields(&fields));
+
+        writeln!(writer, "// {} : {}", class.qualified_name(), class.parent)?;
+        let has_fields = self.write_struct(writer, &format!("{}_Fields", name), &all, header)?;
+
+        let vtable = self.vtable(class, 0);
+        if !vtable.is_empty() {
+            writeln!(writer, "struct {}_VTable {{", name)?;
+            for (slot, method) in vtable.iter().enumerate() {
+                writeln!(
+                    writer,
+                    "    VirtualInvokeData _{}_{};",
+                    slot,
+                    sanitize_identifier(method)
+                )?;
+            }
+            writeln!(writer, "}};")?;
+        }
+
+        writeln!(writer, "struct {}_o {{", name)?;
+        writeln!(writer, "    Il2CppClass* klass;")?;
+        writeln!(writer, "    void* monitor;")?;
+        if has_fields {
+            writeln!(writer, "    struct {}_Fields fields;", name)?;
+        }
+        writeln!(writer, "}};")?;
+        self.write_static_fields(writer, &name, &fields)?;
+        writeln!(writer)
+    }
+
+    fn write_static_fields(
+        &self,
+        writer: &mut impl Write,
+        name: &str,
+        fields: &[Field],
+    ) -> Result<()> {
+        // thread statics live in per thread storage and literals have no storage at all
+        let statics: Vec<&Field> = fields
+            .iter()
+            .filter(|f| f.static_field && !f.thread_static && !f.is_literal())
+            .collect();
+        self.write_struct(writer, &format!("{}_StaticFields", name), &statics, 0)?;
+        Ok(())
+    }
+
+    /// Write `struct name` unless it would be empty, which standard C does not allow
+    fn write_struct(
+        &self,
+        writer: &mut impl Write,
+        name: &str,
+        fields: &[&Field],
+        base: usize,
+    ) -> Result<bool> {
+        let mut members = Vec::new();
+        self.write_members(&mut members, fields, base)?;
+        if members.is_empty() {
+            return Ok(false);
+        }
+        writeln!(writer, "struct {} {{", name)?;
+        writer.write_all(&members)?;
+        writeln!(writer, "}};")?;
+        Ok(true)
+    }
+
+    /// Members sorted by offset with explicit padding, `base` is subtracted from the offsets
+    fn write_members(&self, writer: &mut impl Write, fields: &[&Field], base: usize) -> Result<()> {
+        let mut sorted: Vec<&&Field> = fields.iter().filter(|f| f.offset >= 0).collect();
+        sorted.sort_by_key(|f| f.offset);
+
+        let mut names = HashSet::new();
+        let mut cursor = 0;
+        for field in sorted {
+            let offset = (field.offset as usize).saturating_sub(base);
+            if offset < cursor {
+                writeln!(
+                    writer,
+                    "    // {} at 0x{:X} overlaps the previous field",
+                    field.name, offset
+                )?;
+                continue;
+            }
+            if offset > cursor {
+                writeln!(
+                    writer,
+                    "    uint8_t _pad_0x{:x}[0x{:x}];",
+                    cursor,
+                    offset - cursor
+                )?;
+            }
+            let mut ident = c_identifier(&field.name);
+            let mut n = 1;
+            while !names.insert(ident.clone()) {
+                ident = format!("{}_{}", c_identifier(&field.name), n);
+                n += 1;
+            }
+            writeln!(
+                writer,
+                "    {}; // 0x{:X}",
+                self.declaration(&field.itype, &ident),
+                offset
+            )?;
+            cursor = offset + type_size(&field.itype);
+        }
+        Ok(())
+    }
+
+    fn declaration(&self, itype: &TypeInner, ident: &str) -> String {
+        // value types without a struct in this header are kept as raw bytes
+        let opaque = itype.value_type
+            && !itype.byref
+            && !itype.kind.is_primitive()
+            && self.embedded_value_type(itype).is_none()
+            && !self
+                .names
+                .index
+                .get(itype.name.as_str())
+                .is_some_and(|class| class.enum_type);
+        if opaque {
+            return format!("uint8_t {}[{}]", ident, type_size(itype));
+        }
+        format!("{} {}", self.names.c_type(itype), ident)
+    }
+
+    /// The value type class of `itype` when its struct is part of this header
+    fn embedded_value_type(&self, itype: &TypeInner) -> Option<&ClassInner> {
+        if itype.byref
+            || !itype.value_type
+            || itype.kind.is_primitive()
+            || !self.emitted_value_types.contains(&itype.name)
+        {
+            return None;
+        }
+        self.names.index.get(itype.name.as_str()).copied()
+    }
+
+    /// Method names by reconstructed vtable slot
+    fn vtable(&self, class: &ClassInner, depth: usize) -> Vec<String> {
+        let mut slots = match self.names.index.get(class.parent.as_str()) {
+            Some(parent) if depth < MAX_DEPTH => self.vtable(parent, depth + 1),
+            _ => Vec::new(),
+        };
+        for method in class.methods.read().iter() {
+            if (method.flags & METHOD_ATTRIBUTE_VIRTUAL) == 0 {
+                continue;
+            }
+            let overridden = slots.iter().rposition(|slot| *slot == method.name);
+            match overridden {
+                Some(slot) if (method.flags & METHOD_ATTRIBUTE_NEW_SLOT) == 0 => {
+                    slots[slot] = method.name.clone()
+                }
+                _ => slots.push(method.name.clone()),
+            }
+        }
+        slots
+    }
+}
+
+fn instance_fields(fields: &[Field]) -> Vec<&Field> {
+    fields.iter().filter(|f| !f.static_field).collect()
+}
+
+fn type_size(itype: &TypeInner) -> usize {
+    usize::try_from(itype.size).unwrap_or(std::mem::size_of::<usize>())
+}
+
+pub(crate) fn c_identifier(name: &str) -> String {
+    let ident = sanitize_identifier(name);
+    if C_KEYWORDS.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_dump::fixture::{self, add_field, class, itype};
+
+    fn header() -> String {
+        let mut out = Vec::new();
+        write_c_header(&fixture::assemblies(), &mut out, &DumpFilter::default()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn empty_value_types_get_a_member() {
+        let header = header();
+        assert!(header.contains("struct Marker_o {\n    uint8_t _pad_0x0[0x1];\n};"));
+        assert!(!header.contains("{\n};"));
+    }
+
+    #[test]
+    fn value_types_are_written_before_users() {
+        let header = header();
+        let stats = header.find("struct Game_Data_Stats_o {").unwrap();
+        let player = header.find("struct Game_Player_Fields {").unwrap();
+        assert!(stats < player);
+        assert!(header.contains("struct Game_Data_Stats_o stats;"));
+    }
+
+    #[test]
+    fn nested_classes_sharing_a_name_get_distinct_structs() {
+        let mut assemblies = fixture::assemblies();
+        let nested = |outer: &str, name: &str, value_type: bool| {
+            let nested = class("", name, "System.ValueType", value_type);
+            *nested.declaring_type.write() = format!("Game.{}", outer);
+            nested
+        };
+        let first_stats = nested("Outer1", "Stats", true);
+        add_field(
+            &first_stats,
+            "level",
+            itype("System.Int32", TypeKind::I4, 4, true),
+            0x10,
+            0,
+        );
+        let second_stats = nested("Outer2", "Stats", true);
+        add_field(
+            &second_stats,
+            "ratio",
+            itype("System.Double", TypeKind::R8, 8, true),
+            0x10,
+            0,
+        );
+        let closures = nested("Outer1", "<>c", false);
+        let first = class("Game", "Outer1", "System.Object", false);
+        add_field(
+            &first,
+            "stats",
+            itype("Game.Outer1.Stats", TypeKind::ValueType, 4, true),
+            0x10,
+            0,
+        );
+        let second = class("Game", "Outer2", "System.Object", false);
+        add_field(
+            &second,
+            "stats",
+            itype("Game.Outer2.Stats", TypeKind::ValueType, 8, true),
+            0x10,
+            0,
+        );
+        // sanitizes to the same identifier as `Game.Outer1.Stats`
+        let lookalike = class("Game", "Outer1_Stats", "System.ValueType", true);
+        assemblies[0].classes.extend([
+            first_stats,
+            second_stats,
+            closures,
+            first,
+            second,
+            lookalike,
+        ]);
+
+        let mut out = Vec::new();
+        write_c_header(&assemblies, &mut out, &DumpFilter::default()).unwrap();
+        let header = String::from_utf8(out).unwrap();
+        assert!(
+            header.contains("struct Game_Outer1_Stats_Fields {\n    int32_t level; // 0x0\n};")
+        );
+        assert!(header.contains("struct Game_Outer2_Stats_Fields {\n    double ratio; // 0x0\n};"));
+        assert!(header.contains("struct Game_Outer1_Stats_1_o {"));
+        assert!(header.contains("struct Game_Outer1___c_o {"));
+        assert!(header.contains(
+            "struct Game_Outer1_Fields {\n    struct Game_Outer1_Stats_o stats; // 0x0\n};"
+        ));
+        assert!(header.contains(
+            "struct Game_Outer2_Fields {\n    struct Game_Outer2_Stats_o stats; // 0x0\n};"
+        ));
+    }
+}
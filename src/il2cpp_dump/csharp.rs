@@ -0,0 +1,385 @@
+use crate::il2cpp::classes::assembly::Assembly;
+use crate::il2cpp::classes::class::{
+    ClassInner, TYPE_ATTRIBUTE_NESTED_FAM_AND_ASSEM, TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM,
+    TYPE_ATTRIBUTE_NESTED_FAMILY, TYPE_ATTRIBUTE_NESTED_PRIVATE, TYPE_ATTRIBUTE_NESTED_PUBLIC,
+    TYPE_ATTRIBUTE_PUBLIC, TYPE_ATTRIBUTE_VISIBILITY_MASK,
+};
+use crate::il2cpp::classes::field::{
+    FIELD_ATTRIBUTE_FIELD_ACCESS_MASK, FIELD_ATTRIBUTE_INIT_ONLY, FieldInner,
+};
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::method::{
+    METHOD_ATTRIBUTE_ABSTRACT, METHOD_ATTRIBUTE_FINAL, METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK,
+    METHOD_ATTRIBUTE_NEW_SLOT, METHOD_ATTRIBUTE_PINVOKE_IMPL, METHOD_ATTRIBUTE_VIRTUAL,
+    MethodInner,
+};
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp::module_base;
+use crate::il2cpp_dump::DumpFilter;
+use std::io::{Result, Write};
+
+// What `write_csharp` includes, everything by default
+#[derive(Debug, Clone)]
+pub struct CSharpDumpOptions {
+    pub filter: DumpFilter,
+    /// `// 0x10` offset comments on fields
+    pub field_offsets: bool,
+    pub methods: bool,
+    /// `// RVA: 0x... VA: 0x...` comments above methods
+    pub method_addresses: bool,
+    /// Base the RVAs are relative to, the loaded il2cpp module when `None`
+    pub module_base: Option<usize>,
+}
+
+impl Default for CSharpDumpOptions {
+    fn default() -> Self {
+        Self {
+            filter: DumpFilter::default(),
+            field_offsets: true,
+            methods: true,
+            method_addresses: true,
+            module_base: None,
+        }
+    }
+}
+
+/// Write the selected classes as C# pseudo-source in the Il2CppDumper `dump.cs` layout
+pub fn write_csharp(
+    assemblies: &[Assembly],
+    writer: &mut impl Write,
+    options: &CSharpDumpOptions,
+) -> Result<()> {
+    let base = options.module_base.or_else(|| module_base().ok());
+
+    let mut type_index = 0;
+    for (index, assembly) in assemblies.iter().enumerate() {
+        if options.filter.includes_assembly(assembly) {
+            writeln!(
+                writer,
+                "// Image {}: {} - {}",
+                index, assembly.name, type_index
+            )?;
+        }
+        type_index += assembly.classes.len();
+    }
+    writeln!(writer)?;
+
+    for (_, class) in options.filter.classes(assemblies) {
+        write_class(writer, class, options, base)?;
+    }
+    Ok(())
+}
+
+fn write_class(
+    writer: &mut impl Write,
+    class: &ClassInner,
+    options: &CSharpDumpOptions,
+    base: Option<usize>,
+) -> Result<()> {
+    writeln!(writer, "// Namespace: {}", class.namespace)?;
+    write!(writer, "{}", class_modifiers(class))?;
+    write!(writer, "{}", class.name)?;
+    if let Some(parent) = base_type(class) {
+        write!(writer, " : {}", parent)?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "{{")?;
+
+    let fields = class.fields.read();
+    if !fields.is_empty() {
+        writeln!(writer, "\t// Fields")?;
+        for field in fields.iter() {
+            write_field(writer, class, field, options)?;
+        }
+    }
+
+    let methods = class.methods.read();
+    if options.methods && !methods.is_empty() {
+        if !fields.is_empty() {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "\t// Methods")?;
+        for method in methods.iter() {
+            writeln!(writer)?;
+            if options.method_addresses {
+                writeln!(writer, "\t{}", address_comment(method, base))?;
+            }
+            write_method(writer, method)?;
+        }
+    }
+    writeln!(writer, "}}")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_field(
+    writer: &mut impl Write,
+    class: &ClassInner,
+    field: &FieldInner,
+    options: &CSharpDumpOptions,
+) -> Result<()> {
+    let mut modifiers =
+        access_modifier(field.flags & FIELD_ATTRIBUTE_FIELD_ACCESS_MASK).to_string();
+    if field.is_literal() {
+        modifiers.push_str("const ");
+    } else {
+        if field.static_field {
+            modifiers.push_str("static ");
+        }
+        if (field.flags & FIELD_ATTRIBUTE_INIT_ONLY) != 0 {
+            modifiers.push_str("readonly ");
+        }
+    }
+
+    write!(
+        writer,
+        "\t{}{} {};",
+        modifiers,
+        type_name(&field.itype),
+        field.name
+    )?;
+    if options.field_offsets && !field.is_literal() {
+        if field.thread_static {
+            write!(writer, " // ThreadStatic")?;
+        } else {
+            // instance offsets of structs include the object header il2cpp strips when boxing
+            let offset = if class.value_type && !field.static_field {
+                field.offset - std::mem::size_of::<ObjectInner>() as i32
+            } else {
+                field.offset
+            };
+            write!(writer, " // 0x{:X}", offset)?;
+        }
+    }
+    writeln!(writer)
+}
+
+fn write_method(writer: &mut impl Write, method: &MethodInner) -> Result<()> {
+    let flags = method.flags;
+    let mut modifiers = access_modifier(flags & METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK).to_string();
+    if method.is_static {
+        modifiers.push_str("static ");
+    } else if (flags & METHOD_ATTRIBUTE_ABSTRACT) != 0 {
+        modifiers.push_str("abstract ");
+        if (flags & METHOD_ATTRIBUTE_NEW_SLOT) == 0 {
+            modifiers.push_str("override ");
+        }
+    } else if (flags & METHOD_ATTRIBUTE_VIRTUAL) != 0 {
+        if (flags & METHOD_ATTRIBUTE_FINAL) != 0 {
+            if (flags & METHOD_ATTRIBUTE_NEW_SLOT) == 0 {
+                modifiers.push_str("sealed override ");
+            }
+        } else if (flags & METHOD_ATTRIBUTE_NEW_SLOT) != 0 {
+            modifiers.push_str("virtual ");
+        } else {
+            modifiers.push_str("override ");
+        }
+    }
+    if (flags & METHOD_ATTRIBUTE_PINVOKE_IMPL) != 0 {
+        modifiers.push_str("extern ");
+    }
+
+    let params: Vec<String> = method
+        .args
+        .read()
+        .iter()
+        .map(|arg| {
+            let by_ref = if arg.itype.byref { "ref " } else { "" };
+            format!("{}{} {}", by_ref, type_name(&arg.itype), arg.name)
+        })
+        .collect();
+    writeln!(
+        writer,
+        "\t{}{} {}({}) {{ }}",
+        modifiers,
+        type_name(&method.return_type),
+        method.name,
+        params.join(", ")
+    )
+}
+
+fn address_comment(method: &MethodInner, base: Option<usize>) -> String {
+    let va = method.code_address;
+    match base {
+        _ if va == 0 => "// RVA: -1".to_string(),
+        Some(base) if va >= base => format!("// RVA: 0x{:X} VA: 0x{:X}", va - base, va),
+        _ => format!("// VA: 0x{:X}", va),
+    }
+}
+
+fn class_modifiers(class: &ClassInner) -> String {
+    let mut modifiers = match class.flags & TYPE_ATTRIBUTE_VISIBILITY_MASK {
+        TYPE_ATTRIBUTE_PUBLIC | TYPE_ATTRIBUTE_NESTED_PUBLIC => "public ",
+        TYPE_ATTRIBUTE_NESTED_PRIVATE => "private ",
+        TYPE_ATTRIBUTE_NESTED_FAMILY => "protected ",
+        TYPE_ATTRIBUTE_NESTED_FAM_AND_ASSEM => "private protected ",
+        TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM => "protected internal ",
+        _ => "internal ",
+    }
+    .to_string();
+
+    if class.is_interface() {
+        modifiers.push_str("interface ");
+    } else if class.enum_type {
+        modifiers.push_str("enum ");
+    } else if class.value_type {
+        modifiers.push_str("struct ");
+    } else {
+        match (class.is_abstract(), class.is_sealed()) {
+            (true, true) => modifiers.push_str("static "),
+            (true, false) => modifiers.push_str("abstract "),
+            (false, true) => modifiers.push_str("sealed "),
+            _ => {}
+        }
+        modifiers.push_str("class ");
+    }
+    modifiers
+}
+
+// implicit bases are left out, like the C# compiler would
+fn base_type(class: &ClassInner) -> Option<&str> {
+    match class.parent.as_str() {
+        "" | "System.Object" | "System.ValueType" | "System.Enum" => None,
+        parent => Some(parent),
+    }
+}
+
+// FieldAttributes and MethodAttributes share the access values
+fn access_modifier(access: i32) -> &'static str {
+    match access {
+        1 => "private ",
+        2 => "private protected ",
+        3 => "internal ",
+        4 => "protected ",
+        5 => "protected internal ",
+        6 => "public ",
+        _ => "",
+    }
+}
+
+fn type_name(itype: &TypeInner) -> String {
+    let keyword = match itype.kind {
+        TypeKind::Void => "void",
+        TypeKind::Boolean => "bool",
+        TypeKind::Char => "char",
+        TypeKind::I1 => "sbyte",
+        TypeKind::U1 => "byte",
+        TypeKind::I2 => "short",
+        TypeKind::U2 => "ushort",
+        TypeKind::I4 => "int",
+        TypeKind::U4 => "uint",
+        TypeKind::I8 => "long",
+        TypeKind::U8 => "ulong",
+        TypeKind::R4 => "float",
+        TypeKind::R8 => "double",
+        TypeKind::String => "string",
+        TypeKind::Object => "object",
+        // il2cpp_type_get_name appends `&` to by-ref types, the `ref` modifier says it already
+        _ => return itype.name.trim_end_matches('&').to_string(),
+    };
+    keyword.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::class::{TYPE_ATTRIBUTE_ABSTRACT, TYPE_ATTRIBUTE_SEALED};
+    use crate::il2cpp::classes::method::METHOD_ATTRIBUTE_STATIC;
+    use crate::il2cpp_dump::fixture::{self, add_field, add_method_at, itype};
+    use std::sync::Arc;
+
+    const BASE: usize = 0x10000;
+    // member access values shared by fields and methods
+    const PRIVATE: i32 = 1;
+    const PUBLIC: i32 = 6;
+
+    fn class_with_flags(name: &str, parent: &str, flags: i32) -> Arc<ClassInner> {
+        ClassInner::new(
+            std::ptr::null_mut(),
+            name.to_string(),
+            parent.to_string(),
+            "Game".to_string(),
+            flags,
+            false,
+            false,
+        )
+    }
+
+    fn dump() -> String {
+        let mut assemblies = fixture::assemblies();
+        let tools = class_with_flags("Tools", "", TYPE_ATTRIBUTE_ABSTRACT | TYPE_ATTRIBUTE_SEALED);
+        let void = itype("System.Void", TypeKind::Void, 0, false);
+        let flags = METHOD_ATTRIBUTE_STATIC | PUBLIC;
+        add_method_at(&tools, "InModule", void.clone(), &[], flags, BASE + 0x1234);
+        add_method_at(&tools, "OutsideModule", void, &[], flags, 0x800);
+        let hidden = class_with_flags(
+            "Hidden",
+            "Game.Player",
+            TYPE_ATTRIBUTE_NESTED_PRIVATE | TYPE_ATTRIBUTE_SEALED,
+        );
+        add_field(
+            &hidden,
+            "secret",
+            itype("System.Int64", TypeKind::I8, 8, true),
+            0x10,
+            PRIVATE,
+        );
+        let base = class_with_flags(
+            "Base",
+            "",
+            TYPE_ATTRIBUTE_NESTED_FAMILY | TYPE_ATTRIBUTE_ABSTRACT,
+        );
+        let internal = class_with_flags("Internal", "", TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM);
+        assemblies[0]
+            .classes
+            .extend([tools, hidden, base, internal]);
+
+        let options = CSharpDumpOptions {
+            module_base: Some(BASE),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_csharp(&assemblies, &mut out, &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn class_modifiers_follow_type_attributes() {
+        let dump = dump();
+        assert!(
+            dump.contains("// Namespace: Game\npublic class Player : UnityEngine.MonoBehaviour\n{")
+        );
+        assert!(dump.contains("internal static class Tools\n"));
+        assert!(dump.contains("private sealed class Hidden : Game.Player\n"));
+        assert!(dump.contains("protected abstract class Base\n"));
+        assert!(dump.contains("protected internal class Internal\n"));
+        assert!(dump.contains("public struct Stats\n"));
+    }
+
+    #[test]
+    fn implicit_base_types_are_left_out() {
+        let dump = dump();
+        assert!(dump.contains("public class Config\n"));
+        assert!(dump.contains("public struct Marker\n"));
+    }
+
+    #[test]
+    fn struct_offsets_exclude_the_object_header() {
+        let dump = dump();
+        assert!(dump.contains("\tfloat attack; // 0x0\n\tfloat defense; // 0x4\n"));
+        assert!(dump.contains("\tint health; // 0x18\n"));
+        assert!(dump.contains("\tprivate long secret; // 0x10\n"));
+        assert!(dump.contains("\tstatic int count; // 0x0\n\tconst int MaxHealth;\n"));
+    }
+
+    #[test]
+    fn method_comments_are_relative_to_the_module() {
+        let dump = dump();
+        assert!(
+            dump.contains("\t// RVA: 0x1234 VA: 0x11234\n\tpublic static void InModule() { }\n")
+        );
+        assert!(dump.contains("\t// VA: 0x800\n\tpublic static void OutsideModule() { }\n"));
+        assert!(dump.contains("\t// RVA: -1\n\tbool Revive(bool force) { }\n"));
+        assert!(dump.contains("\t// RVA: -1\n\tstatic void Spawn(string name) { }\n"));
+    }
+}
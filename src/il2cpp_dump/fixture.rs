@@ -0,0 +1,140 @@
+// A small Assembly-CSharp model shared by the writer tests, laid out for 64-bit targets
+use crate::il2cpp::classes::{
+    arg::ArgInner,
+    assembly::Assembly,
+    class::{Class, ClassInner, TYPE_ATTRIBUTE_PUBLIC},
+    field::{FIELD_ATTRIBUTE_LITERAL, FIELD_ATTRIBUTE_STATIC, FieldInner},
+    itype::{Type, TypeInner, TypeKind},
+    method::{METHOD_ATTRIBUTE_STATIC, MethodInner},
+};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+pub(crate) fn itype(name: &str, kind: TypeKind, size: isize, value_type: bool) -> Type {
+    TypeInner::new(
+        std::ptr::null_mut(),
+        name.to_string(),
+        size,
+        kind,
+        false,
+        value_type,
+    )
+}
+
+pub(crate) fn class(namespace: &str, name: &str, parent: &str, value_type: bool) -> Class {
+    ClassInner::new(
+        std::ptr::null_mut(),
+        name.to_string(),
+        parent.to_string(),
+        namespace.to_string(),
+        TYPE_ATTRIBUTE_PUBLIC,
+        value_type,
+        false,
+    )
+}
+
+pub(crate) fn add_field(class: &Class, name: &str, itype: Type, offset: i32, flags: i32) {
+    class.fields.write().push(FieldInner::new(
+        std::ptr::null_mut(),
+        name.to_string(),
+        itype,
+        Arc::downgrade(class),
+        offset,
+        flags,
+    ));
+}
+
+pub(crate) fn add_method(class: &Class, name: &str, ret: Type, args: &[(&str, Type)], flags: i32) {
+    add_method_at(class, name, ret, args, flags, 0);
+}
+
+/// A method whose native code is at `function`, 0 for none
+pub(crate) fn add_method_at(
+    class: &Class,
+    name: &str,
+    ret: Type,
+    args: &[(&str, Type)],
+    flags: i32,
+    function: usize,
+) {
+    let args = args
+        .iter()
+        .map(|(name, itype)| ArgInner::new(name.to_string(), itype.clone()))
+        .collect();
+    class.methods.write().push(MethodInner::new(
+        std::ptr::null_mut(),
+        name.to_string(),
+        Arc::downgrade(class),
+        ret,
+        flags,
+        (flags & METHOD_ATTRIBUTE_STATIC) != 0,
+        function as *mut u8,
+        RwLock::new(args),
+    ));
+}
+
+/// `Game.Player`, the `Game.Data.Stats` struct, `Game_Data.Config` whose module name
+/// collides with `Game.Data`, and an empty struct in the global namespace
+pub(crate) fn assemblies() -> Vec<Assembly> {
+    let int = itype("System.Int32", TypeKind::I4, 4, true);
+    let float = itype("System.Single", TypeKind::R4, 4, true);
+    let boolean = itype("System.Boolean", TypeKind::Boolean, 1, true);
+    let void = itype("System.Void", TypeKind::Void, 0, false);
+    let string = itype("System.String", TypeKind::String, 8, false);
+    let stats_type = itype("Game.Data.Stats", TypeKind::ValueType, 8, true);
+
+    let stats = class("Game.Data", "Stats", "System.ValueType", true);
+    add_field(&stats, "attack", float.clone(), 0x10, 0);
+    add_field(&stats, "defense", float.clone(), 0x14, 0);
+
+    let player = class("Game", "Player", "UnityEngine.MonoBehaviour", false);
+    add_field(&player, "health", int.clone(), 0x18, 0);
+    add_field(&player, "type", int.clone(), 0x1C, 0);
+    add_field(&player, "name", string.clone(), 0x20, 0);
+    add_field(&player, "stats", stats_type.clone(), 0x28, 0);
+    add_field(&player, "<Alive>k__BackingField", boolean.clone(), 0x30, 0);
+    add_field(&player, "count", int.clone(), 0x0, FIELD_ATTRIBUTE_STATIC);
+    add_field(
+        &player,
+        "MaxHealth",
+        int.clone(),
+        0x0,
+        FIELD_ATTRIBUTE_STATIC | FIELD_ATTRIBUTE_LITERAL,
+    );
+    add_method(
+        &player,
+        "TakeDamage",
+        void.clone(),
+        &[("amount", int.clone())],
+        0,
+    );
+    add_method(&player, "get_Name", string.clone(), &[], 0);
+    add_method(&player, "get_Stats", stats_type.clone(), &[], 0);
+    add_method(
+        &player,
+        "Revive",
+        boolean.clone(),
+        &[("force", boolean.clone())],
+        0,
+    );
+    add_method(
+        &player,
+        "Spawn",
+        void.clone(),
+        &[("name", string)],
+        METHOD_ATTRIBUTE_STATIC,
+    );
+
+    let config = class("Game_Data", "Config", "System.Object", false);
+    add_field(&config, "volume", float, 0x10, 0);
+
+    let marker = class("", "Marker", "System.ValueType", true);
+
+    let mut assembly = Assembly::new(
+        std::ptr::null_mut(),
+        "Assembly-CSharp.dll".to_string(),
+        "Assembly-CSharp.dll".to_string(),
+    );
+    assembly.classes = vec![player, stats, config, marker];
+    vec![assembly]
+}
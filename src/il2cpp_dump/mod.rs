@@ -0,0 +1,98 @@
+// Writers turning the metadata cache into source code and tooling formats
+pub mod c_header;
+pub mod csharp;
+#[cfg(test)]
+pub(crate) mod fixture;
+pub mod rust_bindings;
+pub mod script_json;
+
+use crate::il2cpp::classes::{assembly::Assembly, class::ClassInner};
+use std::collections::{HashMap, HashSet};
+
+// Selects the assemblies and namespaces a dump covers, empty lists select everything
+#[derive(Debug, Clone, Default)]
+pub struct DumpFilter {
+    /// Image names, e.g. `Assembly-CSharp.dll`
+    pub assemblies: Vec<String>,
+    /// Namespaces, each also selects its children (`Game` selects `Game.UI`)
+    pub namespaces: Vec<String>,
+}
+
+impl DumpFilter {
+    pub fn assembly(mut self, name: &str) -> Self {
+        self.assemblies.push(name.to_string());
+        self
+    }
+
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespaces.push(namespace.to_string());
+        self
+    }
+
+    pub fn includes_assembly(&self, assembly: &Assembly) -> bool {
+        self.assemblies.is_empty() || self.assemblies.contains(&assembly.name)
+    }
+
+    pub fn includes_class(&self, class: &ClassInner) -> bool {
+        self.namespaces.is_empty()
+            || self.namespaces.iter().any(|ns| {
+                class.namespace == *ns
+                    || class
+                        .namespace
+                        .strip_prefix(ns.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+    }
+
+    /// Selected classes in cache order, with the assembly they belong to
+    pub fn classes<'c>(
+        &self,
+        assemblies: &'c [Assembly],
+    ) -> impl Iterator<Item = (&'c Assembly, &'c ClassInner)> {
+        assemblies
+            .iter()
+            .filter(|assembly| self.includes_assembly(assembly))
+            .flat_map(|assembly| {
+                assembly
+                    .classes
+                    .iter()
+                    .map(move |class| (assembly, class.as_ref()))
+            })
+            .filter(|(_, class)| self.includes_class(class))
+    }
+}
+
+/// Make `name` a valid identifier, `<Name>k__BackingField` -> `_Name_k__BackingField`
+pub(crate) fn sanitize_identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+/// Every class of the cache by the name types refer to it with, `Outer.Inner` for nested
+/// classes. The first one wins when several assemblies define the same name.
+pub(crate) fn class_index(assemblies: &[Assembly]) -> HashMap<String, &ClassInner> {
+    let mut index = HashMap::new();
+    for class in assemblies.iter().flat_map(|a| a.classes.iter()) {
+        index
+            .entry(class.qualified_name())
+            .or_insert(class.as_ref());
+    }
+    index
+}
+
+/// `name`, or `name_N` when already taken
+pub(crate) fn unique(taken: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 1;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{}_{}", name, n);
+        n += 1;
+    }
+    candidate
+}
@@ -0,0 +1,409 @@
+use crate::il2cpp::classes::assembly::Assembly;
+use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::field::Field;
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::object::ObjectInner;
+use crate::il2cpp_dump::{DumpFilter, sanitize_identifier, unique};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Result, Write};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+    "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+    "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+const HEADER: &str = "\
+// Rust bindings generated by il2cpp_rs from live il2cpp metadata.
+// Offsets are only valid for the game build they were generated from.
+// Include this file as a module, e.g. `mod game;`
+#![allow(non_snake_case, non_camel_case_types, dead_code, unused_imports, clippy::all)]
+
+use il2cpp_rs::il2cpp::classes::il2cpp_view::Il2CppManagedClass;
+use il2cpp_rs::il2cpp::classes::object::ObjectInner;
+use il2cpp_rs::il2cpp::classes::string::UnityStringInner;
+use il2cpp_rs::il2cpp::classes::thunk::Thunk;
+use il2cpp_rs::il2cpp_cache::Il2CppCacheTrait;
+use il2cpp_rs::il2cpp_view;
+use std::ffi::c_void;
+";
+
+/// Write `il2cpp_view!` structs, field getters, static field accessors and method thunk
+/// lookups for every class selected by `filter`, one module per namespace
+pub fn write_rust_bindings(
+    assemblies: &[Assembly],
+    writer: &mut impl Write,
+    filter: &DumpFilter,
+) -> Result<()> {
+    let mut namespaces: BTreeMap<&str, Vec<(&Assembly, &ClassInner)>> = BTreeMap::new();
+    for (assembly, class) in filter.classes(assemblies) {
+        // interfaces have no layout, compiler generated and open generic classes no stable one
+        if class.is_interface() || class.is_generic_definition() || class.name.starts_with('<') {
+            continue;
+        }
+        namespaces
+            .entry(class.namespace.as_str())
+            .or_default()
+            .push((assembly, class));
+    }
+
+    writeln!(writer, "{}", HEADER)?;
+    // `Foo.Bar` and `Foo_Bar` both map to `foo_bar`, the later one gets a suffix
+    let mut module_names = HashSet::new();
+    for (namespace, classes) in namespaces {
+        let module = unique(&mut module_names, module_name(namespace));
+        if !namespace.is_empty() {
+            writeln!(writer, "// namespace {}", namespace)?;
+        }
+        writeln!(writer, "pub mod {} {{", module)?;
+        writeln!(writer, "    use super::*;")?;
+        let mut struct_names = HashSet::new();
+        for (assembly, class) in classes {
+            let name = unique(&mut struct_names, rust_identifier(&class.name));
+            writeln!(writer)?;
+            if class.value_type {
+                write_value_type(writer, class, &name)?;
+            } else {
+                write_view(writer, assembly, class, &name)?;
+            }
+        }
+        writeln!(writer, "}}")?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn write_view(
+    writer: &mut impl Write,
+    assembly: &Assembly,
+    class: &ClassInner,
+    name: &str,
+) -> Result<()> {
+    let fields = class.fields.read();
+    writeln!(writer, "    // {} : {}", class.full_name(), class.parent)?;
+    writeln!(writer, "    il2cpp_view! {{")?;
+    writeln!(
+        writer,
+        "        #[il2cpp(class = {:?}, assembly = {:?})]",
+        class.full_name(),
+        assembly.name
+    )?;
+    writeln!(writer, "        pub struct {} {{", name)?;
+    writeln!(writer, "            pub obj: ObjectInner,")?;
+    let layout = write_fields(writer, &fields, false, "            ")?;
+    writeln!(writer, "        }}")?;
+    writeln!(writer, "    }}")?;
+
+    let mut body = Vec::new();
+    let mut fn_names = HashSet::new();
+    for (field, ident) in &layout {
+        let getter = unique(&mut fn_names, format!("get_{}", snake_case(&field.name)));
+        let (ty, read) = if is_boolean(&field.itype) {
+            ("bool".to_string(), format!("{} != 0", ident))
+        } else {
+            (field_type(&field.itype), ident.clone())
+        };
+        writeln!(
+            body,
+            "        pub fn {}(&self) -> {} {{\n            \
+             self.as_ref().{}\n        \
+             }}",
+            getter, ty, read
+        )?;
+    }
+    for field in fields.iter().filter(|f| f.static_field) {
+        write_static_accessors(&mut body, &mut fn_names, field)?;
+    }
+    for method in class.methods.read().iter() {
+        if method.name == ".cctor" {
+            continue;
+        }
+        let args = method.args.read();
+        let arg_types: Option<Vec<String>> = args.iter().map(|a| thunk_type(&a.itype)).collect();
+        let (Some(arg_types), Some(ret)) = (arg_types, thunk_type(&method.return_type)) else {
+            writeln!(
+                body,
+                "        // {}: generic or value type signature, use MethodInner::invoke",
+                method.name
+            )?;
+            continue;
+        };
+        let thunk = unique(&mut fn_names, format!("thunk_{}", snake_case(&method.name)));
+        let param_names: Vec<String> = args.iter().map(|a| format!("{:?}", a.itype.name)).collect();
+        writeln!(
+            body,
+            "        pub fn {}(cache: &impl Il2CppCacheTrait) -> Result<Thunk<{}, {}>, String> {{\n            \
+             Self::cached_method(cache, {:?}, &[{}])?.thunk()\n        \
+             }}",
+            thunk,
+            tuple_type(&arg_types),
+            ret,
+            method.name,
+            param_names.join(", ")
+        )?;
+    }
+
+    if !body.is_empty() {
+        writeln!(writer)?;
+        writeln!(writer, "    impl<'a> {}View<'a> {{", name)?;
+        writer.write_all(&body)?;
+        writeln!(writer, "    }}")?;
+    }
+    Ok(())
+}
+
+// Structs are stored without the object header, their field offsets include it
+fn write_value_type(writer: &mut impl Write, class: &ClassInner, name: &str) -> Result<()> {
+    let fields = class.fields.read();
+    writeln!(writer, "    // {} (value type)", class.full_name())?;
+    writeln!(writer, "    #[repr(C)]")?;
+    writeln!(writer, "    #[derive(Clone, Copy)]")?;
+    writeln!(writer, "    pub struct {} {{", name)?;
+    write_fields(writer, &fields, true, "        ")?;
+    writeln!(writer, "    }}")?;
+    Ok(())
+}
+
+/// Instance fields in offset order with explicit padding, returns the fields and their identifiers
+fn write_fields(
+    writer: &mut impl Write,
+    fields: &[Field],
+    value_type: bool,
+    indent: &str,
+) -> Result<Vec<(Field, String)>> {
+    // views start after the embedded object header, structs at 0 with offsets rebased
+    let header = std::mem::size_of::<ObjectInner>();
+    let (mut cursor, base) = if value_type { (0, header) } else { (header, 0) };
+    let mut instance: Vec<&Field> = fields
+        .iter()
+        .filter(|f| !f.static_field && f.offset >= 0)
+        .collect();
+    instance.sort_by_key(|f| f.offset);
+
+    let mut emitted = Vec::new();
+    let mut idents = HashSet::new();
+    for field in instance {
+        let offset = (field.offset as usize).saturating_sub(base);
+        if offset < cursor {
+            // explicit layout unions, the first field at an offset wins
+            writeln!(
+                writer,
+                "{}// {} at 0x{:X} overlaps the previous field",
+                indent, field.name, offset
+            )?;
+            continue;
+        }
+        if offset > cursor {
+            writeln!(
+                writer,
+                "{}_pad_0x{:x}: [u8; 0x{:x}],",
+                indent,
+                cursor,
+                offset - cursor
+            )?;
+        }
+        let ident = unique(&mut idents, rust_identifier(&field.name));
+        writeln!(
+            writer,
+            "{}pub {}: {}, // 0x{:X} {}",
+            indent,
+            ident,
+            field_type(&field.itype),
+            offset,
+            field.itype.name
+        )?;
+        cursor = offset + field_size(&field.itype);
+        emitted.push((field.clone(), ident));
+    }
+    Ok(emitted)
+}
+
+fn write_static_accessors(
+    body: &mut Vec<u8>,
+    fn_names: &mut HashSet<String>,
+    field: &Field,
+) -> Result<()> {
+    // `bool` is not valid for every byte, static storage is copied as `u8`
+    let (ty, read, write) = if is_boolean(&field.itype) {
+        (
+            "bool".to_string(),
+            "get_static::<u8>().map(|value| value != 0)",
+            "set_static(value as u8)",
+        )
+    } else {
+        (
+            field_type(&field.itype),
+            "get_static()",
+            "set_static(value)",
+        )
+    };
+    let snake = snake_case(&field.name);
+    let getter = unique(fn_names, format!("get_static_{}", snake));
+    writeln!(
+        body,
+        "        pub fn {}(cache: &impl Il2CppCacheTrait) -> Result<{}, String> {{\n            \
+         Self::cached_field(cache, {:?})?.{}\n        \
+         }}",
+        getter, ty, field.name, read
+    )?;
+    if !field.is_literal() {
+        let setter = unique(fn_names, format!("set_static_{}", snake));
+        writeln!(
+            body,
+            "        pub fn {}(cache: &impl Il2CppCacheTrait, value: {}) -> Result<(), String> {{\n            \
+             Self::cached_field(cache, {:?})?.{}\n        \
+             }}",
+            setter, ty, field.name, write
+        )?;
+    }
+    Ok(())
+}
+
+/// Storage type of a field, unknown structs become byte arrays of their size
+fn field_type(itype: &TypeInner) -> String {
+    if itype.byref {
+        return "*mut c_void".to_string();
+    }
+    if let Some(primitive) = primitive_type(itype.kind) {
+        return primitive.to_string();
+    }
+    match itype.kind {
+        TypeKind::String => "*mut UnityStringInner".to_string(),
+        TypeKind::Ptr | TypeKind::FnPtr => "*mut c_void".to_string(),
+        _ if itype.value_type => format!("[u8; {}]", field_size(itype)),
+        _ => "*mut ObjectInner".to_string(),
+    }
+}
+
+fn field_size(itype: &TypeInner) -> usize {
+    usize::try_from(itype.size).unwrap_or(std::mem::size_of::<usize>())
+}
+
+/// Type usable with `Thunk`, `None` for signatures a native call cannot express safely
+fn thunk_type(itype: &TypeInner) -> Option<String> {
+    if itype.byref {
+        return Some("*mut c_void".to_string());
+    }
+    if itype.is_void() {
+        return Some("()".to_string());
+    }
+    if let Some(primitive) = primitive_type(itype.kind) {
+        return Some(primitive.to_string());
+    }
+    match itype.kind {
+        TypeKind::Var | TypeKind::MVar => None,
+        _ if itype.value_type => None,
+        _ => Some(field_type(itype)),
+    }
+}
+
+// `System.Boolean` is stored and passed as `u8`, a `bool` would be undefined behavior
+// for any byte other than 0 or 1. Getters compare against 0 instead.
+fn primitive_type(kind: TypeKind) -> Option<&'static str> {
+    Some(match kind {
+        TypeKind::Boolean => "u8",
+        TypeKind::Char => "u16",
+        TypeKind::I1 => "i8",
+        TypeKind::U1 => "u8",
+        TypeKind::I2 => "i16",
+        TypeKind::U2 => "u16",
+        TypeKind::I4 => "i32",
+        TypeKind::U4 => "u32",
+        TypeKind::I8 => "i64",
+        TypeKind::U8 => "u64",
+        TypeKind::R4 => "f32",
+        TypeKind::R8 => "f64",
+        TypeKind::I => "isize",
+        TypeKind::U => "usize",
+        _ => return None,
+    })
+}
+
+fn is_boolean(itype: &TypeInner) -> bool {
+    !itype.byref && itype.kind == TypeKind::Boolean
+}
+
+fn tuple_type(types: &[String]) -> String {
+    match types {
+        [] => "()".to_string(),
+        [single] => format!("({},)", single),
+        _ => format!("({})", types.join(", ")),
+    }
+}
+
+fn rust_identifier(name: &str) -> String {
+    let ident = sanitize_identifier(name);
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+// `TakeDamage` -> `take_damage`, `get_Health` -> `get_health`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lower = false;
+    for c in sanitize_identifier(name).chars() {
+        if c.is_ascii_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        snake.push(c.to_ascii_lowercase());
+    }
+    rust_identifier(&snake)
+}
+
+fn module_name(namespace: &str) -> String {
+    if namespace.is_empty() {
+        "global".to_string()
+    } else {
+        snake_case(&namespace.replace('.', "_"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_dump::fixture;
+
+    // compiled by tests/rust_bindings.rs, regenerate with IL2CPP_RS_BLESS=1 after output changes
+    const GOLDEN: &str = "tests/fixtures/rust_bindings.rs";
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn bindings_match_compiled_fixture() {
+        let mut out = Vec::new();
+        write_rust_bindings(&fixture::assemblies(), &mut out, &DumpFilter::default()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN);
+        if std::env::var_os("IL2CPP_RS_BLESS").is_some() {
+            std::fs::write(&path, &out).unwrap();
+        }
+        assert_eq!(out, std::fs::read_to_string(path).unwrap());
+    }
+
+    #[test]
+    fn module_names_do_not_collide() {
+        let mut out = Vec::new();
+        write_rust_bindings(&fixture::assemblies(), &mut out, &DumpFilter::default()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("pub mod game_data {"));
+        assert!(out.contains("pub mod game_data_1 {"));
+        assert!(out.contains("pub mod global {"));
+    }
+
+    #[test]
+    fn identifiers_are_rust() {
+        assert_eq!(snake_case("TakeDamage"), "take_damage");
+        assert_eq!(snake_case("get_Health"), "get_health");
+        assert_eq!(rust_identifier("type"), "type_");
+        assert_eq!(
+            rust_identifier("<Alive>k__BackingField"),
+            "_Alive_k__BackingField"
+        );
+        assert_eq!(module_name(""), "global");
+        assert_eq!(module_name("UnityEngine.UI"), "unity_engine_ui");
+    }
+}
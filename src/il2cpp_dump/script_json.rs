@@ -0,0 +1,519 @@
+use crate::il2cpp::classes::assembly::Assembly;
+use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::method::MethodInner;
+use crate::il2cpp::module_base;
+use crate::il2cpp_dump::DumpFilter;
+use crate::il2cpp_dump::c_header::{ClassNames, c_identifier};
+use crate::il2cpp_offline::metadata::GlobalMetadata;
+use crate::il2cpp_offline::registration::{BinaryMetadata, MetadataUsageKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+
+// a struct holding itself by value cannot compile, this only guards corrupt metadata
+const MAX_STRUCT_DEPTH: usize = 16;
+
+// What `ScriptJson::from_assemblies` exports
+#[derive(Debug, Clone, Default)]
+pub struct ScriptJsonOptions {
+    pub filter: DumpFilter,
+    /// Base the RVAs are relative to, the loaded il2cpp module when `None`
+    pub module_base: Option<usize>,
+}
+
+/// Symbols in the Il2CppDumper `script.json` layout read by the IDA, Ghidra and Binary Ninja
+/// loader scripts, every address is an RVA into GameAssembly.
+/// `from_assemblies` fills `methods` and `addresses`. The il2cpp API does not expose the
+/// metadata usage globals behind the other lists, `add_metadata_usages` reads them from a
+/// binary analyzed by `il2cpp_offline`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScriptJson {
+    #[serde(rename = "ScriptMethod")]
+    pub methods: Vec<ScriptMethod>,
+    #[serde(rename = "ScriptString")]
+    pub strings: Vec<ScriptString>,
+    #[serde(rename = "ScriptMetadata")]
+    pub metadata: Vec<ScriptMetadata>,
+    #[serde(rename = "ScriptMetadataMethod")]
+    pub metadata_methods: Vec<ScriptMetadataMethod>,
+    /// Sorted method starts, loaders use them to split functions
+    pub addresses: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScriptMethod {
+    pub address: u64,
+    /// `Namespace.Class$$Method`
+    pub name: String,
+    /// C prototype using the structs of `il2cpp_dump::c_header`
+    pub signature: String,
+    /// Return type then parameters as the wasm32 C ABI passes them, `v` void, `i` int or
+    /// pointer, `j` 64 bit int, `f`/`d` float. Structs go through memory, so returning one
+    /// is `v` with a leading pointer argument.
+    pub type_signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScriptString {
+    pub address: u64,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScriptMetadata {
+    pub address: u64,
+    pub name: String,
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScriptMetadataMethod {
+    pub address: u64,
+    pub name: String,
+    pub method_address: u64,
+}
+
+impl ScriptJson {
+    /// Collect every compiled method of the selected classes, methods without native code
+    /// (abstract, open generic) or outside the module are skipped
+    pub fn from_assemblies(
+        assemblies: &[Assembly],
+        options: &ScriptJsonOptions,
+    ) -> Result<Self, String> {
+        let base = match options.module_base {
+            Some(base) => base,
+            None => module_base()?,
+        };
+        let names = ClassNames::new(assemblies);
+
+        let mut script = Self::default();
+        for (_, class) in options.filter.classes(assemblies) {
+            for method in class.methods.read().iter() {
+                let va = method.code_address;
+                if va == 0 || va < base {
+                    continue;
+                }
+                script.methods.push(ScriptMethod {
+                    address: (va - base) as u64,
+                    name: format!("{}$${}", class.qualified_name(), method.name),
+                    signature: signature(class, method, &names),
+                    type_signature: type_signature(method, &names),
+                });
+            }
+        }
+        script.addresses = script.methods.iter().map(|m| m.address).collect();
+        script.addresses.sort_unstable();
+        script.addresses.dedup();
+        Ok(script)
+    }
+
+    /// Add the metadata usage globals of `binary`, through which generated code reaches
+    /// string literals (`strings`), classes, types and fields (`metadata`) and methods
+    /// (`metadata_methods`). `base` is the address `binary` is relative to, its
+    /// `Executable::image_base` unless rebased. Usages below `base` and those naming
+    /// something the tables do not hold are skipped.
+    pub fn add_metadata_usages(
+        &mut self,
+        metadata: &GlobalMetadata,
+        binary: &BinaryMetadata,
+        base: u64,
+    ) -> Result<(), String> {
+        let declaring_types = metadata.declaring_types()?;
+        let type_name = |index: usize| -> Option<String> {
+            let definition = metadata.type_definitions.get(index)?;
+            let name = metadata.string(definition.name_index).ok()?;
+            let namespace = metadata.string(definition.namespace_index).ok()?;
+            Some(match declaring_types.get(&index) {
+                Some(declaring_type) => format!("{}.{}", declaring_type, name),
+                None if namespace.is_empty() => name.to_string(),
+                None => format!("{}.{}", namespace, name),
+            })
+        };
+        let method_name = |index: usize| -> Option<(String, String)> {
+            let method = metadata.methods.get(index)?;
+            let class = type_name(usize::try_from(method.declaring_type).ok()?)?;
+            Some((class, metadata.string(method.name_index).ok()?.to_string()))
+        };
+        let rva = |address: u64| address.saturating_sub(base);
+        let generic_methods: HashMap<usize, _> = binary
+            .generic_methods
+            .iter()
+            .map(|method| (method.method_spec, method))
+            .collect();
+
+        for usage in &binary.metadata_usages {
+            let Some(address) = usage.address.checked_sub(base) else {
+                continue;
+            };
+            match usage.kind {
+                MetadataUsageKind::StringLiteral => {
+                    let Ok(value) = metadata.string_literal(usage.index) else {
+                        continue;
+                    };
+                    self.strings.push(ScriptString { address, value });
+                }
+                MetadataUsageKind::TypeInfo | MetadataUsageKind::Il2CppType => {
+                    let Some(Some(resolved)) = binary.types.get(usage.index) else {
+                        continue;
+                    };
+                    let (suffix, signature) = match usage.kind {
+                        MetadataUsageKind::TypeInfo => ("TypeInfo", "Il2CppClass*"),
+                        _ => ("var", "Il2CppType*"),
+                    };
+                    self.metadata.push(ScriptMetadata {
+                        address,
+                        name: format!("{}_{}", resolved.name, suffix),
+                        signature: Some(signature.to_string()),
+                    });
+                }
+                MetadataUsageKind::FieldInfo => {
+                    let Some(field_ref) = metadata.field_refs.get(usage.index) else {
+                        continue;
+                    };
+                    let Some(resolved) = binary.type_at(field_ref.type_index) else {
+                        continue;
+                    };
+                    let field = resolved
+                        .definition
+                        .and_then(|index| metadata.type_definitions.get(index))
+                        .and_then(|definition| {
+                            let first = usize::try_from(definition.field_start).ok()?;
+                            let index = usize::try_from(field_ref.field_index).ok()?;
+                            metadata.fields.get(first + index)
+                        });
+                    let Some(Ok(field)) = field.map(|field| metadata.string(field.name_index))
+                    else {
+                        continue;
+                    };
+                    self.metadata.push(ScriptMetadata {
+                        address,
+                        name: format!("Field${}.{}", resolved.name, field),
+                        signature: None,
+                    });
+                }
+                MetadataUsageKind::MethodDef => {
+                    let Some((class, name)) = method_name(usage.index) else {
+                        continue;
+                    };
+                    let pointer = binary.methods.get(usage.index).and_then(|m| m.pointer);
+                    self.metadata_methods.push(ScriptMetadataMethod {
+                        address,
+                        name: format!("Method${}.{}()", class, name),
+                        method_address: pointer.map_or(0, rva),
+                    });
+                }
+                // instances without compiled code are not in the generic method table
+                MetadataUsageKind::MethodRef => {
+                    let Some(method) = generic_methods.get(&usage.index) else {
+                        continue;
+                    };
+                    let Some((class, name)) = method_name(method.method_definition) else {
+                        continue;
+                    };
+                    self.metadata_methods.push(ScriptMetadataMethod {
+                        address,
+                        name: format!(
+                            "Method${}{}.{}{}()",
+                            class.split('`').next().unwrap_or_default(),
+                            generic_arguments(&method.class_arguments),
+                            name,
+                            generic_arguments(&method.method_arguments)
+                        ),
+                        method_address: rva(method.pointer),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(std::io::Error::other)
+    }
+}
+
+// `ret Ns_Class__Method (struct Ns_Class_o* __this, ..., const MethodInfo* method);`
+fn signature(class: &ClassInner, method: &MethodInner, names: &ClassNames) -> String {
+    let mut params = Vec::new();
+    if !method.is_static {
+        params.push(format!("struct {}_o* __this", names.prefix(class)));
+    }
+    for arg in method.args.read().iter() {
+        params.push(format!(
+            "{} {}",
+            names.c_type(&arg.itype),
+            c_identifier(&arg.name)
+        ));
+    }
+    params.push("const MethodInfo* method".to_string());
+    format!(
+        "{} {} ({});",
+        names.c_type(&method.return_type),
+        c_identifier(&format!("{}$${}", class.qualified_name(), method.name)),
+        params.join(", ")
+    )
+}
+
+fn generic_arguments(arguments: &[String]) -> String {
+    if arguments.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", arguments.join(","))
+    }
+}
+
+fn type_signature(method: &MethodInner, names: &ClassNames) -> String {
+    let mut signature = String::new();
+    let mut params = String::new();
+    match passing(&method.return_type, names, 0) {
+        _ if method.return_type.is_void() => signature.push('v'),
+        Passing::Scalar(letter) => signature.push(letter),
+        Passing::Ignored => signature.push('v'),
+        // written to memory the caller passes a pointer to, ahead of every argument
+        Passing::Memory => {
+            signature.push('v');
+            params.push('i');
+        }
+    }
+    if !method.is_static {
+        params.push('i');
+    }
+    for arg in method.args.read().iter() {
+        match passing(&arg.itype, names, 0) {
+            Passing::Scalar(letter) => params.push(letter),
+            Passing::Memory => params.push('i'),
+            Passing::Ignored => {}
+        }
+    }
+    // the trailing MethodInfo*
+    params.push('i');
+    signature + &params
+}
+
+// How the wasm32 C ABI the signature letters come from passes a value
+enum Passing {
+    Scalar(char),
+    /// Structs are copied to memory and passed by pointer
+    Memory,
+    /// Empty structs take no argument at all
+    Ignored,
+}
+
+fn passing(itype: &TypeInner, names: &ClassNames, depth: usize) -> Passing {
+    if itype.byref || !itype.value_type {
+        return Passing::Scalar('i');
+    }
+    match itype.kind {
+        TypeKind::I8 | TypeKind::U8 => return Passing::Scalar('j'),
+        TypeKind::R4 => return Passing::Scalar('f'),
+        TypeKind::R8 => return Passing::Scalar('d'),
+        kind if kind.is_primitive() || matches!(kind, TypeKind::I | TypeKind::U) => {
+            return Passing::Scalar('i');
+        }
+        _ => {}
+    }
+    let Some(class) = names.index.get(itype.name.as_str()) else {
+        return Passing::Memory;
+    };
+    // enums are their `value__`, a struct of one scalar field is passed as that scalar
+    let fields = class.fields.read();
+    let instance: Vec<_> = fields.iter().filter(|f| !f.static_field).collect();
+    match instance.as_slice() {
+        [] if !class.enum_type => Passing::Ignored,
+        [field] if depth < MAX_STRUCT_DEPTH => passing(&field.itype, names, depth + 1),
+        _ => Passing::Memory,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::method::METHOD_ATTRIBUTE_STATIC;
+    use crate::il2cpp_dump::fixture::{self, add_method_at, class, itype};
+    use crate::il2cpp_offline::registration::{
+        GenericMethodAddress, MetadataUsage, MethodAddresses, Registrations, ResolvedType,
+    };
+
+    const BASE: usize = 0x10000;
+
+    fn script() -> ScriptJson {
+        let mut assemblies = fixture::assemblies();
+        let tools = class("Game", "Tools", "System.Object", false);
+        let void = itype("System.Void", TypeKind::Void, 0, false);
+        let stats = itype("Game.Data.Stats", TypeKind::ValueType, 8, true);
+        let marker = itype("Marker", TypeKind::ValueType, 0, true);
+        let long = itype("System.Int64", TypeKind::I8, 8, true);
+        let float = itype("System.Single", TypeKind::R4, 4, true);
+        let args = [("stats", stats.clone()), ("factor", long)];
+        let flags = METHOD_ATTRIBUTE_STATIC;
+        add_method_at(&tools, "Scale", float, &args, flags, BASE + 0x300);
+        add_method_at(&tools, "Copy", stats, &[], 0, BASE + 0x200);
+        add_method_at(
+            &tools,
+            "Touch",
+            void.clone(),
+            &[("marker", marker)],
+            0,
+            BASE + 0x100,
+        );
+        add_method_at(&tools, "Unmapped", void.clone(), &[], 0, 0x800);
+        add_method_at(&tools, "Abstract", void, &[], 0, 0);
+        assemblies[0].classes.push(tools);
+
+        let options = ScriptJsonOptions {
+            module_base: Some(BASE),
+            ..Default::default()
+        };
+        ScriptJson::from_assemblies(&assemblies, &options).unwrap()
+    }
+
+    #[test]
+    fn methods_are_named_by_class_and_relative_to_the_module() {
+        let script = script();
+        let names: Vec<_> = script.methods.iter().map(|m| m.name.as_str()).collect();
+        // the fixture's own methods and `Abstract` have no code, `Unmapped` is below the base
+        assert_eq!(
+            names,
+            ["Game.Tools$$Scale", "Game.Tools$$Copy", "Game.Tools$$Touch"]
+        );
+        let addresses: Vec<_> = script.methods.iter().map(|m| m.address).collect();
+        assert_eq!(addresses, [0x300, 0x200, 0x100]);
+        assert_eq!(script.addresses, [0x100, 0x200, 0x300]);
+    }
+
+    #[test]
+    fn signatures_use_the_header_structs() {
+        let script = script();
+        assert_eq!(
+            script.methods[0].signature,
+            "float Game_Tools__Scale (struct Game_Data_Stats_o stats, int64_t factor, \
+             const MethodInfo* method);"
+        );
+        assert_eq!(
+            script.methods[1].signature,
+            "struct Game_Data_Stats_o Game_Tools__Copy (struct Game_Tools_o* __this, \
+             const MethodInfo* method);"
+        );
+    }
+
+    #[test]
+    fn structs_follow_the_wasm_abi_in_type_signatures() {
+        let script = script();
+        let signatures: Vec<_> = script
+            .methods
+            .iter()
+            .map(|m| m.type_signature.as_str())
+            .collect();
+        // Stats by pointer, a returned Stats through a leading pointer, the empty Marker dropped
+        assert_eq!(signatures, ["fiji", "viii", "vii"]);
+    }
+
+    fn resolved(name: &str, kind: TypeKind, definition: Option<usize>) -> Option<ResolvedType> {
+        Some(ResolvedType {
+            name: name.to_string(),
+            kind,
+            attrs: 0,
+            byref: false,
+            value_type: false,
+            size: 8,
+            definition,
+        })
+    }
+
+    #[test]
+    fn metadata_usages_are_named_from_the_offline_tables() {
+        let metadata =
+            GlobalMetadata::parse(crate::il2cpp_offline::fixture::metadata(24, 2)).unwrap();
+        let base = BASE as u64;
+        let mut types = vec![None; 8];
+        types[5] = resolved("Game.Player", TypeKind::Class, Some(0));
+        types[7] = resolved("System.Int32", TypeKind::I4, None);
+        let mut methods = vec![MethodAddresses::default(); metadata.methods.len()];
+        methods[0].pointer = Some(base + 0x40);
+        let usage = |offset: u64, kind: MetadataUsageKind, index: usize| MetadataUsage {
+            address: base + offset,
+            kind,
+            index,
+        };
+        let binary = BinaryMetadata {
+            registrations: Registrations {
+                code_registration: None,
+                metadata_registration: 0,
+            },
+            types,
+            field_offsets: Vec::new(),
+            methods,
+            generic_methods: vec![GenericMethodAddress {
+                method_spec: 0,
+                method_definition: 0,
+                class_arguments: vec!["System.Int32".to_string()],
+                method_arguments: Vec::new(),
+                pointer: base + 0x80,
+                invoker: None,
+            }],
+            metadata_usages: vec![
+                usage(0x1000, MetadataUsageKind::StringLiteral, 1),
+                usage(0x1008, MetadataUsageKind::TypeInfo, 5),
+                usage(0x1010, MetadataUsageKind::MethodDef, 0),
+                usage(0x1018, MetadataUsageKind::FieldInfo, 0),
+                usage(0x1020, MetadataUsageKind::Il2CppType, 7),
+                usage(0x1028, MetadataUsageKind::MethodRef, 0),
+                // skipped: no compiled instance, nothing at the index, below the base
+                usage(0x1030, MetadataUsageKind::MethodRef, 9),
+                usage(0x1038, MetadataUsageKind::StringLiteral, 7),
+                usage(0x1040, MetadataUsageKind::TypeInfo, 3),
+                MetadataUsage {
+                    address: 0x800,
+                    kind: MetadataUsageKind::StringLiteral,
+                    index: 0,
+                },
+            ],
+        };
+
+        let mut script = ScriptJson::default();
+        script
+            .add_metadata_usages(&metadata, &binary, base)
+            .unwrap();
+        let strings: Vec<_> = script
+            .strings
+            .iter()
+            .map(|s| (s.address, s.value.as_str()))
+            .collect();
+        assert_eq!(strings, [(0x1000, "World")]);
+        let entries: Vec<_> = script
+            .metadata
+            .iter()
+            .map(|m| (m.address, m.name.as_str(), m.signature.as_deref()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (0x1008, "Game.Player_TypeInfo", Some("Il2CppClass*")),
+                (0x1018, "Field$Game.Player.name", None),
+                (0x1020, "System.Int32_var", Some("Il2CppType*")),
+            ]
+        );
+        let methods: Vec<_> = script
+            .metadata_methods
+            .iter()
+            .map(|m| (m.address, m.name.as_str(), m.method_address))
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                (0x1010, "Method$Game.Player.TakeDamage()", 0x40),
+                (
+                    0x1028,
+                    "Method$Game.Player<System.Int32>.TakeDamage()",
+                    0x80
+                ),
+            ]
+        );
+    }
+}
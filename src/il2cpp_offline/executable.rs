@@ -0,0 +1,583 @@
+use crate::il2cpp_offline::Cursor;
+use std::path::Path;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7FELF";
+const MACHO_MAGIC_32: u32 = 0xFEED_FACE;
+const MACHO_MAGIC_64: u32 = 0xFEED_FACF;
+// universal binaries store their header big endian
+const FAT_MAGIC: u32 = 0xCAFE_BABE;
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PF_X: u32 = 1;
+const DT_RELA: u64 = 7;
+const DT_RELASZ: u64 = 8;
+const R_X86_64_RELATIVE: u32 = 8;
+const R_AARCH64_RELATIVE: u32 = 1027;
+
+const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+
+const LC_SEGMENT: u32 = 0x1;
+const LC_SEGMENT_64: u32 = 0x19;
+const VM_PROT_EXECUTE: u32 = 0x4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutableFormat {
+    Elf,
+    Pe,
+    MachO,
+}
+
+/// A mapped range of the file
+#[derive(Debug, Clone, Copy)]
+pub struct Segment {
+    pub address: u64,
+    pub file_offset: usize,
+    pub file_size: usize,
+    pub executable: bool,
+}
+
+/// GameAssembly.dll, libil2cpp.so or the il2cpp Mach-O binary, mapped the way the loader would.
+/// ELF RELA relative relocations are applied so pointers in data read as addresses.
+/// Packed Android relocations and Mach-O chained fixups are not decoded.
+pub struct Executable {
+    pub format: ExecutableFormat,
+    pub is_64: bool,
+    /// Preferred load address, RVAs are relative to it
+    pub image_base: u64,
+    pub segments: Vec<Segment>,
+    data: Vec<u8>,
+}
+
+impl Executable {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let data =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(data)
+    }
+
+    pub fn parse(data: Vec<u8>) -> Result<Self, String> {
+        if data.starts_with(ELF_MAGIC) {
+            return parse_elf(data);
+        }
+        if data.starts_with(b"MZ") {
+            return parse_pe(data);
+        }
+        let magic = Cursor::new(&data).u32()?;
+        match magic {
+            MACHO_MAGIC_32 | MACHO_MAGIC_64 => parse_macho(data, 0),
+            _ if u32::from_be_bytes(magic.to_le_bytes()) == FAT_MAGIC => parse_fat(data),
+            _ => Err("Unknown executable format, expected ELF, PE or Mach-O".to_string()),
+        }
+    }
+
+    pub fn pointer_size(&self) -> usize {
+        if self.is_64 { 8 } else { 4 }
+    }
+
+    /// File offset of the virtual address `address`
+    pub fn offset_of(&self, address: u64) -> Option<usize> {
+        self.segments.iter().find_map(|segment| {
+            let delta = address.checked_sub(segment.address)? as usize;
+            (delta < segment.file_size).then_some(segment.file_offset + delta)
+        })
+    }
+
+    pub fn is_mapped(&self, address: u64) -> bool {
+        self.offset_of(address).is_some()
+    }
+
+    /// `len` bytes at `address`, all within one segment
+    pub fn bytes_at(&self, address: u64, len: usize) -> Result<&[u8], String> {
+        self.segments
+            .iter()
+            .find_map(|segment| {
+                let delta = address.checked_sub(segment.address)? as usize;
+                let end = delta.checked_add(len)?;
+                (delta < segment.file_size && end <= segment.file_size)
+                    .then(|| &self.data[segment.file_offset + delta..segment.file_offset + end])
+            })
+            .ok_or(format!("Address 0x{:X} is not mapped", address))
+    }
+
+    pub fn read_u32(&self, address: u64) -> Result<u32, String> {
+        Cursor::new(self.bytes_at(address, 4)?).u32()
+    }
+
+    pub fn read_i32(&self, address: u64) -> Result<i32, String> {
+        Cursor::new(self.bytes_at(address, 4)?).i32()
+    }
+
+    /// A pointer-sized value, counts in il2cpp registration structs are padded to this size
+    pub fn read_pointer(&self, address: u64) -> Result<u64, String> {
+        let mut cursor = Cursor::new(self.bytes_at(address, self.pointer_size())?);
+        if self.is_64 {
+            cursor.u64()
+        } else {
+            cursor.u32().map(u64::from)
+        }
+    }
+
+    pub fn is_executable(&self, address: u64) -> bool {
+        self.segments.iter().any(|segment| {
+            segment.executable
+                && address >= segment.address
+                && address - segment.address < segment.file_size as u64
+        })
+    }
+
+    /// `count` consecutive pointers starting at `address`
+    pub fn read_pointers(&self, address: u64, count: usize) -> Result<Vec<u64>, String> {
+        let size = self.pointer_size();
+        let bytes = self.bytes_at(address, count * size)?;
+        Ok(bytes
+            .chunks_exact(size)
+            .map(|chunk| {
+                if self.is_64 {
+                    u64::from_le_bytes(chunk.try_into().unwrap())
+                } else {
+                    u32::from_le_bytes(chunk.try_into().unwrap()) as u64
+                }
+            })
+            .collect())
+    }
+
+    pub fn read_i32s(&self, address: u64, count: usize) -> Result<Vec<i32>, String> {
+        let bytes = self.bytes_at(address, count * 4)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Null terminated string at `address`
+    pub fn read_c_string(&self, address: u64) -> Result<&str, String> {
+        let offset = self
+            .offset_of(address)
+            .ok_or(format!("Address 0x{:X} is not mapped", address))?;
+        let bytes = &self.data[offset..];
+        let end = bytes
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(format!("String at 0x{:X} is not terminated", address))?;
+        std::str::from_utf8(&bytes[..end]).map_err(|e| e.to_string())
+    }
+
+    /// Pointer-aligned addresses in non-executable segments holding `value`
+    pub fn find_pointer(&self, value: u64) -> Vec<u64> {
+        let size = self.pointer_size();
+        let pattern = if self.is_64 {
+            value.to_le_bytes().to_vec()
+        } else {
+            (value as u32).to_le_bytes().to_vec()
+        };
+        let mut hits = Vec::new();
+        for segment in self.segments.iter().filter(|s| !s.executable) {
+            let bytes = &self.data[segment.file_offset..segment.file_offset + segment.file_size];
+            for (index, chunk) in bytes.chunks_exact(size).enumerate() {
+                if chunk == pattern.as_slice() {
+                    hits.push(segment.address + (index * size) as u64);
+                }
+            }
+        }
+        hits
+    }
+
+    /// Addresses of `needle` anywhere in the mapped segments
+    pub fn find_bytes(&self, needle: &[u8]) -> Vec<u64> {
+        let mut hits = Vec::new();
+        for segment in &self.segments {
+            let bytes = &self.data[segment.file_offset..segment.file_offset + segment.file_size];
+            for (index, window) in bytes.windows(needle.len()).enumerate() {
+                if window == needle {
+                    hits.push(segment.address + index as u64);
+                }
+            }
+        }
+        hits
+    }
+
+    fn segment(
+        address: u64,
+        file_offset: u64,
+        file_size: u64,
+        executable: bool,
+        data_len: usize,
+    ) -> Result<Segment, String> {
+        let file_offset = file_offset as usize;
+        let file_size = file_size as usize;
+        if file_offset
+            .checked_add(file_size)
+            .is_none_or(|end| end > data_len)
+        {
+            return Err(format!(
+                "Segment at 0x{:X} extends past the end of the file",
+                address
+            ));
+        }
+        Ok(Segment {
+            address,
+            file_offset,
+            file_size,
+            executable,
+        })
+    }
+}
+
+fn parse_elf(mut data: Vec<u8>) -> Result<Executable, String> {
+    let is_64 = match data.get(4) {
+        Some(1) => false,
+        Some(2) => true,
+        _ => return Err("Invalid ELF class".to_string()),
+    };
+    if data.get(5) != Some(&1) {
+        return Err("Big endian ELF files are not supported".to_string());
+    }
+
+    let mut c = Cursor::new(&data);
+    c.skip(18)?; // e_ident, e_type
+    let machine = c.u16()?;
+    c.skip(4)?; // e_version
+    let word = |c: &mut Cursor| -> Result<u64, String> {
+        if is_64 {
+            c.u64()
+        } else {
+            c.u32().map(u64::from)
+        }
+    };
+    word(&mut c)?; // e_entry
+    let phoff = word(&mut c)?;
+    word(&mut c)?; // e_shoff
+    c.skip(6)?; // e_flags, e_ehsize
+    let phentsize = c.u16()? as u64;
+    let phnum = c.u16()? as u64;
+
+    let mut segments = Vec::new();
+    let mut dynamic = None;
+    for index in 0..phnum {
+        let start = (phoff + index * phentsize) as usize;
+        let mut c = Cursor::new(data.get(start..).unwrap_or_default());
+        let p_type = c.u32()?;
+        let (flags, offset, vaddr, filesz) = if is_64 {
+            let flags = c.u32()?;
+            let offset = c.u64()?;
+            let vaddr = c.u64()?;
+            c.skip(8)?; // p_paddr
+            (flags, offset, vaddr, c.u64()?)
+        } else {
+            let offset = c.u32()? as u64;
+            let vaddr = c.u32()? as u64;
+            c.skip(4)?; // p_paddr
+            let filesz = c.u32()? as u64;
+            c.skip(4)?; // p_memsz
+            (c.u32()?, offset, vaddr, filesz)
+        };
+        match p_type {
+            PT_LOAD => segments.push(Executable::segment(
+                vaddr,
+                offset,
+                filesz,
+                (flags & PF_X) != 0,
+                data.len(),
+            )?),
+            PT_DYNAMIC => dynamic = Some((vaddr, filesz)),
+            _ => {}
+        }
+    }
+    let image_base = segments.iter().map(|s| s.address).min().unwrap_or(0);
+    let mut executable = Executable {
+        format: ExecutableFormat::Elf,
+        is_64,
+        image_base,
+        segments,
+        data: Vec::new(),
+    };
+
+    // position independent 64 bit libraries leave relocated pointers as RELA addends
+    if let (true, Some((address, size))) = (is_64, dynamic) {
+        executable.data = std::mem::take(&mut data);
+        let relocations = elf_relative_relocations(&executable, address, size, machine)?;
+        for (target, addend) in relocations {
+            let slot = executable
+                .offset_of(target)
+                .and_then(|offset| executable.data.get_mut(offset..offset + 8));
+            if let Some(slot) = slot {
+                slot.copy_from_slice(&addend.to_le_bytes());
+            }
+        }
+    } else {
+        executable.data = data;
+    }
+    Ok(executable)
+}
+
+fn elf_relative_relocations(
+    executable: &Executable,
+    dynamic: u64,
+    size: u64,
+    machine: u16,
+) -> Result<Vec<(u64, u64)>, String> {
+    let mut rela = None;
+    let mut rela_size = 0;
+    let mut c = Cursor::new(executable.bytes_at(dynamic, size as usize)?);
+    while !c.at_end() {
+        let (tag, value) = (c.u64()?, c.u64()?);
+        match tag {
+            0 => break,
+            DT_RELA => rela = Some(value),
+            DT_RELASZ => rela_size = value as usize,
+            _ => {}
+        }
+    }
+    let Some(rela) = rela else {
+        return Ok(Vec::new());
+    };
+
+    let relative = match machine {
+        0x3E => R_X86_64_RELATIVE,
+        0xB7 => R_AARCH64_RELATIVE,
+        _ => return Ok(Vec::new()),
+    };
+    let mut relocations = Vec::new();
+    let mut c = Cursor::new(executable.bytes_at(rela, rela_size)?);
+    while !c.at_end() {
+        let (offset, info, addend) = (c.u64()?, c.u64()?, c.u64()?);
+        if (info & 0xFFFF_FFFF) as u32 == relative {
+            relocations.push((offset, addend));
+        }
+    }
+    Ok(relocations)
+}
+
+fn parse_pe(data: Vec<u8>) -> Result<Executable, String> {
+    let mut c = Cursor::new(&data);
+    c.skip(0x3C)?;
+    let pe_offset = c.u32()? as usize;
+    let mut c = Cursor::new(data.get(pe_offset..).unwrap_or_default());
+    if c.u32()? != 0x0000_4550 {
+        return Err("Missing PE signature".to_string());
+    }
+    c.skip(2)?; // Machine
+    let section_count = c.u16()?;
+    c.skip(12)?; // TimeDateStamp, PointerToSymbolTable, NumberOfSymbols
+    let optional_size = c.u16()? as usize;
+    c.skip(2)?; // Characteristics
+
+    let optional = pe_offset + 24;
+    let mut o = Cursor::new(data.get(optional..).unwrap_or_default());
+    let is_64 = match o.u16()? {
+        0x10B => false,
+        0x20B => true,
+        magic => return Err(format!("Unknown PE optional header magic 0x{:X}", magic)),
+    };
+    let image_base = if is_64 {
+        o.skip(22)?;
+        o.u64()?
+    } else {
+        o.skip(26)?;
+        o.u32()? as u64
+    };
+
+    let mut segments = Vec::new();
+    let table = optional + optional_size;
+    for index in 0..section_count as usize {
+        let mut s = Cursor::new(data.get(table + index * 40..).unwrap_or_default());
+        s.skip(8)?; // Name
+        let virtual_size = s.u32()? as u64;
+        let virtual_address = s.u32()? as u64;
+        let raw_size = s.u32()? as u64;
+        let raw_offset = s.u32()? as u64;
+        s.skip(12)?; // relocations, line numbers
+        let characteristics = s.u32()?;
+        segments.push(Executable::segment(
+            image_base + virtual_address,
+            raw_offset,
+            // some linkers leave VirtualSize 0, otherwise the raw data is padded past it
+            if virtual_size == 0 {
+                raw_size
+            } else {
+                raw_size.min(virtual_size)
+            },
+            (characteristics & IMAGE_SCN_MEM_EXECUTE) != 0,
+            data.len(),
+        )?);
+    }
+    Ok(Executable {
+        format: ExecutableFormat::Pe,
+        is_64,
+        image_base,
+        segments,
+        data,
+    })
+}
+
+// picks the first 64 bit slice, the one il2cpp games ship for arm64 devices
+fn parse_fat(data: Vec<u8>) -> Result<Executable, String> {
+    let be = |offset: usize| -> Result<u32, String> {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes(b.try_into().unwrap()))
+            .ok_or("Truncated universal binary header".to_string())
+    };
+    let count = be(4)? as usize;
+    let mut slice = None;
+    for index in 0..count {
+        let entry = 8 + index * 20;
+        let cpu_type = be(entry)?;
+        let offset = be(entry + 8)? as usize;
+        if slice.is_none() || (cpu_type & CPU_ARCH_ABI64) != 0 {
+            slice = Some(offset);
+        }
+        if (cpu_type & CPU_ARCH_ABI64) != 0 {
+            break;
+        }
+    }
+    match slice {
+        Some(offset) => parse_macho(data, offset),
+        None => Err("Universal binary without slices".to_string()),
+    }
+}
+
+fn parse_macho(data: Vec<u8>, slice: usize) -> Result<Executable, String> {
+    let mut c = Cursor::new(data.get(slice..).unwrap_or_default());
+    let is_64 = c.u32()? == MACHO_MAGIC_64;
+    c.skip(12)?; // cputype, cpusubtype, filetype
+    let command_count = c.u32()?;
+    c.skip(if is_64 { 12 } else { 8 })?; // sizeofcmds, flags, reserved
+
+    let mut segments = Vec::new();
+    let mut image_base = None;
+    for _ in 0..command_count {
+        let command = c.u32()?;
+        let size = c.u32()? as usize;
+        let mut body = Cursor::new(c.bytes(size.saturating_sub(8))?);
+        if command != LC_SEGMENT && command != LC_SEGMENT_64 {
+            continue;
+        }
+        let name = body.bytes(16)?;
+        let (address, file_offset, file_size) = if command == LC_SEGMENT_64 {
+            let address = body.u64()?;
+            body.skip(8)?; // vmsize
+            (address, body.u64()?, body.u64()?)
+        } else {
+            let address = body.u32()? as u64;
+            body.skip(4)?; // vmsize
+            (address, body.u32()? as u64, body.u32()? as u64)
+        };
+        body.skip(4)?; // maxprot
+        let protection = body.u32()?;
+        if name.starts_with(b"__TEXT\0") {
+            image_base = Some(address);
+        }
+        if file_size == 0 {
+            continue; // __PAGEZERO and zero fill
+        }
+        segments.push(Executable::segment(
+            address,
+            slice as u64 + file_offset,
+            file_size,
+            (protection & VM_PROT_EXECUTE) != 0,
+            data.len(),
+        )?);
+    }
+    Ok(Executable {
+        format: ExecutableFormat::MachO,
+        is_64,
+        image_base: image_base.unwrap_or(0),
+        segments,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_offline::fixture::{self, Section};
+
+    // code, then data holding a pointer into the code and a string
+    fn sections(base: u64) -> Vec<Section> {
+        let mut text = Section::new(base + 0x1000, true);
+        text.bytes(&[0xC3; 0x20]);
+        let mut data = Section::new(base + 0x4000, false);
+        data.pointer(base + 0x1010);
+        data.bytes(b"GameAssembly\0");
+        vec![text, data]
+    }
+
+    fn check_mapping(executable: &Executable, base: u64) {
+        assert!(executable.is_64);
+        assert_eq!(
+            executable.read_pointer(base + 0x4000).unwrap(),
+            base + 0x1010
+        );
+        assert_eq!(
+            executable.read_c_string(base + 0x4008).unwrap(),
+            "GameAssembly"
+        );
+        assert!(executable.is_executable(base + 0x1010));
+        assert!(!executable.is_executable(base + 0x4000));
+        assert!(!executable.is_mapped(base + 0x1020));
+        assert!(executable.bytes_at(base + 0x4010, 0x10).is_err());
+        // ELF relocation addends hold the pointer too, they come after the data
+        assert_eq!(executable.find_pointer(base + 0x1010)[0], base + 0x4000);
+        assert_eq!(executable.find_bytes(b"Assembly"), vec![base + 0x400C]);
+    }
+
+    #[test]
+    fn elf_relative_relocations_are_applied() {
+        let data = fixture::elf64(&sections(0), true);
+        let executable = Executable::parse(data.clone()).unwrap();
+        assert_eq!(executable.format, ExecutableFormat::Elf);
+        assert_eq!(executable.image_base, 0x1000);
+        // the relocation table gets a segment of its own
+        assert_eq!(executable.segments.len(), 3);
+        let offset = executable.offset_of(0x4000).unwrap();
+        assert_eq!(data[offset..offset + 8], [0; 8]);
+        check_mapping(&executable, 0);
+
+        let executable = Executable::parse(fixture::elf64(&sections(0), false)).unwrap();
+        assert_eq!(executable.segments.len(), 2);
+        check_mapping(&executable, 0);
+    }
+
+    #[test]
+    fn pe_sections_map_at_the_image_base() {
+        let base = 0x1_8000_0000;
+        let executable = Executable::parse(fixture::pe64(&sections(base), base)).unwrap();
+        assert_eq!(executable.format, ExecutableFormat::Pe);
+        assert_eq!(executable.image_base, base);
+        // raw data is padded to the file alignment, only the virtual size is mapped
+        assert_eq!(executable.segments[0].file_size, 0x20);
+        assert_eq!(executable.segments[0].file_offset, 0x400);
+        check_mapping(&executable, base);
+    }
+
+    #[test]
+    fn macho_skips_zero_fill_segments() {
+        let base = 0x1_0000_0000;
+        let executable = Executable::parse(fixture::macho64(&sections(base))).unwrap();
+        assert_eq!(executable.format, ExecutableFormat::MachO);
+        assert_eq!(executable.image_base, base + 0x1000);
+        assert_eq!(executable.segments.len(), 2);
+        check_mapping(&executable, base);
+
+        let slices = [
+            (12, vec![0; 32]),
+            (0x0100_000C, fixture::macho64(&sections(base))),
+        ];
+        let executable = Executable::parse(fixture::fat(&slices)).unwrap();
+        assert_eq!(executable.segments[0].file_offset, 0x2000 + 0x100);
+        check_mapping(&executable, base);
+    }
+
+    #[test]
+    fn unknown_formats_are_rejected() {
+        let error = Executable::parse(vec![0; 64]).err().unwrap();
+        assert!(error.starts_with("Unknown executable format"), "{}", error);
+        let mut data = fixture::elf64(&sections(0), false);
+        data[5] = 2;
+        let error = Executable::parse(data).err().unwrap();
+        assert!(error.contains("Big endian"), "{}", error);
+    }
+}
@@ -0,0 +1,736 @@
+// A minimal global-metadata.dat in the layout of each supported version, shared by the tests:
+// Assembly-CSharp.dll with `Game.Player` (fields health/name, methods TakeDamage/.cctor), the
+// enum `Game.Mode` and `Stats` nested in Player, an empty mscorlib.dll and the string literals
+// "Hello" and "World". Code references Player's `name` field and, in 24, the metadata usage
+// slots 0 to 4 hold `"World"`, the TypeInfo of type 5, TakeDamage, that field and type 7.
+// Below it, ELF, PE and Mach-O writers and the registration structs of a matching binary.
+use crate::il2cpp_offline::metadata::METADATA_SANITY;
+use std::collections::HashMap;
+
+struct Writer {
+    version: (u32, u32),
+    strings: Vec<u8>,
+    indices: HashMap<String, u32>,
+    method_token: u32,
+}
+
+impl Writer {
+    fn string(&mut self, value: &str) -> u32 {
+        if let Some(index) = self.indices.get(value) {
+            return *index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.extend_from_slice(value.as_bytes());
+        self.strings.push(0);
+        self.indices.insert(value.to_string(), index);
+        index
+    }
+
+    fn image(&mut self, out: &mut Vec<u8>, name: &str, type_start: i32, type_count: u32) {
+        let name = self.string(name);
+        push(
+            out,
+            &[name as i32, 0, type_start, type_count as i32, -1, 0, -1, 1],
+        );
+        if self.version >= (24, 1) {
+            push(out, &[0, 0]);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn type_definition(
+        &mut self,
+        out: &mut Vec<u8>,
+        name: &str,
+        namespace: &str,
+        flags: u32,
+        field_start: i32,
+        field_count: u16,
+        method_start: i32,
+        method_count: u16,
+        nested: (i32, u16),
+        declaring: i32,
+        bitfield: u32,
+    ) {
+        let (name, namespace) = (self.string(name), self.string(namespace));
+        push(out, &[name as i32, namespace as i32]);
+        if self.version <= (24, 0) {
+            push(out, &[-1]);
+        }
+        push(out, &[5]);
+        if self.version <= (24, 5) {
+            push(out, &[6]);
+        }
+        push(out, &[declaring, 3, -1]);
+        if self.version <= (24, 1) {
+            push(out, &[-1, 0]);
+        }
+        push(
+            out,
+            &[
+                -1,
+                flags as i32,
+                field_start,
+                method_start,
+                -1,
+                -1,
+                nested.0,
+                -1,
+                -1,
+                -1,
+            ],
+        );
+        for count in [method_count, 0, field_count, 0, nested.1, 0, 0, 0] {
+            out.extend_from_slice(&count.to_le_bytes());
+        }
+        push(out, &[bitfield as i32, 0x0200_0001]);
+    }
+
+    fn method(
+        &mut self,
+        out: &mut Vec<u8>,
+        name: &str,
+        parameter_start: i32,
+        count: u16,
+        flags: u16,
+    ) {
+        self.method_token += 1;
+        let name = self.string(name);
+        push(out, &[name as i32, 0, 1]);
+        if self.version >= (31, 0) {
+            push(out, &[0x0800_0000]);
+        }
+        push(out, &[parameter_start]);
+        if self.version <= (24, 0) {
+            push(out, &[-1]);
+        }
+        push(out, &[-1]);
+        if self.version <= (24, 1) {
+            push(out, &[0; 5]);
+        }
+        push(out, &[0x0600_0000 + self.method_token as i32]);
+        for half in [flags, 0, 0xFFFF, count] {
+            out.extend_from_slice(&half.to_le_bytes());
+        }
+    }
+
+    fn field(&mut self, out: &mut Vec<u8>, name: &str, type_index: i32) {
+        let name = self.string(name);
+        push(out, &[name as i32, type_index]);
+        if self.version <= (24, 0) {
+            push(out, &[-1]);
+        }
+        push(out, &[0x0400_0001]);
+    }
+
+    fn parameter(&mut self, out: &mut Vec<u8>, name: &str, type_index: i32) {
+        let name = self.string(name);
+        push(out, &[name as i32, 0x0800_0001]);
+        if self.version <= (24, 0) {
+            push(out, &[-1]);
+        }
+        push(out, &[type_index]);
+    }
+}
+
+fn push(out: &mut Vec<u8>, values: &[i32]) {
+    for value in values {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// The fixture file for metadata `major.minor`, 24.0 and 24.1 share a header, as do 24.2-24.5
+pub(crate) fn metadata(major: u32, minor: u32) -> Vec<u8> {
+    let version = (major, minor);
+    let mut w = Writer {
+        version,
+        strings: Vec::new(),
+        indices: HashMap::new(),
+        method_token: 0,
+    };
+    let mut images = Vec::new();
+    w.image(&mut images, "Assembly-CSharp.dll", 0, 3);
+    w.image(&mut images, "mscorlib.dll", 3, 0);
+    let mut types = Vec::new();
+    w.type_definition(
+        &mut types,
+        "Player",
+        "Game",
+        0x10_0001,
+        0,
+        2,
+        0,
+        2,
+        (0, 1),
+        -1,
+        0,
+    );
+    w.type_definition(
+        &mut types,
+        "Mode",
+        "Game",
+        0x101,
+        2,
+        1,
+        2,
+        0,
+        (-1, 0),
+        -1,
+        3,
+    );
+    w.type_definition(
+        &mut types,
+        "Stats",
+        "",
+        0x10_0002,
+        3,
+        0,
+        2,
+        0,
+        (-1, 0),
+        0,
+        0,
+    );
+    let mut nested = Vec::new();
+    push(&mut nested, &[2]);
+    let mut fields = Vec::new();
+    w.field(&mut fields, "health", 7);
+    w.field(&mut fields, "name", 8);
+    w.field(&mut fields, "value__", 9);
+    let mut methods = Vec::new();
+    w.method(&mut methods, "TakeDamage", 0, 1, 0x86);
+    w.method(&mut methods, ".cctor", 1, 0, 0x1891);
+    let mut parameters = Vec::new();
+    w.parameter(&mut parameters, "amount", 10);
+    w.parameter(&mut parameters, "unused", 11);
+    let mut literals = Vec::new();
+    push(&mut literals, &[5, 0, 5, 5]);
+    let mut field_refs = Vec::new();
+    push(&mut field_refs, &[5, 1]);
+    let mut usage_pairs = Vec::new();
+    for (slot, (kind, index)) in [(5, 1), (1, 5), (3, 0), (4, 0), (2, 7)].iter().enumerate() {
+        push(&mut usage_pairs, &[slot as i32, (kind << 29) | index]);
+    }
+
+    let pairs = match version {
+        (24, 0) | (24, 1) => 33,
+        (24, _) => 32,
+        _ => 31,
+    };
+    let header_size = 8 + pairs * 8;
+    let images_slot = if version <= (24, 1) { 21 } else { 20 };
+    let (usage_pairs_slot, field_refs_slot) = match version {
+        (24, 0) | (24, 1) => (24, 25),
+        (24, _) => (23, 24),
+        // no usage pairs, an empty table
+        _ => (23, 22),
+    };
+    if major != 24 {
+        usage_pairs.clear();
+    }
+    let tables: [(usize, &[u8]); 11] = [
+        (0, &literals),
+        (1, b"HelloWorld"),
+        (2, &w.strings),
+        (5, &methods),
+        (10, &parameters),
+        (11, &fields),
+        (15, &nested),
+        (19, &types),
+        (images_slot, &images),
+        (usage_pairs_slot, &usage_pairs),
+        (field_refs_slot, &field_refs),
+    ];
+    let mut sections = vec![(0u32, 0u32); pairs];
+    let mut body = Vec::new();
+    for (slot, table) in tables {
+        sections[slot] = ((header_size + body.len()) as u32, table.len() as u32);
+        body.extend_from_slice(table);
+    }
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&METADATA_SANITY.to_le_bytes());
+    data.extend_from_slice(&major.to_le_bytes());
+    for (offset, size) in sections {
+        data.extend_from_slice(&offset.to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes());
+    }
+    data.extend_from_slice(&body);
+    data
+}
+
+/// A segment of a generated executable. Pointers written through `pointer` are recorded so
+/// the ELF writer can leave them to relative relocations.
+pub(crate) struct Section {
+    pub(crate) address: u64,
+    pub(crate) executable: bool,
+    pub(crate) data: Vec<u8>,
+    pub(crate) pointers: Vec<(u64, u64)>,
+}
+
+impl Section {
+    pub(crate) fn new(address: u64, executable: bool) -> Self {
+        Self {
+            address,
+            executable,
+            data: Vec::new(),
+            pointers: Vec::new(),
+        }
+    }
+
+    pub(crate) fn here(&self) -> u64 {
+        self.address + self.data.len() as u64
+    }
+
+    /// A pointer-sized value that is not an address, returns where it was written
+    pub(crate) fn word(&mut self, value: u64) -> u64 {
+        let at = self.here();
+        self.data.extend_from_slice(&value.to_le_bytes());
+        at
+    }
+
+    pub(crate) fn pointer(&mut self, value: u64) -> u64 {
+        let at = self.word(value);
+        if value != 0 {
+            self.pointers.push((at, value));
+        }
+        at
+    }
+
+    pub(crate) fn pointers(&mut self, values: &[u64]) -> u64 {
+        let at = self.here();
+        for value in values {
+            self.pointer(*value);
+        }
+        at
+    }
+
+    pub(crate) fn i32s(&mut self, values: &[i32]) -> u64 {
+        let at = self.here();
+        push(&mut self.data, values);
+        self.align();
+        at
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> u64 {
+        let at = self.here();
+        self.data.extend_from_slice(bytes);
+        self.align();
+        at
+    }
+
+    // keeps pointers aligned, `Executable::find_pointer` only looks there
+    fn align(&mut self) {
+        while !self.data.len().is_multiple_of(8) {
+            self.data.push(0);
+        }
+    }
+}
+
+/// An x86-64 shared library loading `sections`. With `relocate` the recorded pointers are
+/// zeroed in the file and restored by R_X86_64_RELATIVE entries, next to an R_X86_64_64
+/// entry that must not be applied.
+pub(crate) fn elf64(sections: &[Section], relocate: bool) -> Vec<u8> {
+    let mut loads: Vec<(u64, bool, Vec<u8>)> = sections
+        .iter()
+        .map(|section| {
+            let mut data = section.data.clone();
+            if relocate {
+                for (at, _) in &section.pointers {
+                    let offset = (at - section.address) as usize;
+                    data[offset..offset + 8].fill(0);
+                }
+            }
+            (section.address, section.executable, data)
+        })
+        .collect();
+    let mut dynamic = None;
+    if relocate {
+        let end = sections.iter().map(|s| s.here()).max().unwrap_or(0);
+        let mut rela = Section::new((end + 0xFFF) & !0xFFF, false);
+        for section in sections {
+            for (at, value) in &section.pointers {
+                rela.word(*at);
+                rela.word(8);
+                rela.word(*value);
+            }
+        }
+        let (at, _) = sections.iter().flat_map(|s| &s.pointers).next().unwrap();
+        rela.word(*at);
+        rela.word((1 << 32) | 1);
+        rela.word(0xDEAD);
+        let size = rela.data.len() as u64;
+        let table = rela.address;
+        let address = rela.here();
+        for (tag, value) in [(7, table), (8, size), (0, 0)] {
+            rela.word(tag);
+            rela.word(value);
+        }
+        dynamic = Some((address, 48));
+        loads.push((rela.address, false, rela.data));
+    }
+
+    let headers = loads.len() + dynamic.is_some() as usize;
+    let mut offset = (64 + 56 * headers as u64 + 15) & !15;
+    let mut out = Vec::new();
+    out.extend_from_slice(b"\x7FELF\x02\x01\x01");
+    out.resize(16, 0);
+    for half in [3u16, 0x3E] {
+        out.extend_from_slice(&half.to_le_bytes());
+    }
+    out.extend_from_slice(&1u32.to_le_bytes());
+    for word in [0u64, 64, 0] {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    out.extend_from_slice(&0u32.to_le_bytes());
+    for half in [64u16, 56, headers as u16, 64, 0, 0] {
+        out.extend_from_slice(&half.to_le_bytes());
+    }
+    let mut offsets = Vec::new();
+    for (address, executable, data) in &loads {
+        let flags = if *executable { 5 } else { 6 };
+        program_header(&mut out, 1, flags, offset, *address, data.len() as u64);
+        offsets.push(offset);
+        offset = (offset + data.len() as u64 + 15) & !15;
+    }
+    if let Some((address, size)) = dynamic {
+        let (base, _, _) = loads.last().unwrap();
+        let file_offset = offsets.last().unwrap() + (address - base);
+        program_header(&mut out, 2, 6, file_offset, address, size);
+    }
+    for ((_, _, data), offset) in loads.iter().zip(offsets) {
+        out.resize(offset as usize, 0);
+        out.extend_from_slice(data);
+    }
+    out
+}
+
+fn program_header(out: &mut Vec<u8>, kind: u32, flags: u32, offset: u64, address: u64, size: u64) {
+    out.extend_from_slice(&kind.to_le_bytes());
+    out.extend_from_slice(&flags.to_le_bytes());
+    for word in [offset, address, address, size, size, 0x1000] {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// A PE32+ image based at `image_base`, each section's raw data padded with 0xCC past its
+/// virtual size to a file alignment of 0x200
+pub(crate) fn pe64(sections: &[Section], image_base: u64) -> Vec<u8> {
+    let mut out = b"MZ".to_vec();
+    out.resize(0x3C, 0);
+    out.extend_from_slice(&0x40u32.to_le_bytes());
+    out.extend_from_slice(b"PE\0\0");
+    for half in [0x8664u16, sections.len() as u16] {
+        out.extend_from_slice(&half.to_le_bytes());
+    }
+    out.resize(out.len() + 12, 0);
+    for half in [0xF0u16, 0x2022] {
+        out.extend_from_slice(&half.to_le_bytes());
+    }
+    let optional = out.len();
+    out.extend_from_slice(&0x20Bu16.to_le_bytes());
+    out.resize(optional + 24, 0);
+    out.extend_from_slice(&image_base.to_le_bytes());
+    out.resize(optional + 0xF0, 0);
+
+    let mut raw_offset = 0x400;
+    let mut raw = Vec::new();
+    for section in sections {
+        let raw_size = (section.data.len() as u32 + 0x1FF) & !0x1FF;
+        let name: &[u8; 8] = if section.executable {
+            b".text\0\0\0"
+        } else {
+            b".data\0\0\0"
+        };
+        out.extend_from_slice(name);
+        let rva = (section.address - image_base) as u32;
+        for value in [section.data.len() as u32, rva, raw_size, raw_offset] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.resize(out.len() + 12, 0);
+        let characteristics: u32 = if section.executable {
+            0x6000_0020
+        } else {
+            0xC000_0040
+        };
+        out.extend_from_slice(&characteristics.to_le_bytes());
+        raw.push((raw_offset, raw_size));
+        raw_offset += raw_size;
+    }
+    for (section, (offset, size)) in sections.iter().zip(raw) {
+        out.resize(offset as usize, 0);
+        out.extend_from_slice(&section.data);
+        out.resize((offset + size) as usize, 0xCC);
+    }
+    out
+}
+
+/// An arm64 Mach-O dylib behind a `__PAGEZERO` segment, executable sections become `__TEXT`
+pub(crate) fn macho64(sections: &[Section]) -> Vec<u8> {
+    let commands = sections.len() as u32 + 1;
+    let mut out = Vec::new();
+    for value in [
+        0xFEED_FACFu32,
+        0x0100_000C,
+        0,
+        6,
+        commands,
+        commands * 72,
+        0,
+        0,
+    ] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    let mut offset = (32 + commands as u64 * 72 + 0xF) & !0xF;
+    segment_command(&mut out, b"__PAGEZERO", 0, 0x1000, 0, 0, 0);
+    let mut offsets = Vec::new();
+    for section in sections {
+        let (name, protection): (&[u8], u32) = if section.executable {
+            (b"__TEXT", 5)
+        } else {
+            (b"__DATA", 3)
+        };
+        let size = section.data.len() as u64;
+        segment_command(
+            &mut out,
+            name,
+            section.address,
+            size,
+            offset,
+            size,
+            protection,
+        );
+        offsets.push(offset);
+        offset = (offset + size + 0xF) & !0xF;
+    }
+    for (section, offset) in sections.iter().zip(offsets) {
+        out.resize(offset as usize, 0);
+        out.extend_from_slice(&section.data);
+    }
+    out
+}
+
+fn segment_command(
+    out: &mut Vec<u8>,
+    name: &[u8],
+    address: u64,
+    size: u64,
+    offset: u64,
+    file_size: u64,
+    protection: u32,
+) {
+    for value in [0x19u32, 72] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    let mut segment_name = [0u8; 16];
+    segment_name[..name.len()].copy_from_slice(name);
+    out.extend_from_slice(&segment_name);
+    for word in [address, size, offset, file_size] {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    for value in [protection, protection, 0, 0] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// A universal binary of `(cputype, slice)` pairs, slices aligned to 0x1000
+pub(crate) fn fat(slices: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in [0xCAFE_BABEu32, slices.len() as u32] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    let mut offset = 0x1000;
+    let mut placed = Vec::new();
+    for (cpu_type, slice) in slices {
+        for value in [*cpu_type, 0, offset, slice.len() as u32, 12] {
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+        placed.push(offset);
+        offset = (offset + slice.len() as u32 + 0xFFF) & !0xFFF;
+    }
+    for ((_, slice), offset) in slices.iter().zip(placed) {
+        out.resize(offset as usize, 0);
+        out.extend_from_slice(slice);
+    }
+    out
+}
+
+/// Where the code of the registration binary starts, relative to its base. Methods are at
+/// +0x10 (TakeDamage) and +0x20 (.cctor), `TakeDamage<System.Int32>` at +0x40 and the
+/// invokers at +0x80 and +0x90.
+pub(crate) const CODE: u64 = 0x1000;
+const DATA: u64 = 0x4000;
+
+/// The binary half of the fixture metadata and where `registration` put its parts
+pub(crate) struct Registration {
+    pub(crate) sections: Vec<Section>,
+    pub(crate) metadata_registration: u64,
+    pub(crate) code_registration: u64,
+    /// The globals metadata usages resolve into, in usage order
+    pub(crate) usage_slots: Vec<u64>,
+}
+
+/// The code and metadata registrations a compiler would emit for `metadata(major, minor)`,
+/// for 24.2 and later, loaded at `base`. The type table is
+///
+/// 0 `Game.Player`, 1 `Game.Mode`, 2 `Game.Player.Stats`, 3 `System.Object`,
+/// 4 `System.Int32[]`, 5 `Game.Player`, 6 `Game.Player&`, 7 `System.Int32`, 8 `System.String`,
+/// 9 `System.Int32` with field attributes 0x0606, 10 `System.Single`, 11 `Game.Player<System.Int32>`
+///
+/// Player's `name` field is thread static, Stats has no field offsets. The usage slots of 24
+/// are listed by the metadata, later versions encode the usage in the slot and a sixth slot
+/// holds a resolved pointer. Binaries of 29 and later have adjustor thunk tables.
+pub(crate) fn registration(major: u32, minor: u32, base: u64) -> Registration {
+    const VALUE_TYPE: u32 = 0x11;
+    const CLASS: u32 = 0x12;
+    let adjustors = major >= 29;
+    let byref = if (major, minor) >= (27, 2) {
+        1 << 29
+    } else {
+        1 << 30
+    };
+    let code = |offset: u64| base + CODE + offset;
+    let mut text = Section::new(base + CODE, true);
+    text.bytes(&[0xC3; 0x100]);
+
+    let mut d = Section::new(base + DATA, false);
+    let assembly_name = d.bytes(b"Assembly-CSharp.dll\0");
+    let corlib_name = d.bytes(b"mscorlib.dll\0");
+
+    // Il2CppType: data, then attrs:16 type:8 and the byref bit
+    let mut types = [0u64; 12];
+    let ty = |d: &mut Section, data: u64, address: bool, bits: u32| {
+        let at = if address {
+            d.pointer(data)
+        } else {
+            d.word(data)
+        };
+        d.i32s(&[bits as i32, 0]);
+        at
+    };
+    types[0] = ty(&mut d, 0, false, CLASS << 16);
+    types[1] = ty(&mut d, 1, false, VALUE_TYPE << 16);
+    types[2] = ty(&mut d, 2, false, VALUE_TYPE << 16);
+    types[3] = ty(&mut d, 0, false, 0x1C << 16);
+    types[5] = ty(&mut d, 0, false, CLASS << 16);
+    types[6] = ty(&mut d, 0, false, CLASS << 16 | byref);
+    types[7] = ty(&mut d, 0, false, 0x08 << 16);
+    types[8] = ty(&mut d, 0, false, 0x0E << 16);
+    types[9] = ty(&mut d, 0, false, 0x08 << 16 | 0x0606);
+    types[10] = ty(&mut d, 0, false, 0x0C << 16);
+    types[4] = ty(&mut d, types[7], true, 0x1D << 16);
+    let arguments = d.pointers(&[types[7]]);
+    let inst = d.word(1);
+    d.pointer(arguments);
+    // Il2CppGenericClass: the definition (an index before 27, its type after), then the
+    // context's class and method instances
+    let generic_class = if major < 27 {
+        d.word(0)
+    } else {
+        d.pointer(types[0])
+    };
+    d.pointers(&[inst, 0]);
+    types[11] = ty(&mut d, generic_class, true, 0x15 << 16);
+    let type_table = d.pointers(&types);
+
+    // Il2CppTypeDefinitionSizes: instance, native, static and thread static sizes
+    let sizes: Vec<u64> = [0x30, 0x14, 0x18]
+        .iter()
+        .map(|size| d.i32s(&[*size, 0, 0, 0]))
+        .collect();
+    let sizes = d.pointers(&sizes);
+    let player_offsets = d.i32s(&[0x10, 0x8000_0008u32 as i32]);
+    let mode_offsets = d.i32s(&[0x10]);
+    let field_offsets = d.pointers(&[player_offsets, mode_offsets, 0]);
+
+    let generic_insts = d.pointers(&[inst]);
+    let method_specs = d.i32s(&[0, -1, 0]);
+    let generic_method_table = if adjustors {
+        d.i32s(&[0, 0, 1, -1])
+    } else {
+        d.i32s(&[0, 0, 1])
+    };
+
+    let usage_slots: Vec<u64> = if major == 24 {
+        (0..5).map(|_| d.word(0)).collect()
+    } else {
+        [(5, 1), (1, 5), (3, 0), (4, 0), (2, 7)]
+            .iter()
+            .map(|(kind, index)| d.word((kind << 29) | (index << 1) | 1))
+            .collect()
+    };
+    let mut slots = usage_slots.clone();
+    if major != 24 {
+        // already replaced by il2cpp, an aligned pointer leaves bit 0 clear
+        slots.push(d.pointer(type_table));
+    }
+    let usages = d.pointers(&slots);
+
+    let metadata_registration = d.here();
+    for (count, table) in [
+        (0, 0),
+        (1, generic_insts),
+        (1, generic_method_table),
+        (types.len() as u64, type_table),
+        (1, method_specs),
+        (3, field_offsets),
+        (3, sizes),
+        (slots.len() as u64, usages),
+    ] {
+        d.word(count);
+        d.pointer(table);
+    }
+
+    // Il2CppCodeGenModule: moduleName, methodPointerCount, methodPointers,
+    // [adjustorThunkCount, adjustorThunks,] invokerIndices and zeroed tables after
+    let method_pointers = d.pointers(&[code(0x10), code(0x20)]);
+    let invoker_indices = d.i32s(&[0, -1]);
+    let module = |d: &mut Section, name: u64, count: u64, pointers: u64, invokers: u64| {
+        let at = d.pointer(name);
+        d.word(count);
+        d.pointer(pointers);
+        if adjustors {
+            d.word(0);
+            d.word(0);
+        }
+        d.pointer(invokers);
+        for _ in 0..7 {
+            d.word(0);
+        }
+        at
+    };
+    let assembly = module(&mut d, assembly_name, 2, method_pointers, invoker_indices);
+    let corlib = module(&mut d, corlib_name, 0, 0, 0);
+    let modules = d.pointers(&[assembly, corlib]);
+
+    let generic_method_pointers = d.pointers(&[code(0x40)]);
+    let invokers = d.pointers(&[code(0x80), code(0x90)]);
+    let code_registration = d.here();
+    d.word(0);
+    d.word(0);
+    d.word(1);
+    d.pointer(generic_method_pointers);
+    if adjustors {
+        d.word(0);
+    }
+    d.word(2);
+    d.pointer(invokers);
+    // the fields up to codeGenModules, which moved with the Unity releases
+    let modules_slot = match major {
+        24 | 27 => 13,
+        29 => 14,
+        _ => 16,
+    };
+    while d.here() < code_registration + (modules_slot - 1) * 8 {
+        d.word(0);
+    }
+    d.word(2);
+    d.pointer(modules);
+
+    Registration {
+        sections: vec![text, d],
+        metadata_registration,
+        code_registration,
+        usage_slots,
+    }
+}
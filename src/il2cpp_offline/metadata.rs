@@ -0,0 +1,955 @@
+use crate::il2cpp::classes::{
+    arg::ArgInner,
+    assembly::Assembly,
+    class::ClassInner,
+    field::FieldInner,
+    itype::{Type, TypeInner, TypeKind},
+    method::{METHOD_ATTRIBUTE_STATIC, MethodInner},
+};
+use crate::il2cpp_offline::Cursor;
+use crate::il2cpp_offline::registration::BinaryMetadata;
+use crate::il2cpp_offline::transform::{self, MetadataTransform, Xor};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+pub const METADATA_SANITY: u32 = 0xFAB1_1BAF;
+
+/// Metadata format version. The file only stores the major one, minors are told apart by
+/// the header size and table contents, the same way Il2CppDumper does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MetadataVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl MetadataVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Display for MetadataVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.minor == 0 {
+            write!(f, "{}", self.major)
+        } else {
+            write!(f, "{}.{}", self.major, self.minor)
+        }
+    }
+}
+
+const V24_0: MetadataVersion = MetadataVersion::new(24, 0);
+const V24_1: MetadataVersion = MetadataVersion::new(24, 1);
+const V24_2: MetadataVersion = MetadataVersion::new(24, 2);
+const V24_5: MetadataVersion = MetadataVersion::new(24, 5);
+const V31: MetadataVersion = MetadataVersion::new(31, 0);
+
+// stringLiteralOffset, the first table starts right after the header
+const HEADER_SIZE_V24_0: usize = 272;
+const HEADER_SIZE_V24_2: usize = 264;
+
+// the header pairs read by `parse_header`, named after Il2CppGlobalMetadataHeader for 24.2+.
+// 24.0 and 24.1 store rgctxEntries at 20 and images at 21.
+const SECTION_NAMES: [&str; 22] = [
+    "stringLiteral",
+    "stringLiteralData",
+    "string",
+    "events",
+    "properties",
+    "methods",
+    "parameterDefaultValues",
+    "fieldDefaultValues",
+    "fieldAndParameterDefaultValueData",
+    "fieldMarshaledSizes",
+    "parameters",
+    "fields",
+    "genericParameters",
+    "genericParameterConstraints",
+    "genericContainers",
+    "nestedTypes",
+    "interfaces",
+    "vtableMethods",
+    "interfaceOffsets",
+    "typeDefinitions",
+    "images",
+    "assemblies",
+];
+
+/// A table of the file, as an absolute byte range
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Section {
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// The tables of Il2CppGlobalMetadataHeader this parser reads
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataHeader {
+    pub version: MetadataVersion,
+    pub string_literals: Section,
+    pub string_literal_data: Section,
+    pub strings: Section,
+    pub methods: Section,
+    pub parameters: Section,
+    pub fields: Section,
+    pub generic_parameters: Section,
+    pub nested_types: Section,
+    pub type_definitions: Section,
+    pub images: Section,
+    /// Only in metadata 24, later versions encode the usage where the binary points
+    pub metadata_usage_pairs: Section,
+    pub field_refs: Section,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageDefinition {
+    pub name_index: u32,
+    pub assembly_index: i32,
+    pub type_start: i32,
+    pub type_count: u32,
+    pub entry_point_index: i32,
+    pub token: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDefinition {
+    pub name_index: u32,
+    pub namespace_index: u32,
+    pub byval_type_index: i32,
+    pub declaring_type_index: i32,
+    pub parent_index: i32,
+    pub element_type_index: i32,
+    pub generic_container_index: i32,
+    pub flags: u32,
+    pub field_start: i32,
+    pub method_start: i32,
+    pub nested_types_start: i32,
+    pub method_count: u16,
+    pub field_count: u16,
+    pub nested_type_count: u16,
+    /// bit 0 value type, bit 1 enum
+    pub bitfield: u32,
+    pub token: u32,
+}
+
+impl TypeDefinition {
+    pub fn is_value_type(&self) -> bool {
+        (self.bitfield & 1) != 0
+    }
+
+    pub fn is_enum(&self) -> bool {
+        (self.bitfield & 2) != 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MethodDefinition {
+    pub name_index: u32,
+    pub declaring_type: i32,
+    pub return_type: i32,
+    pub parameter_start: i32,
+    pub generic_container_index: i32,
+    pub token: u32,
+    pub flags: u16,
+    pub iflags: u16,
+    pub slot: u16,
+    pub parameter_count: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDefinition {
+    pub name_index: u32,
+    pub type_index: i32,
+    pub token: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParameterDefinition {
+    pub name_index: u32,
+    pub token: u32,
+    pub type_index: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenericParameter {
+    pub owner_index: i32,
+    pub name_index: u32,
+    pub constraints_start: i16,
+    pub constraints_count: i16,
+    pub num: u16,
+    pub flags: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
+    pub length: u32,
+    pub data_index: i32,
+}
+
+/// Links a metadata usage slot of the binary to what il2cpp initializes it with
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetadataUsagePair {
+    /// Index into `Il2CppMetadataRegistration::metadataUsages`
+    pub destination_index: u32,
+    /// Usage kind in the top 3 bits, the index of the type, method, field or literal below
+    pub encoded_source_index: u32,
+}
+
+/// A field referenced by code, `field_index` counts from the first field of the type
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldRef {
+    /// Index into the type table of the binary
+    pub type_index: i32,
+    pub field_index: i32,
+}
+
+/// A parsed `global-metadata.dat` (versions 24 to 31).
+/// The file holds names, flags and the class/member structure, while types are indices into
+/// the type table of the compiled binary and field offsets and method pointers live in the
+/// binary as well. `to_assemblies` leaves those unresolved, `to_assemblies_with` fills them
+/// from a `registration::BinaryMetadata`.
+pub struct GlobalMetadata {
+    pub header: MetadataHeader,
+    pub images: Vec<ImageDefinition>,
+    pub type_definitions: Vec<TypeDefinition>,
+    pub methods: Vec<MethodDefinition>,
+    pub fields: Vec<FieldDefinition>,
+    pub parameters: Vec<ParameterDefinition>,
+    pub generic_parameters: Vec<GenericParameter>,
+    pub string_literals: Vec<StringLiteral>,
+    /// Type definition indices, each type's nested types are a run of this table
+    pub nested_types: Vec<i32>,
+    /// Empty after metadata 24
+    pub metadata_usage_pairs: Vec<MetadataUsagePair>,
+    pub field_refs: Vec<FieldRef>,
+    data: Vec<u8>,
+}
+
+impl GlobalMetadata {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::load_with(path, &[])
+    }
+
+    /// Load a file that was encrypted, compressed or otherwise modified, `transforms` run in
+    /// order to turn it back into the stock layout
+    pub fn load_with(
+        path: impl AsRef<Path>,
+        transforms: &[&dyn MetadataTransform],
+    ) -> Result<Self, String> {
+        let path = path.as_ref();
+        let data =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse_with(data, transforms)
+    }
+
+    pub fn parse(data: Vec<u8>) -> Result<Self, String> {
+        Self::parse_with(data, &[])
+    }
+
+    pub fn parse_with(
+        mut data: Vec<u8>,
+        transforms: &[&dyn MetadataTransform],
+    ) -> Result<Self, String> {
+        for transform in transforms {
+            data = transform.apply(data)?;
+        }
+        let mut header = parse_header(&data)?;
+        let version = header.version;
+        let images = if version == V24_0 {
+            // 24.0 and 24.1 share the header, 24.1 images read with the 24.0 layout lose their tokens
+            match read_table(&data, header.images, |c| read_image(c, V24_0)) {
+                Ok(images) if images.iter().all(|image| image.token == 1) => images,
+                _ => {
+                    header.version = V24_1;
+                    read_table(&data, header.images, |c| read_image(c, V24_1))?
+                }
+            }
+        } else {
+            read_table(&data, header.images, |c| read_image(c, version))?
+        };
+        let version = header.version;
+
+        Ok(Self {
+            images,
+            type_definitions: read_table(&data, header.type_definitions, |c| {
+                read_type_definition(c, version)
+            })?,
+            methods: read_table(&data, header.methods, |c| read_method(c, version))?,
+            fields: read_table(&data, header.fields, |c| read_field(c, version))?,
+            parameters: read_table(&data, header.parameters, |c| read_parameter(c, version))?,
+            generic_parameters: read_table(&data, header.generic_parameters, |c| {
+                Ok(GenericParameter {
+                    owner_index: c.i32()?,
+                    name_index: c.u32()?,
+                    constraints_start: c.u16()? as i16,
+                    constraints_count: c.u16()? as i16,
+                    num: c.u16()?,
+                    flags: c.u16()?,
+                })
+            })?,
+            string_literals: read_table(&data, header.string_literals, |c| {
+                Ok(StringLiteral {
+                    length: c.u32()?,
+                    data_index: c.i32()?,
+                })
+            })?,
+            nested_types: read_table(&data, header.nested_types, |c| c.i32())?,
+            metadata_usage_pairs: read_table(&data, header.metadata_usage_pairs, |c| {
+                Ok(MetadataUsagePair {
+                    destination_index: c.u32()?,
+                    encoded_source_index: c.u32()?,
+                })
+            })?,
+            field_refs: read_table(&data, header.field_refs, |c| {
+                Ok(FieldRef {
+                    type_index: c.i32()?,
+                    field_index: c.i32()?,
+                })
+            })?,
+            header,
+            data,
+        })
+    }
+
+    pub fn version(&self) -> MetadataVersion {
+        self.header.version
+    }
+
+    /// Null terminated UTF-8 identifier at `index` of the string table
+    pub fn string(&self, index: u32) -> Result<&str, String> {
+        let strings = section_bytes(&self.data, self.header.strings)?;
+        let start = strings
+            .get(index as usize..)
+            .ok_or(format!("String index {} out of range", index))?;
+        let end = start
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(format!("String {} is not terminated", index))?;
+        std::str::from_utf8(&start[..end]).map_err(|e| format!("String {}: {}", index, e))
+    }
+
+    /// Contents of the C# string literal `index`, stored as UTF-8 without terminator
+    pub fn string_literal(&self, index: usize) -> Result<String, String> {
+        let literal = self
+            .string_literals
+            .get(index)
+            .ok_or(format!("String literal {} out of range", index))?;
+        let data = section_bytes(&self.data, self.header.string_literal_data)?;
+        let start = usize::try_from(literal.data_index)
+            .map_err(|_| format!("String literal {} has no data", index))?;
+        data.get(start..start + literal.length as usize)
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .ok_or(format!("String literal {} data out of range", index))
+    }
+
+    /// Build the cache model from the file alone. Field, return and parameter types are
+    /// placeholders named after their type index, field offsets and flags are 0 and method
+    /// pointers null, as all of those are stored in the binary.
+    pub fn to_assemblies(&self) -> Result<Vec<Assembly>, String> {
+        self.build_assemblies(None)
+    }
+
+    /// Build the cache model with types, parents, field offsets and method pointers taken
+    /// from the binary the metadata belongs to
+    pub fn to_assemblies_with(&self, binary: &BinaryMetadata) -> Result<Vec<Assembly>, String> {
+        self.build_assemblies(Some(binary))
+    }
+
+    fn build_assemblies(&self, binary: Option<&BinaryMetadata>) -> Result<Vec<Assembly>, String> {
+        let declaring_types = self.declaring_types()?;
+        let mut assemblies = Vec::new();
+        for image in &self.images {
+            let name = self.string(image.name_index)?.to_string();
+            let mut assembly = Assembly::new(std::ptr::null_mut(), name.clone(), name);
+            for index in range(image.type_start, image.type_count as usize) {
+                let class = self.build_class(index, binary)?;
+                if let Some(declaring_type) = declaring_types.get(&index) {
+                    *class.declaring_type.write() = declaring_type.clone();
+                }
+                assembly.classes.push(class);
+            }
+            assemblies.push(assembly);
+        }
+        Ok(assemblies)
+    }
+
+    // Full name of the enclosing type of every nested type definition, by index
+    pub(crate) fn declaring_types(&self) -> Result<HashMap<usize, String>, String> {
+        let mut declaring_types = HashMap::new();
+        for definition in &self.type_definitions {
+            if definition.nested_type_count == 0 {
+                continue;
+            }
+            let namespace = self.string(definition.namespace_index)?;
+            let name = self.string(definition.name_index)?;
+            let full_name = if namespace.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", namespace, name)
+            };
+            for index in range(
+                definition.nested_types_start,
+                definition.nested_type_count as usize,
+            ) {
+                let nested = self
+                    .nested_types
+                    .get(index)
+                    .ok_or(format!("Nested type {} out of range", index))?;
+                if let Ok(nested) = usize::try_from(*nested) {
+                    declaring_types.insert(nested, full_name.clone());
+                }
+            }
+        }
+        Ok(declaring_types)
+    }
+
+    fn build_class(
+        &self,
+        type_index: usize,
+        binary: Option<&BinaryMetadata>,
+    ) -> Result<Arc<ClassInner>, String> {
+        let definition = self
+            .type_definitions
+            .get(type_index)
+            .ok_or(format!("Type definition {} out of range", type_index))?;
+        let parent = match binary.and_then(|b| b.type_at(definition.parent_index)) {
+            Some(parent) => parent.name.clone(),
+            None => String::new(),
+        };
+        let class = ClassInner::new(
+            std::ptr::null_mut(),
+            self.string(definition.name_index)?.to_string(),
+            parent,
+            self.string(definition.namespace_index)?.to_string(),
+            definition.flags as i32,
+            definition.is_value_type(),
+            definition.is_enum(),
+        );
+
+        for (slot, index) in
+            range(definition.field_start, definition.field_count as usize).enumerate()
+        {
+            let field = self
+                .fields
+                .get(index)
+                .ok_or(format!("Field {} out of range", index))?;
+            let (offset, flags) = match binary {
+                Some(binary) => (
+                    binary.field_offset(type_index, slot).unwrap_or(0),
+                    binary
+                        .type_at(field.type_index)
+                        .map_or(0, |t| t.attrs as i32),
+                ),
+                None => (0, 0),
+            };
+            class.fields.write().push(FieldInner::new(
+                std::ptr::null_mut(),
+                self.string(field.name_index)?.to_string(),
+                resolve_type(field.type_index, binary),
+                Arc::downgrade(&class),
+                offset,
+                flags,
+            ));
+        }
+
+        for index in range(definition.method_start, definition.method_count as usize) {
+            let method = self
+                .methods
+                .get(index)
+                .ok_or(format!("Method {} out of range", index))?;
+            let mut args = Vec::new();
+            for index in range(method.parameter_start, method.parameter_count as usize) {
+                let parameter = self
+                    .parameters
+                    .get(index)
+                    .ok_or(format!("Parameter {} out of range", index))?;
+                args.push(ArgInner::new(
+                    self.string(parameter.name_index)?.to_string(),
+                    resolve_type(parameter.type_index, binary),
+                ));
+            }
+            let function = binary
+                .and_then(|b| b.methods.get(index))
+                .and_then(|m| m.pointer)
+                .map_or(0, |p| p as usize);
+            let flags = method.flags as i32;
+            class.methods.write().push(MethodInner::new_offline(
+                std::ptr::null_mut(),
+                self.string(method.name_index)?.to_string(),
+                Arc::downgrade(&class),
+                resolve_type(method.return_type, binary),
+                flags,
+                (flags & METHOD_ATTRIBUTE_STATIC) != 0,
+                function,
+                RwLock::new(args),
+            ));
+        }
+        Ok(class)
+    }
+}
+
+fn resolve_type(index: i32, binary: Option<&BinaryMetadata>) -> Type {
+    match binary.and_then(|b| b.type_at(index)) {
+        Some(resolved) => TypeInner::new(
+            std::ptr::null_mut(),
+            resolved.name.clone(),
+            resolved.size,
+            resolved.kind,
+            resolved.byref,
+            resolved.value_type,
+        ),
+        None => unresolved_type(index),
+    }
+}
+
+fn unresolved_type(index: i32) -> Type {
+    TypeInner::new(
+        std::ptr::null_mut(),
+        format!("<TypeIndex {}>", index),
+        0,
+        TypeKind::Unknown(0),
+        false,
+        false,
+    )
+}
+
+// `start..start + count`, empty for the -1 il2cpp uses when a list is absent
+fn range(start: i32, count: usize) -> std::ops::Range<usize> {
+    match usize::try_from(start) {
+        Ok(start) => start..start + count,
+        Err(_) => 0..0,
+    }
+}
+
+fn parse_header(data: &[u8]) -> Result<MetadataHeader, String> {
+    let mut cursor = Cursor::new(data);
+    let sanity = cursor
+        .u32()
+        .map_err(|_| "Not a global-metadata.dat: the file is empty".to_string())?;
+    if sanity != METADATA_SANITY {
+        return Err(format!(
+            "Not a global-metadata.dat: sanity 0x{:08X}, expected 0x{:08X}. {}",
+            sanity,
+            METADATA_SANITY,
+            diagnose_sanity(data)
+        ));
+    }
+    let major = cursor.u32()?;
+    if !matches!(major, 24 | 27 | 29 | 31) {
+        return Err(format!(
+            "Unsupported metadata version {}, expected 24, 27, 29 or 31. Stock files only \
+             use these, other values usually mean the header was modified",
+            major
+        ));
+    }
+    let mut sections = Vec::new();
+    // every table up to the images has a fixed position in all supported versions
+    while sections.len() < SECTION_NAMES.len() {
+        sections.push(Section {
+            offset: cursor.u32()? as usize,
+            size: cursor.u32()? as usize,
+        });
+    }
+
+    let header_size = sections[0].offset;
+    let (version, images) = match major {
+        24 if header_size == HEADER_SIZE_V24_0 => (V24_0, sections[21]),
+        24 if header_size == HEADER_SIZE_V24_2 => (V24_2, sections[20]),
+        24 => {
+            return Err(format!(
+                "Unknown version 24 header size {}, the first table should start right \
+                 after the header at {} or {}",
+                header_size, HEADER_SIZE_V24_0, HEADER_SIZE_V24_2
+            ));
+        }
+        _ => (MetadataVersion::new(major, 0), sections[20]),
+    };
+    check_sections(data, &sections, version)?;
+
+    // the tables after the assemblies are not checked above: the metadata usage lists and
+    // pairs were dropped in 27 and shift the field references, whose position is all we need
+    let (usage_pairs, field_refs) = match version {
+        V24_0 => (Some(24), 25),
+        _ if major == 24 => (Some(23), 24),
+        _ => (None, 22),
+    };
+    let mut later = Vec::new();
+    while sections.len() + later.len() <= field_refs {
+        later.push(Section {
+            offset: cursor.u32()? as usize,
+            size: cursor.u32()? as usize,
+        });
+    }
+    let later = |index: usize| later[index - sections.len()];
+
+    Ok(MetadataHeader {
+        version,
+        string_literals: sections[0],
+        string_literal_data: sections[1],
+        strings: sections[2],
+        methods: sections[5],
+        parameters: sections[10],
+        fields: sections[11],
+        generic_parameters: sections[12],
+        nested_types: sections[15],
+        type_definitions: sections[19],
+        images,
+        metadata_usage_pairs: usage_pairs.map(later).unwrap_or_default(),
+        field_refs: later(field_refs),
+    })
+}
+
+// Stock files lay the tables out one after another behind the header, a table outside the
+// file or two overlapping ones mean the header was tampered with or the file is cut short
+fn check_sections(
+    data: &[u8],
+    sections: &[Section],
+    version: MetadataVersion,
+) -> Result<(), String> {
+    let name = |index: usize| match (version <= V24_1, index) {
+        (true, 20) => "rgctxEntries",
+        (true, 21) => "images",
+        _ => SECTION_NAMES[index],
+    };
+    for (index, section) in sections.iter().enumerate() {
+        if section
+            .offset
+            .checked_add(section.size)
+            .is_none_or(|end| end > data.len())
+        {
+            return Err(format!(
+                "Table {} (0x{:X}, 0x{:X} bytes) ends past the 0x{:X} byte file, the header \
+                 was modified or the file is truncated",
+                name(index),
+                section.offset,
+                section.size,
+                data.len()
+            ));
+        }
+    }
+
+    let mut tables: Vec<_> = (0..sections.len())
+        .filter(|i| sections[*i].size > 0)
+        .collect();
+    tables.sort_by_key(|i| sections[*i].offset);
+    for pair in tables.windows(2) {
+        let (first, second) = (sections[pair[0]], sections[pair[1]]);
+        if first.offset + first.size > second.offset {
+            return Err(format!(
+                "Tables {} and {} overlap, the header looks shuffled or modified",
+                name(pair[0]),
+                name(pair[1])
+            ));
+        }
+    }
+    let pairs_end = 8 + sections.len() * 8;
+    if let Some(first) = tables.first().filter(|i| sections[**i].offset < pairs_end) {
+        return Err(format!(
+            "Table {} starts inside the header, the header looks shuffled or modified",
+            name(*first)
+        ));
+    }
+    Ok(())
+}
+
+// A hint at what was done to a file whose sanity value does not match
+fn diagnose_sanity(data: &[u8]) -> String {
+    if let Some(kind) = transform::compression(data) {
+        return format!(
+            "The file is {} compressed, load it with `transform::Decompress`",
+            kind
+        );
+    }
+    if let Some(xor) = Xor::from_magic(data) {
+        return format!(
+            "The file looks XOR-ed with the {} byte key {:02X?}, load it with `transform::Xor`",
+            xor.key.len(),
+            xor.key
+        );
+    }
+    let sanity = METADATA_SANITY.to_le_bytes();
+    let prefix = data
+        .windows(4)
+        .take(0x1000)
+        .position(|window| window == sanity);
+    match prefix {
+        Some(offset) => format!(
+            "The header starts 0x{:X} bytes in, load it with `transform::SkipBytes`",
+            offset
+        ),
+        None => "The file is encrypted or not il2cpp metadata".to_string(),
+    }
+}
+
+fn section_bytes(data: &[u8], section: Section) -> Result<&[u8], String> {
+    data.get(section.offset..section.offset + section.size)
+        .ok_or(format!(
+            "Table at 0x{:X} (0x{:X} bytes) is outside the file",
+            section.offset, section.size
+        ))
+}
+
+/// Read entries until the section is consumed, a truncated last entry is an error
+fn read_table<T>(
+    data: &[u8],
+    section: Section,
+    mut read: impl FnMut(&mut Cursor) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let mut cursor = Cursor::new(section_bytes(data, section)?);
+    let mut entries = Vec::new();
+    while !cursor.at_end() {
+        entries.push(read(&mut cursor).map_err(|e| {
+            format!(
+                "Table at 0x{:X} (0x{:X} bytes) does not hold whole entries: {}. The header \
+                 looks shuffled or modified",
+                section.offset, section.size, e
+            )
+        })?);
+    }
+    Ok(entries)
+}
+
+fn read_image(c: &mut Cursor, version: MetadataVersion) -> Result<ImageDefinition, String> {
+    let name_index = c.u32()?;
+    let assembly_index = c.i32()?;
+    let type_start = c.i32()?;
+    let type_count = c.u32()?;
+    c.skip(8)?; // exportedTypeStart, exportedTypeCount
+    let entry_point_index = c.i32()?;
+    let token = c.u32()?;
+    if version >= V24_1 {
+        c.skip(8)?; // customAttributeStart, customAttributeCount
+    }
+    Ok(ImageDefinition {
+        name_index,
+        assembly_index,
+        type_start,
+        type_count,
+        entry_point_index,
+        token,
+    })
+}
+
+fn read_type_definition(
+    c: &mut Cursor,
+    version: MetadataVersion,
+) -> Result<TypeDefinition, String> {
+    let name_index = c.u32()?;
+    let namespace_index = c.u32()?;
+    if version <= V24_0 {
+        c.skip(4)?; // customAttributeIndex
+    }
+    let byval_type_index = c.i32()?;
+    if version <= V24_5 {
+        c.skip(4)?; // byrefTypeIndex
+    }
+    let declaring_type_index = c.i32()?;
+    let parent_index = c.i32()?;
+    let element_type_index = c.i32()?;
+    if version <= V24_1 {
+        c.skip(8)?; // rgctxStartIndex, rgctxCount
+    }
+    let generic_container_index = c.i32()?;
+    let flags = c.u32()?;
+    let field_start = c.i32()?;
+    let method_start = c.i32()?;
+    c.skip(8)?; // eventStart, propertyStart
+    let nested_types_start = c.i32()?;
+    c.skip(12)?; // interfacesStart, vtableStart, interfaceOffsetsStart
+    let method_count = c.u16()?;
+    c.skip(2)?; // property_count
+    let field_count = c.u16()?;
+    c.skip(2)?; // event_count
+    let nested_type_count = c.u16()?;
+    c.skip(6)?; // vtable_count, interfaces_count, interface_offsets_count
+    let bitfield = c.u32()?;
+    let token = c.u32()?;
+    Ok(TypeDefinition {
+        name_index,
+        namespace_index,
+        byval_type_index,
+        declaring_type_index,
+        parent_index,
+        element_type_index,
+        generic_container_index,
+        flags,
+        field_start,
+        method_start,
+        nested_types_start,
+        method_count,
+        field_count,
+        nested_type_count,
+        bitfield,
+        token,
+    })
+}
+
+fn read_method(c: &mut Cursor, version: MetadataVersion) -> Result<MethodDefinition, String> {
+    let name_index = c.u32()?;
+    let declaring_type = c.i32()?;
+    let return_type = c.i32()?;
+    if version >= V31 {
+        c.skip(4)?; // returnParameterToken
+    }
+    let parameter_start = c.i32()?;
+    if version <= V24_0 {
+        c.skip(4)?; // customAttributeIndex
+    }
+    let generic_container_index = c.i32()?;
+    if version <= V24_1 {
+        c.skip(20)?; // methodIndex, invokerIndex, delegateWrapperIndex, rgctxStartIndex, rgctxCount
+    }
+    Ok(MethodDefinition {
+        name_index,
+        declaring_type,
+        return_type,
+        parameter_start,
+        generic_container_index,
+        token: c.u32()?,
+        flags: c.u16()?,
+        iflags: c.u16()?,
+        slot: c.u16()?,
+        parameter_count: c.u16()?,
+    })
+}
+
+fn read_field(c: &mut Cursor, version: MetadataVersion) -> Result<FieldDefinition, String> {
+    let name_index = c.u32()?;
+    let type_index = c.i32()?;
+    if version <= V24_0 {
+        c.skip(4)?; // customAttributeIndex
+    }
+    Ok(FieldDefinition {
+        name_index,
+        type_index,
+        token: c.u32()?,
+    })
+}
+
+fn read_parameter(c: &mut Cursor, version: MetadataVersion) -> Result<ParameterDefinition, String> {
+    let name_index = c.u32()?;
+    let token = c.u32()?;
+    if version <= V24_0 {
+        c.skip(4)?; // customAttributeIndex
+    }
+    Ok(ParameterDefinition {
+        name_index,
+        token,
+        type_index: c.i32()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_offline::fixture;
+
+    const VERSIONS: [(u32, u32); 5] = [(24, 0), (24, 1), (24, 2), (27, 0), (31, 0)];
+
+    fn parse(major: u32, minor: u32) -> GlobalMetadata {
+        GlobalMetadata::parse(fixture::metadata(major, minor))
+            .unwrap_or_else(|e| panic!("{}.{}: {}", major, minor, e))
+    }
+
+    #[test]
+    fn every_version_parses() {
+        for (major, minor) in VERSIONS {
+            let metadata = parse(major, minor);
+            assert_eq!(metadata.version(), MetadataVersion::new(major, minor));
+            assert_eq!(metadata.images.len(), 2);
+            assert_eq!(metadata.type_definitions.len(), 3);
+            assert_eq!(metadata.nested_types, vec![2]);
+            assert_eq!(metadata.fields.len(), 3);
+            assert_eq!(metadata.methods.len(), 2);
+            assert_eq!(metadata.parameters.len(), 2);
+
+            let player = &metadata.type_definitions[0];
+            assert_eq!(metadata.string(player.name_index).unwrap(), "Player");
+            assert_eq!(metadata.string(player.namespace_index).unwrap(), "Game");
+            assert_eq!((player.field_count, player.method_count), (2, 2));
+            assert!(metadata.type_definitions[1].is_enum());
+            assert_eq!(metadata.methods[1].token, 0x0600_0002);
+            assert_eq!(metadata.methods[0].parameter_count, 1);
+            assert_eq!(metadata.parameters[1].type_index, 11);
+            assert_eq!(metadata.string_literal(1).unwrap(), "World");
+            assert_eq!(metadata.field_refs.len(), 1);
+            assert_eq!(metadata.field_refs[0].field_index, 1);
+            let pairs = &metadata.metadata_usage_pairs;
+            if major == 24 {
+                assert_eq!(pairs.len(), 5);
+                assert_eq!(pairs[2].destination_index, 2);
+                assert_eq!(pairs[2].encoded_source_index, 0x6000_0000);
+            } else {
+                assert!(pairs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn assemblies_follow_the_tables() {
+        let assemblies = parse(29, 0).to_assemblies().unwrap();
+        assert_eq!(assemblies[0].name, "Assembly-CSharp.dll");
+        assert!(assemblies[1].classes.is_empty());
+        let player = &assemblies[0].classes[0];
+        assert_eq!(player.full_name(), "Game.Player");
+        let methods = player.methods.read();
+        assert_eq!(methods[0].name, "TakeDamage");
+        assert_eq!(methods[0].args.read()[0].name, "amount");
+        assert!(methods[1].is_static);
+        assert_eq!(assemblies[0].classes[1].fields.read()[0].name, "value__");
+        assert!(player.declaring_type.read().is_empty());
+        assert_eq!(
+            *assemblies[0].classes[2].declaring_type.read(),
+            "Game.Player"
+        );
+    }
+
+    #[test]
+    fn bad_sanity_and_version_are_rejected() {
+        let mut data = fixture::metadata(27, 0);
+        data[4] = 26;
+        let error = GlobalMetadata::parse(data.clone()).err().unwrap();
+        assert!(
+            error.contains("Unsupported metadata version 26"),
+            "{}",
+            error
+        );
+        data[0] ^= 0xFF;
+        let error = GlobalMetadata::parse(data).err().unwrap();
+        assert!(error.starts_with("Not a global-metadata.dat"), "{}", error);
+    }
+
+    #[test]
+    fn tampered_tables_are_rejected() {
+        // the string table (pair 2) made to run into the methods that follow it
+        let mut data = fixture::metadata(27, 0);
+        let size = u32::from_le_bytes(data[28..32].try_into().unwrap());
+        data[28..32].copy_from_slice(&(size + 4).to_le_bytes());
+        let error = GlobalMetadata::parse(data).err().unwrap();
+        assert!(error.contains("string and methods overlap"), "{}", error);
+
+        let mut data = fixture::metadata(27, 0);
+        let len = data.len() as u32;
+        data[28..32].copy_from_slice(&len.to_le_bytes());
+        let error = GlobalMetadata::parse(data).err().unwrap();
+        assert!(error.contains("ends past"), "{}", error);
+
+        // a methods table that is not a whole number of entries
+        let mut data = fixture::metadata(27, 0);
+        let size = u32::from_le_bytes(data[52..56].try_into().unwrap());
+        data[52..56].copy_from_slice(&(size - 2).to_le_bytes());
+        let error = GlobalMetadata::parse(data).err().unwrap();
+        assert!(error.contains("does not hold whole entries"), "{}", error);
+    }
+
+    #[test]
+    fn unknown_v24_header_size_is_rejected() {
+        let mut data = fixture::metadata(24, 2);
+        data[8..12].copy_from_slice(&300u32.to_le_bytes());
+        let error = GlobalMetadata::parse(data).err().unwrap();
+        assert!(error.contains("header size 300"), "{}", error);
+    }
+}
@@ -0,0 +1,60 @@
+// Reading a game's il2cpp metadata from its files, without a running process
+pub mod executable;
+#[cfg(test)]
+pub(crate) mod fixture;
+pub mod metadata;
+pub mod registration;
+pub mod transform;
+
+// Little endian reader over a byte slice
+pub(crate) struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn skip(&mut self, count: usize) -> Result<(), String> {
+        if self.position + count > self.data.len() {
+            return Err(format!("Unexpected end of data at 0x{:X}", self.position));
+        }
+        self.position += count;
+        Ok(())
+    }
+
+    pub(crate) fn bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + count)
+            .ok_or(format!("Unexpected end of data at 0x{:X}", self.position))?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
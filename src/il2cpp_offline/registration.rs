@@ -0,0 +1,938 @@
+use crate::il2cpp::classes::itype::TypeKind;
+use crate::il2cpp_offline::executable::Executable;
+use crate::il2cpp_offline::metadata::{GlobalMetadata, MetadataVersion};
+use std::collections::{HashMap, HashSet};
+
+// counts above this are taken as a misread pointer
+const MAX_COUNT: u64 = 0x0100_0000;
+// il2cpp marks the offsets of [ThreadStatic] fields with this bit, the runtime reports them as -1
+const THREAD_STATIC_MASK: u32 = 0x8000_0000;
+const THREAD_STATIC_FIELD_OFFSET: i32 = -1;
+// generic arguments nest, a corrupt binary must not recurse forever
+const MAX_TYPE_DEPTH: usize = 32;
+
+// Il2CppMetadataRegistration, in pointer-sized slots: every member is a count/pointer pair
+const MR_GENERIC_INSTS: u64 = 2;
+const MR_GENERIC_METHOD_TABLE: u64 = 4;
+const MR_TYPES: u64 = 6;
+const MR_METHOD_SPECS: u64 = 8;
+const MR_FIELD_OFFSETS: u64 = 10;
+const MR_TYPE_DEFINITION_SIZES: u64 = 12;
+const MR_METADATA_USAGES: u64 = 14;
+
+const V24_2: MetadataVersion = MetadataVersion::new(24, 2);
+
+/// Addresses of the two structs the binary passes to `il2cpp_codegen_register`
+#[derive(Debug, Clone, Copy)]
+pub struct Registrations {
+    /// `Il2CppCodeRegistration`, `None` for metadata 24.0/24.1 or when its layout could not
+    /// be matched. Method pointers still resolve through the code gen modules in the latter case.
+    pub code_registration: Option<u64>,
+    pub metadata_registration: u64,
+}
+
+/// An entry of the binary's type table, described the way `Cache::parse_type` does live
+#[derive(Debug, Clone)]
+pub struct ResolvedType {
+    /// `il2cpp_type_get_name` style, `System.Collections.Generic.List<System.Int32>`
+    pub name: String,
+    pub kind: TypeKind,
+    /// FIELD_ATTRIBUTE_* or PARAM_ATTRIBUTE_* of the member the type belongs to
+    pub attrs: u16,
+    pub byref: bool,
+    pub value_type: bool,
+    /// Storage size on the target, the value for value types and a pointer otherwise.
+    /// 0 for instances of generic value types, whose layout depends on the arguments
+    pub size: isize,
+    /// Index into `GlobalMetadata::type_definitions` for classes, value types and generic
+    /// instances (their definition)
+    pub definition: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MethodAddresses {
+    pub pointer: Option<u64>,
+    pub invoker: Option<u64>,
+}
+
+/// Native code of a generic method instance, `Foo<int>` and `Foo<string>` each get one
+#[derive(Debug, Clone)]
+pub struct GenericMethodAddress {
+    /// Index into `Il2CppMetadataRegistration::methodSpecs`, what method references name
+    pub method_spec: usize,
+    /// Index into `GlobalMetadata::methods`
+    pub method_definition: usize,
+    pub class_arguments: Vec<String>,
+    pub method_arguments: Vec<String>,
+    pub pointer: u64,
+    pub invoker: Option<u64>,
+}
+
+/// What a metadata usage slot holds once il2cpp initialized it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataUsageKind {
+    /// `Il2CppClass*` of a type table entry
+    TypeInfo,
+    /// `Il2CppType*` of a type table entry
+    Il2CppType,
+    /// `MethodInfo*` of a method definition
+    MethodDef,
+    /// `FieldInfo*` of a `GlobalMetadata::field_refs` entry
+    FieldInfo,
+    /// The managed string of a string literal
+    StringLiteral,
+    /// `MethodInfo*` of a generic method instance, by method spec
+    MethodRef,
+}
+
+impl MetadataUsageKind {
+    fn from_raw(kind: u32) -> Option<Self> {
+        Some(match kind {
+            1 => Self::TypeInfo,
+            2 => Self::Il2CppType,
+            3 => Self::MethodDef,
+            4 => Self::FieldInfo,
+            5 => Self::StringLiteral,
+            6 => Self::MethodRef,
+            _ => return None,
+        })
+    }
+}
+
+/// A global of the binary that generated code reads a type, method, field or string through
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataUsage {
+    pub address: u64,
+    pub kind: MetadataUsageKind,
+    /// Index into the table `kind` names
+    pub index: usize,
+}
+
+/// What the compiled binary adds to `global-metadata.dat`: the type table, field offsets and
+/// native addresses. Every address is a virtual address at `Executable::image_base`.
+pub struct BinaryMetadata {
+    pub registrations: Registrations,
+    /// `Il2CppMetadataRegistration::types`, `None` where an entry could not be decoded
+    pub types: Vec<Option<ResolvedType>>,
+    /// Per type definition, the offsets of its fields in declaration order
+    pub field_offsets: Vec<Vec<i32>>,
+    /// Per method definition, empty for 24.0/24.1 binaries
+    pub methods: Vec<MethodAddresses>,
+    pub generic_methods: Vec<GenericMethodAddress>,
+    /// Metadata usage slots, empty when the binary has none or they could not be decoded
+    pub metadata_usages: Vec<MetadataUsage>,
+}
+
+struct CodeGenModule {
+    method_pointers: Vec<u64>,
+    invoker_indices: Vec<i32>,
+}
+
+#[derive(Default)]
+struct CodeRegistration {
+    generic_method_pointers: Vec<u64>,
+    invokers: Vec<u64>,
+}
+
+impl BinaryMetadata {
+    /// Find the registration structs in `executable` and decode them with `metadata`.
+    /// Nothing in the binary names the structs, they are located from values the metadata
+    /// predicts (type and image counts, module names) the same way Il2CppDumper searches.
+    pub fn analyze(executable: &Executable, metadata: &GlobalMetadata) -> Result<Self, String> {
+        let p = executable.pointer_size() as u64;
+        let type_count = metadata.type_definitions.len();
+        let metadata_registration = find_metadata_registration(executable, type_count as u64)?;
+        let slot = |index: u64| executable.read_pointer(metadata_registration + index * p);
+        let table = |index: u64| -> Result<Vec<u64>, String> {
+            let count = slot(index)?;
+            if count == 0 {
+                return Ok(Vec::new());
+            }
+            executable.read_pointers(slot(index + 1)?, count as usize)
+        };
+
+        let mut reader = TypeReader {
+            executable,
+            metadata,
+            types: table(MR_TYPES)?,
+            sizes: table(MR_TYPE_DEFINITION_SIZES)?,
+            names: HashMap::new(),
+        };
+        let types = (0..reader.types.len())
+            .map(|index| reader.resolve(reader.types[index]).ok())
+            .collect();
+        let field_offsets = read_field_offsets(executable, metadata, &table(MR_FIELD_OFFSETS)?);
+        // optional, a binary whose usage table does not read is still usable
+        let metadata_usages = table(MR_METADATA_USAGES)
+            .map(|slots| read_metadata_usages(executable, metadata, &slots))
+            .unwrap_or_default();
+
+        let mut binary = Self {
+            registrations: Registrations {
+                code_registration: None,
+                metadata_registration,
+            },
+            types,
+            field_offsets,
+            methods: vec![MethodAddresses::default(); metadata.methods.len()],
+            generic_methods: Vec::new(),
+            metadata_usages,
+        };
+        // older versions index one global method pointer table, not supported
+        if metadata.version() < V24_2 {
+            return Ok(binary);
+        }
+
+        let (modules_field, modules) = find_code_gen_modules(executable, metadata)?;
+        let adjustors = metadata.version().major >= 29
+            || has_adjustor_thunks(executable, modules.values().next().copied().unwrap_or(0));
+        let code = find_code_registration(executable, metadata, modules_field, adjustors);
+        binary.registrations.code_registration = code.as_ref().map(|(address, _)| *address);
+        let code = code.map(|(_, code)| code).unwrap_or_default();
+
+        for image in &metadata.images {
+            let name = metadata.string(image.name_index)?;
+            let Some(address) = modules.get(name) else {
+                continue;
+            };
+            let module = read_code_gen_module(executable, *address, adjustors)?;
+            let first = usize::try_from(image.type_start).unwrap_or(usize::MAX);
+            let definitions = metadata.type_definitions.iter().skip(first);
+            for definition in definitions.take(image.type_count as usize) {
+                let Ok(method_start) = usize::try_from(definition.method_start) else {
+                    continue;
+                };
+                for index in method_start..method_start + definition.method_count as usize {
+                    let Some(method) = metadata.methods.get(index) else {
+                        continue;
+                    };
+                    let rid = (method.token & 0x00FF_FFFF) as usize;
+                    let Some(slot) = rid.checked_sub(1) else {
+                        continue;
+                    };
+                    binary.methods[index] = MethodAddresses {
+                        pointer: module
+                            .method_pointers
+                            .get(slot)
+                            .copied()
+                            .filter(|p| *p != 0),
+                        invoker: module
+                            .invoker_indices
+                            .get(slot)
+                            .and_then(|i| usize::try_from(*i).ok())
+                            .and_then(|i| code.invokers.get(i).copied()),
+                    };
+                }
+            }
+        }
+
+        binary.generic_methods = read_generic_methods(
+            executable,
+            &mut reader,
+            &code,
+            &table(MR_GENERIC_INSTS)?,
+            (
+                slot(MR_GENERIC_METHOD_TABLE)?,
+                slot(MR_GENERIC_METHOD_TABLE + 1)?,
+            ),
+            (slot(MR_METHOD_SPECS)?, slot(MR_METHOD_SPECS + 1)?),
+            adjustors,
+        )?;
+        Ok(binary)
+    }
+
+    /// Move every address from a binary loaded at `image_base` to one loaded at `base`,
+    /// e.g. where a running process mapped it
+    pub fn rebase(&mut self, image_base: u64, base: u64) {
+        let rebase =
+            |address: &mut u64| *address = address.wrapping_sub(image_base).wrapping_add(base);
+        rebase(&mut self.registrations.metadata_registration);
+        self.registrations
+            .code_registration
+            .iter_mut()
+            .for_each(rebase);
+        for method in &mut self.methods {
+            method.pointer.iter_mut().for_each(rebase);
+            method.invoker.iter_mut().for_each(rebase);
+        }
+        for method in &mut self.generic_methods {
+            rebase(&mut method.pointer);
+            method.invoker.iter_mut().for_each(rebase);
+        }
+        for usage in &mut self.metadata_usages {
+            rebase(&mut usage.address);
+        }
+    }
+
+    /// Entry `index` of the type table, as stored in the metadata's type index fields
+    pub fn type_at(&self, index: i32) -> Option<&ResolvedType> {
+        self.types.get(usize::try_from(index).ok()?)?.as_ref()
+    }
+
+    pub fn field_offset(&self, type_definition: usize, field: usize) -> Option<i32> {
+        self.field_offsets.get(type_definition)?.get(field).copied()
+    }
+}
+
+// fieldOffsetsCount and typeDefinitionsSizesCount both equal the type definition count, with
+// a pointer after each of them
+fn find_metadata_registration(executable: &Executable, type_count: u64) -> Result<u64, String> {
+    let p = executable.pointer_size() as u64;
+    let read = |address: u64| executable.read_pointer(address).unwrap_or(0);
+    for hit in executable.find_pointer(type_count) {
+        if read(hit + 2 * p) != type_count
+            || !executable.is_mapped(read(hit + p))
+            || !executable.is_mapped(read(hit + 3 * p))
+        {
+            continue;
+        }
+        let Some(start) = hit.checked_sub(MR_FIELD_OFFSETS * p) else {
+            continue;
+        };
+        let types_count = read(start + MR_TYPES * p);
+        if types_count > 0
+            && types_count < MAX_COUNT
+            && executable.is_mapped(read(start + (MR_TYPES + 1) * p))
+        {
+            return Ok(start);
+        }
+    }
+    Err("Il2CppMetadataRegistration not found, the binary may not match the metadata".to_string())
+}
+
+// codeGenModulesCount equals the image count and the array holds one module per image, named
+// after it. Returns the address of the codeGenModules field and the modules by name.
+fn find_code_gen_modules(
+    executable: &Executable,
+    metadata: &GlobalMetadata,
+) -> Result<(u64, HashMap<String, u64>), String> {
+    let p = executable.pointer_size() as u64;
+    let names = metadata
+        .images
+        .iter()
+        .map(|image| metadata.string(image.name_index))
+        .collect::<Result<HashSet<_>, _>>()?;
+    let module_name = |module: u64| {
+        executable
+            .read_pointer(module)
+            .and_then(|name| executable.read_c_string(name))
+            .ok()
+            .filter(|name| names.contains(name))
+    };
+
+    for hit in executable.find_pointer(names.len() as u64) {
+        let Ok(array) = executable.read_pointer(hit + p) else {
+            continue;
+        };
+        let Ok(modules) = executable.read_pointers(array, names.len()) else {
+            continue;
+        };
+        let named = modules
+            .iter()
+            .map(|module| module_name(*module).map(|name| (name.to_string(), *module)))
+            .collect::<Option<HashMap<_, _>>>();
+        if let Some(named) = named.filter(|named| named.len() == names.len()) {
+            return Ok((hit + p, named));
+        }
+    }
+    Err(
+        "Il2CppCodeRegistration not found: no code gen module table matches the metadata images"
+            .to_string(),
+    )
+}
+
+// Il2CppCodeGenModule is `moduleName, methodPointerCount, methodPointers, [adjustorThunkCount,
+// adjustorThunks,] invokerIndices, ...`. The adjustor pair appeared in 24.5 and 27.1, which
+// share their metadata version with layouts without it, so look at what the slots hold.
+fn has_adjustor_thunks(executable: &Executable, module: u64) -> bool {
+    let p = executable.pointer_size() as u64;
+    let read = |index: u64| executable.read_pointer(module + index * p).unwrap_or(0);
+    let (method_count, count, thunks) = (read(1), read(3), read(4));
+    count <= method_count
+        && (if count == 0 {
+            thunks == 0
+        } else {
+            executable.is_mapped(thunks)
+        })
+        && executable.is_mapped(read(5))
+}
+
+fn read_code_gen_module(
+    executable: &Executable,
+    module: u64,
+    adjustors: bool,
+) -> Result<CodeGenModule, String> {
+    let p = executable.pointer_size() as u64;
+    let read = |index: u64| executable.read_pointer(module + index * p);
+    let count = read(1)?;
+    if count >= MAX_COUNT {
+        return Err(format!("Code gen module at 0x{:X} is invalid", module));
+    }
+    if count == 0 {
+        return Ok(CodeGenModule {
+            method_pointers: Vec::new(),
+            invoker_indices: Vec::new(),
+        });
+    }
+    Ok(CodeGenModule {
+        method_pointers: executable.read_pointers(read(2)?, count as usize)?,
+        invoker_indices: executable
+            .read_i32s(read(if adjustors { 5 } else { 3 })?, count as usize)
+            .unwrap_or_default(),
+    })
+}
+
+// Il2CppCodeRegistration starts `reversePInvokeWrapperCount, reversePInvokeWrappers,
+// genericMethodPointersCount, genericMethodPointers, [genericAdjustorThunks,]
+// invokerPointersCount, invokerPointers`. What sits between the invokers and codeGenModules
+// changed with every Unity release, try the known distances in pointer slots.
+fn find_code_registration(
+    executable: &Executable,
+    metadata: &GlobalMetadata,
+    modules_field: u64,
+    adjustors: bool,
+) -> Option<(u64, CodeRegistration)> {
+    let p = executable.pointer_size() as u64;
+    let distances: &[u64] = match metadata.version().major {
+        24 => &[13, 15, 16],
+        27 => &[13, 14],
+        29 => &[14, 16],
+        _ => &[16],
+    };
+    let read = |address: u64| executable.read_pointer(address).ok();
+    let table = |count: u64, address: u64| -> Option<Vec<u64>> {
+        match count {
+            0 => Some(Vec::new()),
+            count if count < MAX_COUNT => executable.read_pointers(address, count as usize).ok(),
+            _ => None,
+        }
+    };
+    let code = |pointers: &[u64]| {
+        pointers
+            .iter()
+            .all(|p| *p == 0 || executable.is_executable(*p))
+    };
+
+    distances.iter().find_map(|distance| {
+        let start = modules_field.checked_sub(distance * p)?;
+        let reverse_wrappers = table(read(start)?, read(start + p)?)?;
+        let generic_method_pointers = table(read(start + 2 * p)?, read(start + 3 * p)?)?;
+        let invokers_slot = start + (if adjustors { 5 } else { 4 }) * p;
+        let invokers = table(read(invokers_slot)?, read(invokers_slot + p)?)?;
+        let sample = |pointers: &[u64]| pointers.iter().take(16).copied().collect::<Vec<_>>();
+        if invokers.is_empty()
+            || !code(&sample(&reverse_wrappers))
+            || !code(&sample(&generic_method_pointers))
+            || !code(&sample(&invokers))
+        {
+            return None;
+        }
+        Some((
+            start,
+            CodeRegistration {
+                generic_method_pointers,
+                invokers,
+            },
+        ))
+    })
+}
+
+fn read_field_offsets(
+    executable: &Executable,
+    metadata: &GlobalMetadata,
+    tables: &[u64],
+) -> Vec<Vec<i32>> {
+    metadata
+        .type_definitions
+        .iter()
+        .zip(tables.iter().copied().chain(std::iter::repeat(0)))
+        .map(|(definition, table)| {
+            if table == 0 || definition.field_count == 0 {
+                return Vec::new();
+            }
+            let offsets = executable.read_i32s(table, definition.field_count as usize);
+            offsets
+                .unwrap_or_default()
+                .into_iter()
+                .map(|offset| {
+                    if (offset as u32 & THREAD_STATIC_MASK) != 0 {
+                        THREAD_STATIC_FIELD_OFFSET
+                    } else {
+                        offset
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Metadata 24 lists `(slot, encoded usage)` pairs in the metadata file, later versions store
+// `usage << 29 | index << 1 | 1` in the slot itself until il2cpp replaces it on first use
+fn read_metadata_usages(
+    executable: &Executable,
+    metadata: &GlobalMetadata,
+    slots: &[u64],
+) -> Vec<MetadataUsage> {
+    let decode = |address: u64, encoded: u32, shifted: bool| {
+        let index = if shifted {
+            (encoded & 0x1FFF_FFFE) >> 1
+        } else {
+            encoded & 0x1FFF_FFFF
+        };
+        Some(MetadataUsage {
+            address,
+            kind: MetadataUsageKind::from_raw(encoded >> 29)?,
+            index: index as usize,
+        })
+    };
+    if metadata.version().major == 24 {
+        metadata
+            .metadata_usage_pairs
+            .iter()
+            .filter_map(|pair| {
+                let address = *slots.get(pair.destination_index as usize)?;
+                decode(address, pair.encoded_source_index, false)
+            })
+            .collect()
+    } else {
+        slots
+            .iter()
+            .filter_map(|address| {
+                let encoded = executable.read_u32(*address).ok()?;
+                if encoded & 1 == 0 {
+                    return None;
+                }
+                decode(*address, encoded, true)
+            })
+            .collect()
+    }
+}
+
+// genericMethodTable entries are `genericMethodIndex, methodIndex, invokerIndex,
+// [adjustorThunkIndex]`, the generic method index selects a methodSpecs entry
+// `methodDefinitionIndex, classIndexIndex, methodIndexIndex`
+fn read_generic_methods(
+    executable: &Executable,
+    reader: &mut TypeReader,
+    code: &CodeRegistration,
+    generic_insts: &[u64],
+    (table_count, table): (u64, u64),
+    (spec_count, specs): (u64, u64),
+    adjustors: bool,
+) -> Result<Vec<GenericMethodAddress>, String> {
+    if table_count >= MAX_COUNT || spec_count >= MAX_COUNT {
+        return Err("Il2CppMetadataRegistration generic method tables are invalid".to_string());
+    }
+    let entry_size = if adjustors { 4 } else { 3 };
+    let entries = match table_count {
+        0 => Vec::new(),
+        count => executable.read_i32s(table, count as usize * entry_size)?,
+    };
+    let specs = match spec_count {
+        0 => Vec::new(),
+        count => executable.read_i32s(specs, count as usize * 3)?,
+    };
+    let mut arguments = |index: i32| -> Vec<String> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| generic_insts.get(index))
+            .and_then(|inst| reader.generic_arguments(*inst, 0).ok())
+            .unwrap_or_default()
+    };
+
+    let mut methods = Vec::new();
+    for entry in entries.chunks_exact(entry_size) {
+        let (spec, method, invoker) = (entry[0], entry[1], entry[2]);
+        let pointer = usize::try_from(method)
+            .ok()
+            .and_then(|m| code.generic_method_pointers.get(m))
+            .copied()
+            .filter(|p| *p != 0);
+        let Ok(spec_index) = usize::try_from(spec) else {
+            continue;
+        };
+        let spec = specs.get(spec_index * 3..spec_index * 3 + 3);
+        let (Some(pointer), Some(spec)) = (pointer, spec) else {
+            continue;
+        };
+        let Ok(method_definition) = usize::try_from(spec[0]) else {
+            continue;
+        };
+        methods.push(GenericMethodAddress {
+            method_spec: spec_index,
+            method_definition,
+            class_arguments: arguments(spec[1]),
+            method_arguments: arguments(spec[2]),
+            pointer,
+            invoker: usize::try_from(invoker)
+                .ok()
+                .and_then(|i| code.invokers.get(i).copied()),
+        });
+    }
+    Ok(methods)
+}
+
+// Decodes Il2CppType structs: `data` (a type definition index, generic parameter index or a
+// pointer depending on the kind) followed by `attrs:16 type:8 num_mods byref pinned` bits
+struct TypeReader<'a> {
+    executable: &'a Executable,
+    metadata: &'a GlobalMetadata,
+    types: Vec<u64>,
+    sizes: Vec<u64>,
+    names: HashMap<u64, String>,
+}
+
+impl TypeReader<'_> {
+    fn header(&self, address: u64) -> Result<(u64, u16, TypeKind, bool), String> {
+        let data = self.executable.read_pointer(address)?;
+        let bits = self
+            .executable
+            .read_u32(address + self.executable.pointer_size() as u64)?;
+        // byref is bit 30 before 27.2 and bit 29 after, the other one is never set on a
+        // type stored in the binary (num_mods before, pinned after)
+        let byref = ((bits >> 29) & 3) != 0;
+        let kind = TypeKind::from_raw(((bits >> 16) & 0xFF) as i32);
+        Ok((data, bits as u16, kind, byref))
+    }
+
+    fn resolve(&mut self, address: u64) -> Result<ResolvedType, String> {
+        let (data, attrs, kind, byref) = self.header(address)?;
+        let definition = match kind {
+            TypeKind::Class | TypeKind::ValueType => usize::try_from(data as i32).ok(),
+            TypeKind::GenericInst => Some(self.generic_definition(data)?),
+            _ => None,
+        };
+        let value_type = match kind {
+            TypeKind::Void => false,
+            kind if kind.is_primitive() => true,
+            TypeKind::ValueType => true,
+            TypeKind::GenericInst => definition
+                .and_then(|definition| self.metadata.type_definitions.get(definition))
+                .is_some_and(|d| d.is_value_type()),
+            _ => false,
+        };
+        let pointer = self.executable.pointer_size() as isize;
+        let size = if kind == TypeKind::Void && !byref {
+            0
+        } else if value_type && !byref {
+            self.value_size(kind, data).unwrap_or(0)
+        } else {
+            pointer
+        };
+        Ok(ResolvedType {
+            name: self.name(address, 0)?,
+            kind,
+            attrs,
+            byref,
+            value_type,
+            size,
+            definition,
+        })
+    }
+
+    // what `il2cpp_class_value_size` reports: instance size minus the object header
+    fn value_size(&self, kind: TypeKind, data: u64) -> Option<isize> {
+        let pointer = self.executable.pointer_size() as isize;
+        let size = match kind {
+            TypeKind::Boolean | TypeKind::I1 | TypeKind::U1 => 1,
+            TypeKind::Char | TypeKind::I2 | TypeKind::U2 => 2,
+            TypeKind::I4 | TypeKind::U4 | TypeKind::R4 => 4,
+            TypeKind::I8 | TypeKind::U8 | TypeKind::R8 => 8,
+            TypeKind::I | TypeKind::U => pointer,
+            // the sizes of a generic definition are those of its open form, `Pair<byte>` and
+            // `Pair<long>` differ, so GenericInst sizes are left unknown
+            TypeKind::ValueType => {
+                let definition = usize::try_from(data as i32).ok()?;
+                let sizes = *self.sizes.get(definition)?;
+                let instance_size = self.executable.read_u32(sizes).ok()? as isize;
+                instance_size - 2 * pointer
+            }
+            _ => return None,
+        };
+        (size > 0).then_some(size)
+    }
+
+    // Il2CppGenericClass starts with the type definition index before 27, a type after
+    fn generic_definition(&self, generic_class: u64) -> Result<usize, String> {
+        let index = if self.metadata.version().major < 27 {
+            self.executable.read_i32(generic_class)?
+        } else {
+            let (data, ..) = self.header(self.executable.read_pointer(generic_class)?)?;
+            data as i32
+        };
+        usize::try_from(index)
+            .map_err(|_| format!("Invalid generic class at 0x{:X}", generic_class))
+    }
+
+    fn name(&mut self, address: u64, depth: usize) -> Result<String, String> {
+        if let Some(name) = self.names.get(&address) {
+            return Ok(name.clone());
+        }
+        if depth > MAX_TYPE_DEPTH {
+            return Err(format!("Type at 0x{:X} nests too deep", address));
+        }
+        let (data, _, kind, byref) = self.header(address)?;
+        let p = self.executable.pointer_size() as u64;
+        let mut name = match kind {
+            TypeKind::Class | TypeKind::ValueType => self.definition_name(data as i32, depth)?,
+            TypeKind::SzArray => format!("{}[]", self.name(data, depth + 1)?),
+            TypeKind::Ptr => format!("{}*", self.name(data, depth + 1)?),
+            TypeKind::Array => {
+                // Il2CppArrayType: etype, rank
+                let element = self.executable.read_pointer(data)?;
+                let rank = self.executable.bytes_at(data + p, 1)?[0] as usize;
+                format!(
+                    "{}[{}]",
+                    self.name(element, depth + 1)?,
+                    ",".repeat(rank.saturating_sub(1))
+                )
+            }
+            TypeKind::GenericInst => {
+                let definition = self.generic_definition(data)?;
+                let base = self.definition_name(definition as i32, depth)?;
+                let base = base.split('`').next().unwrap_or_default().to_string();
+                // Il2CppGenericContext follows the definition: class_inst, method_inst
+                let class_inst = self.executable.read_pointer(data + p)?;
+                let arguments = self.generic_arguments(class_inst, depth + 1)?;
+                format!("{}<{}>", base, arguments.join(","))
+            }
+            TypeKind::Var | TypeKind::MVar => {
+                let parameter = usize::try_from(data as i32)
+                    .ok()
+                    .and_then(|index| self.metadata.generic_parameters.get(index))
+                    .ok_or(format!("Invalid generic parameter at 0x{:X}", address))?;
+                self.metadata.string(parameter.name_index)?.to_string()
+            }
+            kind => primitive_name(kind)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("<{:?}>", kind)),
+        };
+        if byref {
+            name.push('&');
+        }
+        self.names.insert(address, name.clone());
+        Ok(name)
+    }
+
+    // Il2CppGenericInst: type_argc, type_argv
+    fn generic_arguments(&mut self, inst: u64, depth: usize) -> Result<Vec<String>, String> {
+        let p = self.executable.pointer_size() as u64;
+        let count = self.executable.read_pointer(inst)?;
+        if count >= MAX_COUNT {
+            return Err(format!("Invalid generic instance at 0x{:X}", inst));
+        }
+        let arguments = self
+            .executable
+            .read_pointers(self.executable.read_pointer(inst + p)?, count as usize)?;
+        arguments
+            .into_iter()
+            .map(|argument| self.name(argument, depth + 1))
+            .collect()
+    }
+
+    // `Namespace.Name`, or `Outer.Name` for nested types
+    fn definition_name(&mut self, index: i32, depth: usize) -> Result<String, String> {
+        let metadata = self.metadata;
+        let definition = usize::try_from(index)
+            .ok()
+            .and_then(|index| metadata.type_definitions.get(index))
+            .ok_or(format!("Type definition {} out of range", index))?;
+        let name = metadata.string(definition.name_index)?;
+        let declaring = usize::try_from(definition.declaring_type_index)
+            .ok()
+            .and_then(|index| self.types.get(index).copied());
+        if let Some(declaring) = declaring {
+            if depth > MAX_TYPE_DEPTH {
+                return Err(format!("Type definition {} nests too deep", index));
+            }
+            let (outer, ..) = self.header(declaring)?;
+            return Ok(format!(
+                "{}.{}",
+                self.definition_name(outer as i32, depth + 1)?,
+                name
+            ));
+        }
+        let namespace = metadata.string(definition.namespace_index)?;
+        Ok(if namespace.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", namespace, name)
+        })
+    }
+}
+
+fn primitive_name(kind: TypeKind) -> Option<&'static str> {
+    Some(match kind {
+        TypeKind::Void => "System.Void",
+        TypeKind::Boolean => "System.Boolean",
+        TypeKind::Char => "System.Char",
+        TypeKind::I1 => "System.SByte",
+        TypeKind::U1 => "System.Byte",
+        TypeKind::I2 => "System.Int16",
+        TypeKind::U2 => "System.UInt16",
+        TypeKind::I4 => "System.Int32",
+        TypeKind::U4 => "System.UInt32",
+        TypeKind::I8 => "System.Int64",
+        TypeKind::U8 => "System.UInt64",
+        TypeKind::R4 => "System.Single",
+        TypeKind::R8 => "System.Double",
+        TypeKind::String => "System.String",
+        TypeKind::TypedByRef => "System.TypedReference",
+        TypeKind::I => "System.IntPtr",
+        TypeKind::U => "System.UIntPtr",
+        TypeKind::Object => "System.Object",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_offline::fixture::{self, CODE, Registration, Section};
+
+    const TYPE_NAMES: [&str; 12] = [
+        "Game.Player",
+        "Game.Mode",
+        "Game.Player.Stats",
+        "System.Object",
+        "System.Int32[]",
+        "Game.Player",
+        "Game.Player&",
+        "System.Int32",
+        "System.String",
+        "System.Int32",
+        "System.Single",
+        "Game.Player<System.Int32>",
+    ];
+
+    fn analyze(major: u32, minor: u32, executable: &Executable) -> BinaryMetadata {
+        let metadata = GlobalMetadata::parse(fixture::metadata(major, minor)).unwrap();
+        BinaryMetadata::analyze(executable, &metadata)
+            .unwrap_or_else(|e| panic!("{}.{}: {}", major, minor, e))
+    }
+
+    fn check(binary: &BinaryMetadata, registration: &Registration, base: u64) {
+        let code = |offset: u64| base + CODE + offset;
+        assert_eq!(
+            binary.registrations.metadata_registration,
+            registration.metadata_registration
+        );
+        assert_eq!(
+            binary.registrations.code_registration,
+            Some(registration.code_registration)
+        );
+
+        let types: Vec<&ResolvedType> = binary.types.iter().map(|t| t.as_ref().unwrap()).collect();
+        let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, TYPE_NAMES);
+        assert_eq!((types[1].kind, types[1].size), (TypeKind::ValueType, 4));
+        assert_eq!(types[2].size, 8);
+        assert!(types[2].value_type && !types[0].value_type);
+        assert_eq!((types[0].size, types[7].size, types[8].size), (8, 4, 8));
+        assert!(types[6].byref && !types[5].byref);
+        assert_eq!(types[9].attrs, 0x0606);
+        assert_eq!(types[11].definition, Some(0));
+        assert_eq!(types[11].size, 8);
+
+        assert_eq!(
+            binary.field_offsets,
+            vec![vec![0x10, THREAD_STATIC_FIELD_OFFSET], vec![0x10], vec![]]
+        );
+        let methods: Vec<(Option<u64>, Option<u64>)> = binary
+            .methods
+            .iter()
+            .map(|m| (m.pointer, m.invoker))
+            .collect();
+        assert_eq!(
+            methods,
+            vec![
+                (Some(code(0x10)), Some(code(0x80))),
+                (Some(code(0x20)), None)
+            ]
+        );
+
+        assert_eq!(binary.generic_methods.len(), 1);
+        let generic = &binary.generic_methods[0];
+        assert_eq!((generic.method_spec, generic.method_definition), (0, 0));
+        assert!(generic.class_arguments.is_empty());
+        assert_eq!(generic.method_arguments, ["System.Int32"]);
+        assert_eq!(
+            (generic.pointer, generic.invoker),
+            (code(0x40), Some(code(0x90)))
+        );
+
+        let usages: Vec<(u64, MetadataUsageKind, usize)> = binary
+            .metadata_usages
+            .iter()
+            .map(|u| (u.address, u.kind, u.index))
+            .collect();
+        let slots = &registration.usage_slots;
+        assert_eq!(
+            usages,
+            vec![
+                (slots[0], MetadataUsageKind::StringLiteral, 1),
+                (slots[1], MetadataUsageKind::TypeInfo, 5),
+                (slots[2], MetadataUsageKind::MethodDef, 0),
+                (slots[3], MetadataUsageKind::FieldInfo, 0),
+                (slots[4], MetadataUsageKind::Il2CppType, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn elf_registrations_are_found_after_relocation() {
+        for (major, minor, relocate) in [(24, 2, true), (27, 0, false), (29, 0, true)] {
+            let registration = fixture::registration(major, minor, 0);
+            let data = fixture::elf64(&registration.sections, relocate);
+            let executable = Executable::parse(data).unwrap();
+            check(&analyze(major, minor, &executable), &registration, 0);
+        }
+    }
+
+    #[test]
+    fn pe_and_macho_registrations_are_found() {
+        let base = 0x1_8000_0000;
+        let registration = fixture::registration(31, 0, base);
+        let executable = Executable::parse(fixture::pe64(&registration.sections, base)).unwrap();
+        check(&analyze(31, 0, &executable), &registration, base);
+
+        let registration = fixture::registration(24, 5, base);
+        let executable = Executable::parse(fixture::macho64(&registration.sections)).unwrap();
+        check(&analyze(24, 5, &executable), &registration, base);
+    }
+
+    #[test]
+    fn mismatched_metadata_is_rejected() {
+        let registration = fixture::registration(27, 0, 0);
+        let executable = Executable::parse(fixture::elf64(&registration.sections, true)).unwrap();
+        let mut metadata = GlobalMetadata::parse(fixture::metadata(27, 0)).unwrap();
+        metadata.type_definitions.pop();
+        let error = BinaryMetadata::analyze(&executable, &metadata)
+            .err()
+            .unwrap();
+        assert!(
+            error.starts_with("Il2CppMetadataRegistration not found"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn self_referencing_types_stop_at_the_depth_limit() {
+        let mut data = Section::new(0x4000, false);
+        // an SzArray of itself
+        data.pointer(0x4000);
+        data.i32s(&[0x1D << 16, 0]);
+        let executable = Executable::parse(fixture::elf64(&[data], false)).unwrap();
+        let metadata = GlobalMetadata::parse(fixture::metadata(27, 0)).unwrap();
+        let mut reader = TypeReader {
+            executable: &executable,
+            metadata: &metadata,
+            types: vec![0x4000],
+            sizes: Vec::new(),
+            names: HashMap::new(),
+        };
+        let error = reader.resolve(0x4000).err().unwrap();
+        assert!(error.contains("nests too deep"), "{}", error);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::il2cpp_offline::metadata::METADATA_SANITY;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::io::Read;
+
+// metadata majors a decoded header is expected to carry
+const KNOWN_MAJORS: [u32; 4] = [24, 27, 29, 31];
+
+/// A step undoing what a game did to its `global-metadata.dat` before shipping it, run by
+/// `GlobalMetadata::parse_with` before the header is read.
+/// Closures `Fn(Vec<u8>) -> Result<Vec<u8>, String>` work as transforms for one-off schemes.
+pub trait MetadataTransform {
+    fn apply(&self, data: Vec<u8>) -> Result<Vec<u8>, String>;
+}
+
+impl<F> MetadataTransform for F
+where
+    F: Fn(Vec<u8>) -> Result<Vec<u8>, String>,
+{
+    fn apply(&self, data: Vec<u8>) -> Result<Vec<u8>, String> {
+        self(data)
+    }
+}
+
+/// Repeating key XOR over the file, starting at byte `start`
+#[derive(Debug, Clone)]
+pub struct Xor {
+    pub key: Vec<u8>,
+    pub start: usize,
+}
+
+impl Xor {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: key.into(),
+            start: 0,
+        }
+    }
+
+    /// Recover a key of up to 4 bytes from the known sanity value, checked against the
+    /// version that follows it
+    pub fn from_magic(data: &[u8]) -> Option<Self> {
+        let head = data.get(..8)?;
+        let sanity = METADATA_SANITY.to_le_bytes();
+        (1..=4).find_map(|len| {
+            let key: Vec<u8> = (0..len).map(|i| head[i] ^ sanity[i]).collect();
+            let decoded: Vec<u8> = (0..8).map(|i| head[i] ^ key[i % len]).collect();
+            let sanity_ok = decoded[..4] == sanity;
+            let major = u32::from_le_bytes(decoded[4..8].try_into().unwrap());
+            (sanity_ok && KNOWN_MAJORS.contains(&major)).then(|| Self::new(key))
+        })
+    }
+}
+
+impl MetadataTransform for Xor {
+    fn apply(&self, mut data: Vec<u8>) -> Result<Vec<u8>, String> {
+        if self.key.is_empty() {
+            return Err("XOR key is empty".to_string());
+        }
+        for (index, byte) in data.iter_mut().enumerate().skip(self.start) {
+            *byte ^= self.key[(index - self.start) % self.key.len()];
+        }
+        Ok(data)
+    }
+}
+
+/// Inflate a gzip or zlib compressed file, told apart by its header
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decompress;
+
+impl MetadataTransform for Decompress {
+    fn apply(&self, data: Vec<u8>) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        let result = match compression(&data) {
+            Some("gzip") => GzDecoder::new(data.as_slice()).read_to_end(&mut out),
+            Some(_) => ZlibDecoder::new(data.as_slice()).read_to_end(&mut out),
+            None => return Err("Metadata is neither gzip nor zlib compressed".to_string()),
+        };
+        result.map_err(|e| format!("Failed to decompress metadata: {}", e))?;
+        Ok(out)
+    }
+}
+
+/// Drop a custom prefix some games put in front of the header. Table offsets are relative
+/// to the header, so nothing else needs adjusting.
+#[derive(Debug, Clone, Copy)]
+pub struct SkipBytes(pub usize);
+
+impl MetadataTransform for SkipBytes {
+    fn apply(&self, mut data: Vec<u8>) -> Result<Vec<u8>, String> {
+        if self.0 > data.len() {
+            return Err(format!(
+                "Cannot skip 0x{:X} bytes of a 0x{:X} byte file",
+                self.0,
+                data.len()
+            ));
+        }
+        data.drain(..self.0);
+        Ok(data)
+    }
+}
+
+/// Undo a shuffled header: `order[i]` is the position the file stores the offset/size pair of
+/// stock table `i` at. Pairs past `order.len()` are left where they are.
+#[derive(Debug, Clone)]
+pub struct HeaderOrder(pub Vec<usize>);
+
+impl MetadataTransform for HeaderOrder {
+    fn apply(&self, mut data: Vec<u8>) -> Result<Vec<u8>, String> {
+        let pair = |index: usize| 8 + index * 8;
+        let last = self.0.iter().copied().max().unwrap_or(0).max(self.0.len());
+        if pair(last + 1) > data.len() {
+            return Err(format!(
+                "Header order covers {} pairs, more than the file holds",
+                last + 1
+            ));
+        }
+        let original = data[..pair(last + 1)].to_vec();
+        for (index, from) in self.0.iter().enumerate() {
+            data[pair(index)..pair(index) + 8]
+                .copy_from_slice(&original[pair(*from)..pair(*from) + 8]);
+        }
+        Ok(data)
+    }
+}
+
+/// `gzip` or `zlib` when the data starts with that stream header
+pub fn compression(data: &[u8]) -> Option<&'static str> {
+    match data {
+        [0x1F, 0x8B, ..] => Some("gzip"),
+        // CMF deflate with a 32K window or less, FCHECK makes the pair a multiple of 31
+        [cmf, flg, ..]
+            if (cmf & 0x0F) == 8
+                && (cmf >> 4) <= 7
+                && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0 =>
+        {
+            Some("zlib")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp_offline::fixture;
+    use crate::il2cpp_offline::metadata::GlobalMetadata;
+    use flate2::{
+        Compression,
+        write::{GzEncoder, ZlibEncoder},
+    };
+    use std::io::Write;
+
+    fn parse_error(data: Vec<u8>) -> String {
+        GlobalMetadata::parse(data).err().unwrap()
+    }
+
+    #[test]
+    fn xor_keys_are_recovered_from_the_sanity() {
+        let plain = fixture::metadata(29, 0);
+        for key in [
+            &[0x5A][..],
+            &[0x12, 0x34],
+            &[1, 2, 3],
+            &[0xDE, 0xAD, 0xBE, 0xEF],
+        ] {
+            let encoded = Xor::new(key).apply(plain.clone()).unwrap();
+            let xor = Xor::from_magic(&encoded).unwrap();
+            assert_eq!(xor.key, key);
+            let metadata = GlobalMetadata::parse_with(encoded.clone(), &[&xor]).unwrap();
+            assert_eq!(metadata.type_definitions.len(), 3);
+            assert!(parse_error(encoded).contains("XOR-ed"));
+        }
+        assert!(Xor::from_magic(&plain[..4]).is_none());
+        assert!(Xor::new(Vec::new()).apply(plain).is_err());
+    }
+
+    #[test]
+    fn xor_start_leaves_the_prefix() {
+        let encoded = Xor {
+            key: vec![0xFF],
+            start: 2,
+        }
+        .apply(vec![1, 2, 3, 4])
+        .unwrap();
+        assert_eq!(encoded, vec![1, 2, 0xFC, 0xFB]);
+    }
+
+    #[test]
+    fn compressed_files_are_detected_and_inflated() {
+        let plain = fixture::metadata(27, 0);
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(&plain).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(&plain).unwrap();
+        let zlib = zlib.finish().unwrap();
+
+        assert_eq!(compression(&gzip), Some("gzip"));
+        assert_eq!(compression(&zlib), Some("zlib"));
+        assert_eq!(compression(&plain), None);
+        for compressed in [gzip, zlib] {
+            assert!(parse_error(compressed.clone()).contains("compressed"));
+            assert_eq!(Decompress.apply(compressed).unwrap(), plain);
+        }
+        assert!(Decompress.apply(plain).is_err());
+    }
+
+    #[test]
+    fn prefixes_are_skipped() {
+        let mut data = b"CUSTOM".to_vec();
+        data.extend(fixture::metadata(31, 0));
+        assert!(parse_error(data.clone()).contains("0x6 bytes in"));
+        let metadata = GlobalMetadata::parse_with(data, &[&SkipBytes(6)]).unwrap();
+        assert_eq!(metadata.images.len(), 2);
+        assert!(SkipBytes(10).apply(vec![0; 4]).is_err());
+    }
+
+    #[test]
+    fn shuffled_headers_are_restored() {
+        let plain = fixture::metadata(27, 0);
+        // the file stores stock pair i at position order[i], here the first three rotated
+        let order = vec![2, 0, 1];
+        let mut shuffled = plain.clone();
+        for (index, at) in order.iter().enumerate() {
+            shuffled[8 + at * 8..16 + at * 8]
+                .copy_from_slice(&plain[8 + index * 8..16 + index * 8]);
+        }
+        assert!(GlobalMetadata::parse(shuffled.clone()).is_err());
+        assert_eq!(HeaderOrder(order).apply(shuffled).unwrap(), plain);
+        assert!(HeaderOrder(vec![10_000]).apply(plain).is_err());
+    }
+
+    #[test]
+    fn closures_are_transforms() {
+        let reverse = |mut data: Vec<u8>| {
+            data.reverse();
+            Ok(data)
+        };
+        let mut data = fixture::metadata(24, 2);
+        data.reverse();
+        let metadata = GlobalMetadata::parse_with(data, &[&reverse]).unwrap();
+        assert_eq!(
+            metadata.version(),
+            crate::il2cpp_offline::metadata::MetadataVersion::new(24, 2)
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use crate::il2cpp_offline::Cursor;
+use crate::il2cpp_remote::MemorySource;
+use crate::il2cpp_remote::maps::{self, GameFiles, MemoryMapping};
+use parking_lot::Mutex;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+const ET_CORE: u16 = 4;
+const PT_LOAD: u32 = 1;
+const PT_NOTE: u32 = 4;
+const PF_X: u32 = 1;
+const PF_W: u32 = 2;
+// "FILE", the note listing the files mapped at crash time
+const NT_FILE: u32 = 0x4649_4C45;
+
+// bytes of the target, either in one of the dump's files or held in memory
+enum RegionData {
+    File { file: usize, offset: u64 },
+    // a file the process had mapped, standing in for bytes the dump left out
+    Mapped { file: usize, offset: u64 },
+    Bytes(Vec<u8>),
+}
+
+struct Region {
+    start: u64,
+    size: u64,
+    data: RegionData,
+}
+
+impl Region {
+    fn end(&self) -> u64 {
+        self.start + self.size
+    }
+
+    // the part of the region from `start` to `end`, both inside it
+    fn slice(&self, start: u64, end: u64) -> Region {
+        let skip = start - self.start;
+        let data = match &self.data {
+            RegionData::File { file, offset } => RegionData::File {
+                file: *file,
+                offset: offset + skip,
+            },
+            RegionData::Mapped { file, offset } => RegionData::Mapped {
+                file: *file,
+                offset: offset + skip,
+            },
+            RegionData::Bytes(bytes) => {
+                RegionData::Bytes(bytes[skip as usize..(end - self.start) as usize].to_vec())
+            }
+        };
+        Region {
+            start,
+            size: end - start,
+            data,
+        }
+    }
+}
+
+/// Memory saved from a process, read without the process: an ELF core file from
+/// `load_core`, or raw region dumps added one by one with a module map from `load_maps`.
+/// Works with the same views and `Cache::from_game_files` as a live `ProcessMemory`.
+/// Regions stay in their files and are read on demand, so large cores are fine.
+pub struct MemoryDump {
+    files: Vec<Mutex<File>>,
+    regions: Vec<Region>,
+    mappings: Vec<MemoryMapping>,
+    pointer_size: usize,
+}
+
+impl MemoryDump {
+    /// An empty dump of a target with `pointer_size` byte pointers, filled with `add_region`
+    /// and `add_region_file`
+    pub fn new(pointer_size: usize) -> Self {
+        Self {
+            files: Vec::new(),
+            regions: Vec::new(),
+            mappings: Vec::new(),
+            pointer_size,
+        }
+    }
+
+    /// Open a little endian ELF core file, e.g. from `gcore` or the kernel's core dumps.
+    /// Memory comes from its `PT_LOAD` segments and the module map from its `NT_FILE` note.
+    /// The kernel leaves unmodified file mappings out by default (`coredump_filter` 0x33),
+    /// the mmapped `global-metadata.dat` among them, so those ranges are read from the
+    /// mapped files at their recorded paths when they exist on this machine. Use
+    /// `add_mapped_file` with copies when the core comes from another device.
+    pub fn load_core(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let mut file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let header = read_at(&mut file, 0, 64)?;
+        if header.get(..4) != Some(b"\x7FELF") {
+            return Err(format!("{} is not an ELF file", path.display()));
+        }
+        let is_64 = match header[4] {
+            1 => false,
+            2 => true,
+            class => return Err(format!("Unknown ELF class {}", class)),
+        };
+        if header[5] != 1 {
+            return Err("Big endian core files are not supported".to_string());
+        }
+        let e_type = u16::from_le_bytes([header[16], header[17]]);
+        if e_type != ET_CORE {
+            return Err(format!(
+                "{} is not a core file (ELF type {})",
+                path.display(),
+                e_type
+            ));
+        }
+        let (phoff, phentsize, phnum) = if is_64 {
+            let mut cursor = Cursor::new(&header[32..]);
+            let phoff = cursor.u64()?;
+            cursor.skip(14)?;
+            (phoff, cursor.u16()?, cursor.u16()?)
+        } else {
+            let mut cursor = Cursor::new(&header[28..]);
+            let phoff = cursor.u32()?.into();
+            cursor.skip(10)?;
+            (phoff, cursor.u16()?, cursor.u16()?)
+        };
+        let table = read_at(&mut file, phoff, phentsize as usize * phnum as usize)?;
+
+        let mut dump = Self::new(if is_64 { 8 } else { 4 });
+        let mut segments = Vec::new();
+        let mut notes = Vec::new();
+        for entry in table.chunks_exact(phentsize.max(1) as usize) {
+            let mut cursor = Cursor::new(entry);
+            // the 64 bit header moves p_flags up next to p_type
+            let (p_type, flags, offset, vaddr, filesz) = if is_64 {
+                let p_type = cursor.u32()?;
+                let flags = cursor.u32()?;
+                let offset = cursor.u64()?;
+                let vaddr = cursor.u64()?;
+                cursor.skip(8)?;
+                (p_type, flags, offset, vaddr, cursor.u64()?)
+            } else {
+                let p_type = cursor.u32()?;
+                let offset = cursor.u32()?.into();
+                let vaddr = cursor.u32()?.into();
+                cursor.skip(4)?;
+                let filesz = cursor.u32()?.into();
+                cursor.skip(4)?;
+                (p_type, cursor.u32()?, offset, vaddr, filesz)
+            };
+            match p_type {
+                PT_LOAD => segments.push((vaddr, filesz, offset, flags)),
+                PT_NOTE => notes.push(read_at(&mut file, offset, filesz as usize)?),
+                _ => {}
+            }
+        }
+
+        dump.files.push(Mutex::new(file));
+        // segments the kernel filtered out (coredump_filter) have no bytes in the file
+        for (start, size, offset, _) in segments.iter().filter(|s| s.1 > 0) {
+            dump.insert(Region {
+                start: *start,
+                size: *size,
+                data: RegionData::File {
+                    file: 0,
+                    offset: *offset,
+                },
+            });
+        }
+        for note in &notes {
+            dump.mappings.extend(parse_file_note(note, is_64)?);
+        }
+        // NT_FILE carries no permissions, take them from the segment holding the mapping
+        for mapping in &mut dump.mappings {
+            if let Some((_, _, _, flags)) = segments
+                .iter()
+                .find(|(start, _, _, _)| *start == mapping.start)
+            {
+                mapping.writable = flags & PF_W != 0;
+                mapping.executable = flags & PF_X != 0;
+            }
+        }
+        for mapping in dump.mappings.clone() {
+            if let Some(file) = mapping
+                .path
+                .as_deref()
+                .and_then(|path| File::open(path).ok())
+            {
+                dump.fill_gaps(&mapping, file)?;
+            }
+        }
+        Ok(dump)
+    }
+
+    /// Add `data` as the memory at `address`, replacing what an earlier region held there
+    pub fn add_region(&mut self, address: u64, data: Vec<u8>) {
+        self.insert(Region {
+            start: address,
+            size: data.len() as u64,
+            data: RegionData::Bytes(data),
+        });
+    }
+
+    /// Add a raw dump of the memory at `address`, the whole file being the region
+    pub fn add_region_file(&mut self, address: u64, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let size = file
+            .metadata()
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .len();
+        self.files.push(Mutex::new(file));
+        self.insert(Region {
+            start: address,
+            size,
+            data: RegionData::File {
+                file: self.files.len() - 1,
+                offset: 0,
+            },
+        });
+        Ok(())
+    }
+
+    /// Back the mappings of the file `name` (e.g. `global-metadata.dat`) with `path`, a copy
+    /// of that file, wherever the dump holds no bytes of its own. Replaces what `load_core`
+    /// read from the recorded path.
+    pub fn add_mapped_file(&mut self, name: &str, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let mappings: Vec<MemoryMapping> = self
+            .mappings
+            .iter()
+            .filter(|m| {
+                m.file_name()
+                    .is_some_and(|file| file.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+            .collect();
+        if mappings.is_empty() {
+            return Err(format!("{} is not in the module map of the dump", name));
+        }
+        for mapping in &mappings {
+            let file = File::open(path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            self.fill_gaps(mapping, file)?;
+        }
+        Ok(())
+    }
+
+    /// Use a `/proc/<pid>/maps` file saved alongside raw region dumps as the module map
+    pub fn load_maps(&mut self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        self.mappings = maps::parse_maps(&text);
+        Ok(())
+    }
+
+    pub fn set_mappings(&mut self, mappings: Vec<MemoryMapping>) {
+        self.mappings = mappings;
+    }
+
+    /// The module map, file backed mappings only for a core file
+    pub fn maps(&self) -> &[MemoryMapping] {
+        &self.mappings
+    }
+
+    /// `(start, end)` of every region the dump holds bytes for, in address order
+    pub fn regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.regions
+            .iter()
+            .map(|region| (region.start, region.end()))
+    }
+
+    /// Load address of the module file `name`, e.g. `GameAssembly.dll` or `libil2cpp.so`
+    pub fn module_base(&self, name: &str) -> Result<u64, String> {
+        maps::module_base(&self.mappings, name)
+            .ok_or(format!("{} is not in the module map of the dump", name))
+    }
+
+    /// Find the il2cpp binary and `global-metadata.dat` in the module map. The paths are the
+    /// ones on the machine the dump was taken on, point them at copies when they differ.
+    pub fn game_files(&self) -> Result<GameFiles, String> {
+        maps::game_files(&self.mappings).map_err(|e| format!("{} in the dump", e))
+    }
+
+    // back the parts of `mapping` the dump has no bytes for with `file`, up to its end
+    fn fill_gaps(&mut self, mapping: &MemoryMapping, file: File) -> Result<(), String> {
+        let len = file
+            .metadata()
+            .map_err(|e| format!("Failed to read the file of 0x{:X}: {}", mapping.start, e))?
+            .len();
+        let end = mapping
+            .end
+            .min(mapping.start + len.saturating_sub(mapping.offset));
+        let mut gaps = Vec::new();
+        let mut at = mapping.start;
+        for region in &self.regions {
+            if matches!(region.data, RegionData::Mapped { .. }) || region.end() <= at {
+                continue;
+            }
+            if region.start >= end {
+                break;
+            }
+            if region.start > at {
+                gaps.push((at, region.start));
+            }
+            at = region.end();
+        }
+        if at < end {
+            gaps.push((at, end));
+        }
+        if gaps.is_empty() {
+            return Ok(());
+        }
+        self.files.push(Mutex::new(file));
+        let file = self.files.len() - 1;
+        for (start, end) in gaps {
+            self.insert(Region {
+                start,
+                size: end - start,
+                data: RegionData::Mapped {
+                    file,
+                    offset: mapping.offset + (start - mapping.start),
+                },
+            });
+        }
+        Ok(())
+    }
+
+    // keep regions sorted and non-overlapping, the newest region wins
+    fn insert(&mut self, region: Region) {
+        let (start, end) = (region.start, region.end());
+        let mut kept = Vec::with_capacity(self.regions.len() + 2);
+        for old in self.regions.drain(..) {
+            if old.end() <= start || old.start >= end {
+                kept.push(old);
+                continue;
+            }
+            if old.start < start {
+                kept.push(old.slice(old.start, start));
+            }
+            if old.end() > end {
+                kept.push(old.slice(end, old.end()));
+            }
+        }
+        kept.push(region);
+        kept.sort_by_key(|region| region.start);
+        self.regions = kept;
+    }
+
+    fn region_at(&self, address: u64) -> Option<&Region> {
+        let index = self
+            .regions
+            .partition_point(|region| region.start <= address)
+            .checked_sub(1)?;
+        let region = &self.regions[index];
+        (address < region.end()).then_some(region)
+    }
+}
+
+impl MemorySource for MemoryDump {
+    fn read(&self, address: u64, buf: &mut [u8]) -> Result<(), String> {
+        let mut done = 0;
+        // a read may span adjacent regions, as consecutive segments of a core often are
+        while done < buf.len() {
+            let at = address + done as u64;
+            let region = self.region_at(at).ok_or_else(|| {
+                format!(
+                    "Failed to read 0x{:X} bytes at 0x{:X}: 0x{:X} is not in the dump",
+                    buf.len(),
+                    address,
+                    at
+                )
+            })?;
+            let skip = at - region.start;
+            let len = ((region.size - skip) as usize).min(buf.len() - done);
+            let out = &mut buf[done..done + len];
+            match &region.data {
+                RegionData::Bytes(bytes) => {
+                    out.copy_from_slice(&bytes[skip as usize..skip as usize + len])
+                }
+                RegionData::File { file, offset } | RegionData::Mapped { file, offset } => {
+                    let mut file = self.files[*file].lock();
+                    file.seek(SeekFrom::Start(offset + skip))
+                        .and_then(|_| file.read_exact(out))
+                        .map_err(|e| format!("Failed to read 0x{:X} from the dump: {}", at, e))?;
+                }
+            }
+            done += len;
+        }
+        Ok(())
+    }
+
+    fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+    let mut buf = vec![0; len];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut buf))
+        .map_err(|e| format!("Failed to read 0x{:X} bytes at 0x{:X}: {}", len, offset, e))?;
+    Ok(buf)
+}
+
+// Mappings of the NT_FILE notes in a PT_NOTE segment: `count, page_size`, then
+// `start, end, page_offset` for each file, then their null terminated names
+fn parse_file_note(notes: &[u8], is_64: bool) -> Result<Vec<MemoryMapping>, String> {
+    let align4 = |len: usize| (len + 3) & !3;
+    let mut cursor = Cursor::new(notes);
+    let mut mappings = Vec::new();
+    while !cursor.at_end() {
+        let name_size = cursor.u32()? as usize;
+        let desc_size = cursor.u32()? as usize;
+        let note_type = cursor.u32()?;
+        cursor.skip(align4(name_size))?;
+        let desc = cursor.bytes(desc_size)?;
+        cursor.skip(align4(desc_size) - desc_size)?;
+        if note_type != NT_FILE {
+            continue;
+        }
+        let word_size = if is_64 { 8 } else { 4 };
+        let mut words = Cursor::new(desc);
+        let mut word = || -> Result<u64, String> {
+            if is_64 {
+                words.u64()
+            } else {
+                words.u32().map(u64::from)
+            }
+        };
+        let count = word()? as usize;
+        let page_size = word()?;
+        let mut ranges = Vec::new();
+        for _ in 0..count {
+            ranges.push((word()?, word()?, word()?));
+        }
+        let mut names = desc
+            .get(word_size * (2 + 3 * count)..)
+            .ok_or("NT_FILE note is truncated")?
+            .split(|b| *b == 0)
+            .map(|name| String::from_utf8_lossy(name).into_owned());
+        for (start, end, page_offset) in ranges {
+            mappings.push(MemoryMapping {
+                start,
+                end,
+                readable: true,
+                writable: false,
+                executable: false,
+                offset: page_offset * page_size,
+                path: names.next(),
+            });
+        }
+    }
+    Ok(mappings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // an ELF note with the name padded to 4 bytes, as in a PT_NOTE segment
+    fn note(note_type: u32, desc: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for value in [5, desc.len() as u32, note_type] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(b"CORE\0\0\0\0");
+        out.extend_from_slice(desc);
+        out.resize((out.len() + 3) & !3, 0);
+        out
+    }
+
+    fn file_note(is_64: bool, page_size: u64, files: &[(u64, u64, u64, &str)]) -> Vec<u8> {
+        let mut desc = Vec::new();
+        let mut word = |value: u64| {
+            if is_64 {
+                desc.extend_from_slice(&value.to_le_bytes());
+            } else {
+                desc.extend_from_slice(&(value as u32).to_le_bytes());
+            }
+        };
+        word(files.len() as u64);
+        word(page_size);
+        for (start, end, page_offset, _) in files {
+            word(*start);
+            word(*end);
+            word(*page_offset);
+        }
+        for (_, _, _, name) in files {
+            desc.extend_from_slice(name.as_bytes());
+            desc.push(0);
+        }
+        note(NT_FILE, &desc)
+    }
+
+    fn temp_file(name: &str, data: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("il2cpp_rs_{}_{}", std::process::id(), name));
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn file_notes_are_parsed_in_both_word_sizes() {
+        let files = [
+            (
+                0x7000_0000,
+                0x7000_3000,
+                2,
+                "/data/app/lib/arm/libil2cpp.so",
+            ),
+            (
+                0x7100_0000,
+                0x7100_1000,
+                0,
+                "/data/Game Data/global-metadata.dat",
+            ),
+        ];
+        for is_64 in [false, true] {
+            // other notes such as NT_PRSTATUS come first and are skipped
+            let mut notes = note(1, &[0xEE; 6]);
+            notes.extend(file_note(is_64, 0x1000, &files));
+            let mappings = parse_file_note(&notes, is_64).unwrap();
+            assert_eq!(mappings.len(), 2);
+            assert_eq!(
+                (mappings[0].start, mappings[0].end),
+                (0x7000_0000, 0x7000_3000)
+            );
+            assert_eq!(mappings[0].offset, 0x2000);
+            assert_eq!(mappings[0].file_name(), Some("libil2cpp.so"));
+            assert_eq!(
+                mappings[1].path.as_deref(),
+                Some("/data/Game Data/global-metadata.dat")
+            );
+        }
+
+        let mut truncated = file_note(true, 0x1000, &files);
+        truncated.truncate(40);
+        assert!(parse_file_note(&truncated, true).is_err());
+    }
+
+    #[test]
+    fn newer_regions_replace_what_they_overlap() {
+        let mut dump = MemoryDump::new(8);
+        dump.add_region(0x1000, vec![1; 0x100]);
+        dump.add_region(0x1100, vec![2; 0x100]);
+        dump.add_region(0x1080, vec![3; 0x100]);
+        assert_eq!(
+            dump.regions().collect::<Vec<_>>(),
+            vec![(0x1000, 0x1080), (0x1080, 0x1180), (0x1180, 0x1200)]
+        );
+        // one read across three regions
+        let bytes = dump.read_bytes(0x107F, 0x103).unwrap();
+        assert_eq!(
+            (bytes[0], bytes[1], bytes[0x100], bytes[0x102]),
+            (1, 3, 3, 2)
+        );
+
+        // a region inside another splits it
+        dump.add_region(0x1010, vec![4; 0x10]);
+        assert_eq!(dump.regions().next(), Some((0x1000, 0x1010)));
+        assert_eq!(dump.read_bytes(0x100F, 3).unwrap(), vec![1, 4, 4]);
+        assert_eq!(dump.read_bytes(0x1020, 1).unwrap(), vec![1]);
+        // and one covering several removes them
+        dump.add_region(0x1000, vec![5; 0x200]);
+        assert_eq!(dump.regions().collect::<Vec<_>>(), vec![(0x1000, 0x1200)]);
+        assert!(
+            dump.read_bytes(0x11FF, 2)
+                .unwrap_err()
+                .contains("0x1200 is not in the dump")
+        );
+    }
+
+    #[test]
+    fn mapped_files_fill_the_gaps_of_the_dump() {
+        let data: Vec<u8> = (0..0x3000u32).map(|i| (i / 0x1000) as u8 + 1).collect();
+        let path = temp_file("mapped.dat", &data);
+        let mut dump = MemoryDump::new(8);
+        // the second page of the file is mapped, with one page of it in the dump
+        dump.set_mappings(vec![MemoryMapping {
+            start: 0x10000,
+            end: 0x13000,
+            readable: true,
+            writable: false,
+            executable: false,
+            offset: 0x1000,
+            path: Some(path.display().to_string()),
+        }]);
+        dump.add_region(0x11000, vec![0xAA; 0x1000]);
+
+        let name = path.file_name().unwrap().to_str().unwrap().to_string();
+        dump.add_mapped_file(&name, &path).unwrap();
+        // the last page lies past the end of the file and stays unreadable
+        assert_eq!(
+            dump.regions().collect::<Vec<_>>(),
+            vec![(0x10000, 0x11000), (0x11000, 0x12000)]
+        );
+        assert_eq!(dump.read_bytes(0x10FFF, 2).unwrap(), vec![2, 0xAA]);
+        assert!(dump.read_bytes(0x12000, 1).is_err());
+
+        // a copy replaces the file read earlier, never the dump's own bytes
+        let copy = temp_file("copy.dat", &[9; 0x3000]);
+        dump.add_mapped_file(&name, &copy).unwrap();
+        assert_eq!(dump.read_bytes(0x10FFF, 2).unwrap(), vec![9, 0xAA]);
+        assert!(dump.add_mapped_file("other.dat", &copy).is_err());
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(copy).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+use crate::il2cpp_remote::MemorySource;
+use crate::il2cpp_remote::maps::{self, GameFiles, MemoryMapping};
+use std::fs::File;
+use std::os::unix::fs::FileExt;
+
+/// Memory of another process on the same machine. Reads go through `process_vm_readv`,
+/// falling back to `/proc/<pid>/mem` where the syscall is unavailable or refused.
+/// Both need ptrace access to the target: the same user with `kernel.yama.ptrace_scope`
+/// at 0, a parent process, or CAP_SYS_PTRACE.
+pub struct ProcessMemory {
+    pid: i32,
+    mem: Option<File>,
+    pointer_size: usize,
+}
+
+impl ProcessMemory {
+    pub fn attach(pid: i32) -> Result<Self, String> {
+        std::fs::metadata(format!("/proc/{}", pid))
+            .map_err(|e| format!("Process {} not found: {}", pid, e))?;
+        let mut ident = [0; 5];
+        let elf_class = File::open(format!("/proc/{}/exe", pid))
+            .and_then(|exe| exe.read_exact_at(&mut ident, 0))
+            .map(|_| ident[4]);
+        // ELFCLASS32 processes (32 bit ARM builds under an emulator) use 4 byte pointers
+        let pointer_size = if elf_class.ok() == Some(1) { 4 } else { 8 };
+        Ok(Self {
+            pid,
+            mem: File::open(format!("/proc/{}/mem", pid)).ok(),
+            pointer_size,
+        })
+    }
+
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// The current memory map of the process
+    pub fn maps(&self) -> Result<Vec<MemoryMapping>, String> {
+        let text = std::fs::read_to_string(format!("/proc/{}/maps", self.pid))
+            .map_err(|e| format!("Failed to read the maps of process {}: {}", self.pid, e))?;
+        Ok(maps::parse_maps(&text))
+    }
+
+    /// Load address of the module file `name`, e.g. `GameAssembly.dll` or `libil2cpp.so`
+    pub fn module_base(&self, name: &str) -> Result<u64, String> {
+        maps::module_base(&self.maps()?, name)
+            .ok_or(format!("{} is not loaded in process {}", name, self.pid))
+    }
+
+    /// Find the il2cpp binary and `global-metadata.dat` among the mapped files
+    pub fn game_files(&self) -> Result<GameFiles, String> {
+        maps::game_files(&self.maps()?).map_err(|e| format!("{} in process {}", e, self.pid))
+    }
+
+    fn read_vm(&self, address: u64, buf: &mut [u8]) -> std::io::Result<usize> {
+        let local = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as usize as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        let read = unsafe { libc::process_vm_readv(self.pid, &local, 1, &remote, 1, 0) };
+        if read < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(read as usize)
+        }
+    }
+}
+
+impl MemorySource for ProcessMemory {
+    fn read(&self, address: u64, buf: &mut [u8]) -> Result<(), String> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let error = match self.read_vm(address, buf) {
+            Ok(read) if read == buf.len() => return Ok(()),
+            Ok(read) => format!("only 0x{:X} bytes readable", read),
+            Err(e) => e.to_string(),
+        };
+        // a range crossing into an unmapped page fails the same way with both, but ptrace
+        // policies and seccomp filters sometimes allow only one of them
+        if let Some(mem) = &self.mem {
+            return mem.read_exact_at(buf, address).map_err(|e| {
+                format!(
+                    "Failed to read 0x{:X} bytes at 0x{:X} in process {}: {}, {}",
+                    buf.len(),
+                    address,
+                    self.pid,
+                    error,
+                    e
+                )
+            });
+        }
+        Err(format!(
+            "Failed to read 0x{:X} bytes at 0x{:X} in process {}: {}",
+            buf.len(),
+            address,
+            self.pid,
+            error
+        ))
+    }
+
+    fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+}
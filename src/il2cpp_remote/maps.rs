@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+// file names the il2cpp code is shipped under, Windows builds run through Wine/Proton
+const IL2CPP_BINARIES: [&str; 4] = [
+    "GameAssembly.dll",
+    "GameAssembly.so",
+    "libil2cpp.so",
+    "UnityFramework",
+];
+const METADATA_FILE: &str = "global-metadata.dat";
+
+/// One line of `/proc/<pid>/maps`
+#[derive(Debug, Clone)]
+pub struct MemoryMapping {
+    pub start: u64,
+    pub end: u64,
+    pub readable: bool,
+    pub writable: bool,
+    pub executable: bool,
+    /// Offset of `start` in the mapped file
+    pub offset: u64,
+    /// Backing file, or a pseudo name such as `[heap]`, `None` for anonymous memory
+    pub path: Option<String>,
+}
+
+impl MemoryMapping {
+    pub fn contains(&self, address: u64) -> bool {
+        address >= self.start && address < self.end
+    }
+
+    /// File name of the backing file, `libil2cpp.so` for `/data/app/.../libil2cpp.so`
+    pub fn file_name(&self) -> Option<&str> {
+        self.path.as_deref()?.rsplit('/').next()
+    }
+}
+
+/// The files a game loaded its il2cpp code and metadata from, see `Cache::from_game_files`
+#[derive(Debug, Clone)]
+pub struct GameFiles {
+    pub metadata: PathBuf,
+    pub binary: PathBuf,
+    /// Where `binary` is mapped in the process
+    pub binary_base: u64,
+}
+
+/// Parse the text of a `/proc/<pid>/maps` file, lines that do not parse are skipped
+pub fn parse_maps(text: &str) -> Vec<MemoryMapping> {
+    text.lines().filter_map(parse_line).collect()
+}
+
+// `start-end perms offset dev inode path`, the path may contain spaces
+fn parse_line(line: &str) -> Option<MemoryMapping> {
+    let mut parts = line.splitn(6, ' ');
+    let (start, end) = parts.next()?.split_once('-')?;
+    let perms = parts.next()?.as_bytes();
+    let offset = parts.next()?;
+    parts.next()?; // dev
+    parts.next()?; // inode
+    let path = parts.next().map(str::trim).filter(|path| !path.is_empty());
+    Some(MemoryMapping {
+        start: u64::from_str_radix(start, 16).ok()?,
+        end: u64::from_str_radix(end, 16).ok()?,
+        readable: perms.first() == Some(&b'r'),
+        writable: perms.get(1) == Some(&b'w'),
+        executable: perms.get(2) == Some(&b'x'),
+        offset: u64::from_str_radix(offset, 16).ok()?,
+        path: path.map(str::to_string),
+    })
+}
+
+/// Lowest address a file named `name` is mapped at, matched case-insensitively since Wine
+/// reports Windows file names as they are on disk
+pub fn module_base(mappings: &[MemoryMapping], name: &str) -> Option<u64> {
+    mappings
+        .iter()
+        .filter(|m| {
+            m.file_name()
+                .is_some_and(|file| file.eq_ignore_ascii_case(name))
+        })
+        .map(|m| m.start)
+        .min()
+}
+
+/// The full path of the first mapping whose file is named `name`
+pub fn module_path<'a>(mappings: &'a [MemoryMapping], name: &str) -> Option<&'a Path> {
+    mappings
+        .iter()
+        .find(|m| {
+            m.file_name()
+                .is_some_and(|file| file.eq_ignore_ascii_case(name))
+        })
+        .and_then(|m| m.path.as_deref())
+        .map(Path::new)
+}
+
+/// Find the il2cpp binary and `global-metadata.dat` among the mapped files. il2cpp maps the
+/// metadata file directly, games that decrypt it into the heap have no such mapping.
+pub fn game_files(mappings: &[MemoryMapping]) -> Result<GameFiles, String> {
+    let (binary, binary_base) = IL2CPP_BINARIES
+        .iter()
+        .find_map(|name| Some((module_path(mappings, name)?, module_base(mappings, name)?)))
+        .ok_or(format!(
+            "No il2cpp binary ({}) is mapped",
+            IL2CPP_BINARIES.join(", ")
+        ))?;
+    let metadata = module_path(mappings, METADATA_FILE).ok_or(format!(
+        "{} is not mapped, load it from the game files instead",
+        METADATA_FILE
+    ))?;
+    Ok(GameFiles {
+        metadata: metadata.to_path_buf(),
+        binary: binary.to_path_buf(),
+        binary_base,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "\
+7f0000000000-7f0000001000 r--p 00000000 103:02 1311 /data/app/com.game/lib/arm64/libil2cpp.so
+7f0000001000-7f0000400000 r-xp 00001000 103:02 1311 /data/app/com.game/lib/arm64/libil2cpp.so
+7f0000400000-7f0000500000 rw-p 00400000 103:02 1311 /data/app/com.game/lib/arm64/libil2cpp.so
+7f1000000000-7f1000200000 r--s 00000000 103:02 2048 /data/Game Data/Metadata/global-metadata.dat
+7f2000000000-7f2000021000 rw-p 00000000 00:00 0                          [heap]
+7f3000000000-7f3000001000 ---p 00000000 00:00 0
+not a mapping
+";
+
+    #[test]
+    fn maps_lines_are_parsed() {
+        let mappings = parse_maps(MAPS);
+        assert_eq!(mappings.len(), 6);
+
+        let text = &mappings[1];
+        assert_eq!((text.start, text.end), (0x7f00_0000_1000, 0x7f00_0040_0000));
+        assert!(text.readable && !text.writable && text.executable);
+        assert_eq!(text.offset, 0x1000);
+        assert_eq!(text.file_name(), Some("libil2cpp.so"));
+        assert!(text.contains(0x7f00_0000_1000) && !text.contains(0x7f00_0040_0000));
+
+        assert_eq!(
+            mappings[3].path.as_deref(),
+            Some("/data/Game Data/Metadata/global-metadata.dat")
+        );
+        assert_eq!(mappings[4].path.as_deref(), Some("[heap]"));
+        let guard = &mappings[5];
+        assert!(guard.path.is_none() && !guard.readable && !guard.executable);
+    }
+
+    #[test]
+    fn game_files_are_found_in_the_maps() {
+        let mappings = parse_maps(MAPS);
+        assert_eq!(
+            module_base(&mappings, "LIBIL2CPP.SO"),
+            Some(0x7f00_0000_0000)
+        );
+        assert_eq!(module_base(&mappings, "libunity.so"), None);
+
+        let files = game_files(&mappings).unwrap();
+        assert_eq!(
+            files.binary,
+            Path::new("/data/app/com.game/lib/arm64/libil2cpp.so")
+        );
+        assert_eq!(files.binary_base, 0x7f00_0000_0000);
+        assert_eq!(files.metadata.file_name().unwrap(), "global-metadata.dat");
+
+        let error = game_files(&mappings[..3]).err().unwrap();
+        assert!(
+            error.contains("global-metadata.dat is not mapped"),
+            "{}",
+            error
+        );
+        let error = game_files(&mappings[3..]).err().unwrap();
+        assert!(error.contains("No il2cpp binary"), "{}", error);
+    }
+}
@@ -0,0 +1,82 @@
+// Reading il2cpp structures out of another process, without injecting or calling exports.
+// The in-process views (`ObjectView`, `Il2CppArrayView`, `il2cpp_view!` structs, ...) deref
+// pointers directly and cannot read through a `MemorySource`, `view` has the remote readers.
+// Caches come from the game's files on disk, see `Cache::from_process`.
+#[cfg(target_os = "linux")]
+pub mod linux;
+pub mod dump;
+pub mod maps;
+pub mod view;
+
+use crate::il2cpp::pod::Pod;
+
+// longest C string read before giving up on a missing terminator
+const MAX_C_STRING: usize = 1024;
+
+/// Memory of the process being inspected. Addresses are the target's, reads copy the bytes
+/// out so a bad address is an error rather than a fault.
+pub trait MemorySource {
+    /// Fill `buf` from `address`, failing unless every byte could be read
+    fn read(&self, address: u64, buf: &mut [u8]) -> Result<(), String>;
+
+    /// Pointer size of the target, 4 for 32 bit Android builds
+    fn pointer_size(&self) -> usize;
+
+    fn read_bytes(&self, address: u64, len: usize) -> Result<Vec<u8>, String> {
+        let mut buf = vec![0; len];
+        self.read(address, &mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_u32(&self, address: u64) -> Result<u32, String> {
+        let mut buf = [0; 4];
+        self.read(address, &mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_i32(&self, address: u64) -> Result<i32, String> {
+        Ok(self.read_u32(address)? as i32)
+    }
+
+    fn read_u64(&self, address: u64) -> Result<u64, String> {
+        let mut buf = [0; 8];
+        self.read(address, &mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// A target pointer, widened to 64 bits
+    fn read_pointer(&self, address: u64) -> Result<u64, String> {
+        if self.pointer_size() == 8 {
+            self.read_u64(address)
+        } else {
+            self.read_u32(address).map(u64::from)
+        }
+    }
+
+    /// Null terminated UTF-8 string such as a class name, read in small chunks so a string
+    /// at the end of a mapping does not fail
+    fn read_c_string(&self, address: u64) -> Result<String, String> {
+        let mut bytes = Vec::new();
+        let mut chunk = [0; 64];
+        while bytes.len() < MAX_C_STRING {
+            let at = address + bytes.len() as u64;
+            // never cross a page boundary in one read
+            let len = chunk.len().min(0x1000 - (at as usize & 0xFFF));
+            self.read(at, &mut chunk[..len])?;
+            if let Some(end) = chunk[..len].iter().position(|b| *b == 0) {
+                bytes.extend_from_slice(&chunk[..end]);
+                return String::from_utf8(bytes)
+                    .map_err(|e| format!("String at 0x{:X}: {}", address, e));
+            }
+            bytes.extend_from_slice(&chunk[..len]);
+        }
+        Err(format!("String at 0x{:X} is not terminated", address))
+    }
+}
+
+/// Copy a `T` out of the target. `Pod` makes any bytes a valid `T`, it must still be laid
+/// out the way the target lays it out (pointers of a 32 bit target are `u32`).
+pub fn read_value<T: Pod>(source: &dyn MemorySource, address: u64) -> Result<T, String> {
+    let bytes = source.read_bytes(address, std::mem::size_of::<T>())?;
+    Ok(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) })
+}
@@ -0,0 +1,477 @@
+use crate::il2cpp::classes::class::Class;
+use crate::il2cpp::classes::field::Field;
+use crate::il2cpp::pod::Pod;
+use crate::il2cpp_cache::Il2CppCacheTrait;
+use crate::il2cpp_remote::{MemorySource, read_value};
+
+// Il2CppClass starts `image, gc_desc, name, namespaze, byval_arg, this_arg, element_class,
+// castClass, declaringType, parent` in every supported version, in pointer-sized slots with
+// the two Il2CppType values taking two each. What follows moves between Unity releases.
+const CLASS_IMAGE: u64 = 0;
+const CLASS_NAME: u64 = 2;
+const CLASS_NAMESPACE: u64 = 3;
+const CLASS_ELEMENT_CLASS: u64 = 8;
+const CLASS_DECLARING_TYPE: u64 = 10;
+const CLASS_PARENT: u64 = 11;
+// a cycle in corrupt memory must not loop forever
+const MAX_PARENT_DEPTH: usize = 64;
+// lengths past this are a stale or wrong pointer, not a real string or array
+const MAX_LENGTH: usize = 0x1000_0000;
+
+/// An `Il2CppClass` in the target, read through its version independent members.
+/// Fields and their offsets come from a cache built for the same game, e.g. `Cache::from_game`.
+#[derive(Clone, Copy)]
+pub struct RemoteClass<'s> {
+    source: &'s dyn MemorySource,
+    pub address: u64,
+}
+
+impl<'s> RemoteClass<'s> {
+    pub fn new(source: &'s dyn MemorySource, address: u64) -> Option<Self> {
+        (address != 0).then_some(Self { source, address })
+    }
+
+    fn slot(&self, index: u64) -> Result<u64, String> {
+        self.source
+            .read_pointer(self.address + index * self.source.pointer_size() as u64)
+    }
+
+    pub fn name(&self) -> Result<String, String> {
+        self.source.read_c_string(self.slot(CLASS_NAME)?)
+    }
+
+    pub fn namespace(&self) -> Result<String, String> {
+        self.source.read_c_string(self.slot(CLASS_NAMESPACE)?)
+    }
+
+    /// `Namespace.Name`, or just `Name` in the global namespace, like `ClassInner::full_name`
+    pub fn full_name(&self) -> Result<String, String> {
+        let namespace = self.namespace()?;
+        if namespace.is_empty() {
+            self.name()
+        } else {
+            Ok(format!("{}.{}", namespace, self.name()?))
+        }
+    }
+
+    /// Name of the image the class is defined in, `Il2CppImage::name`
+    pub fn image_name(&self) -> Result<String, String> {
+        let image = self.slot(CLASS_IMAGE)?;
+        self.source.read_c_string(self.source.read_pointer(image)?)
+    }
+
+    pub fn parent(&self) -> Result<Option<RemoteClass<'s>>, String> {
+        Ok(Self::new(self.source, self.slot(CLASS_PARENT)?))
+    }
+
+    pub fn declaring_type(&self) -> Result<Option<RemoteClass<'s>>, String> {
+        Ok(Self::new(self.source, self.slot(CLASS_DECLARING_TYPE)?))
+    }
+
+    /// Element class of an array class, the class itself otherwise
+    pub fn element_class(&self) -> Result<Option<RemoteClass<'s>>, String> {
+        Ok(Self::new(self.source, self.slot(CLASS_ELEMENT_CLASS)?))
+    }
+
+    /// The class in the cache, matched by image, namespace, name and enclosing class, so
+    /// nested classes sharing a name are told apart. Fails when several classes still match.
+    /// Generic instances such as ``List`1<int>`` match their definition.
+    pub fn cached<'c>(&self, cache: &'c impl Il2CppCacheTrait) -> Result<&'c Class, String> {
+        let (image, namespace, name) = (self.image_name()?, self.namespace()?, self.name()?);
+        let declaring_type = match self.declaring_type()? {
+            Some(declaring_type) => declaring_type.full_name()?,
+            None => String::new(),
+        };
+        let described = match (declaring_type.is_empty(), namespace.is_empty()) {
+            (false, _) => format!("{}.{}", declaring_type, name),
+            (true, true) => name.clone(),
+            (true, false) => format!("{}.{}", namespace, name),
+        };
+        let mut matches = cache
+            .get_assembly(&image)
+            .ok_or_else(|| format!("Assembly {} is not in the cache", image))?
+            .classes
+            .iter()
+            .filter(|c| {
+                c.name == name
+                    && c.namespace == namespace
+                    && *c.declaring_type.read() == declaring_type
+            });
+        let class = matches
+            .next()
+            .ok_or_else(|| format!("Class {} is not in the cache", described))?;
+        if matches.next().is_some() {
+            return Err(format!(
+                "Class {} matches several classes in {}",
+                described, image
+            ));
+        }
+        Ok(class)
+    }
+
+    /// Field `name` declared by this class or one of its parents
+    pub fn find_field(&self, cache: &impl Il2CppCacheTrait, name: &str) -> Result<Field, String> {
+        let mut class = Some(*self);
+        for _ in 0..MAX_PARENT_DEPTH {
+            let Some(current) = class else {
+                break;
+            };
+            let cached = current.cached(cache)?;
+            if let Some(field) = cached.get_field(name) {
+                // generic definitions carry no layout, their instances each have their own
+                if cached.is_generic_definition() && !field.static_field && field.offset <= 0 {
+                    return Err(format!(
+                        "{}.{} is declared by a generic type, its offset depends on the \
+                         instance, read it with `RemoteObject::read`",
+                        cached.full_name(),
+                        name
+                    ));
+                }
+                return Ok(field);
+            }
+            class = current.parent()?;
+        }
+        Err(format!("Field {} not found on {}", name, self.full_name()?))
+    }
+}
+
+/// A managed object in the target
+#[derive(Clone, Copy)]
+pub struct RemoteObject<'s> {
+    source: &'s dyn MemorySource,
+    pub address: u64,
+}
+
+impl<'s> RemoteObject<'s> {
+    /// `None` for a null reference
+    pub fn new(source: &'s dyn MemorySource, address: u64) -> Option<Self> {
+        (address != 0).then_some(Self { source, address })
+    }
+
+    /// Runtime class, from the object header
+    pub fn class(&self) -> Result<RemoteClass<'s>, String> {
+        RemoteClass::new(self.source, self.source.read_pointer(self.address)?)
+            .ok_or_else(|| format!("Object at 0x{:X} has no class", self.address))
+    }
+
+    /// `T` at `offset` from the start of the object, header included
+    pub fn read<T: Pod>(&self, offset: usize) -> Result<T, String> {
+        read_value(self.source, self.address + offset as u64)
+    }
+
+    /// Reference stored at `offset`
+    pub fn read_object(&self, offset: usize) -> Result<Option<RemoteObject<'s>>, String> {
+        let address = self.source.read_pointer(self.address + offset as u64)?;
+        Ok(Self::new(self.source, address))
+    }
+
+    fn instance_field(&self, cache: &impl Il2CppCacheTrait, name: &str) -> Result<Field, String> {
+        let field = self.class()?.find_field(cache, name)?;
+        if field.static_field {
+            return Err(format!(
+                "{} is static, static storage is not reachable through an instance",
+                name
+            ));
+        }
+        if field.offset < 0 {
+            return Err(format!("{} is thread static", name));
+        }
+        Ok(field)
+    }
+
+    /// Value of the instance field `name`, `T`'s size is checked against the field type
+    pub fn field<T: Pod>(&self, cache: &impl Il2CppCacheTrait, name: &str) -> Result<T, String> {
+        let field = self.instance_field(cache, name)?;
+        let size = std::mem::size_of::<T>();
+        if field.itype.size > 0 && field.itype.size as usize != size {
+            return Err(format!(
+                "{} is {} bytes, not {}",
+                name, field.itype.size, size
+            ));
+        }
+        self.read(field.offset as usize)
+    }
+
+    /// The object a reference field points to, `None` when it is null
+    pub fn object_field(
+        &self,
+        cache: &impl Il2CppCacheTrait,
+        name: &str,
+    ) -> Result<Option<RemoteObject<'s>>, String> {
+        let field = self.instance_field(cache, name)?;
+        if field.itype.value_type && !field.itype.byref {
+            return Err(format!("{} is a value type, not a reference", name));
+        }
+        self.read_object(field.offset as usize)
+    }
+
+    pub fn string_field(
+        &self,
+        cache: &impl Il2CppCacheTrait,
+        name: &str,
+    ) -> Result<Option<RemoteString<'s>>, String> {
+        Ok(self
+            .object_field(cache, name)?
+            .map(|object| RemoteString::from_object(&object)))
+    }
+
+    pub fn array_field(
+        &self,
+        cache: &impl Il2CppCacheTrait,
+        name: &str,
+    ) -> Result<Option<RemoteArray<'s>>, String> {
+        Ok(self
+            .object_field(cache, name)?
+            .map(|object| RemoteArray::from_object(&object)))
+    }
+}
+
+/// A `System.String` in the target: the object header, an `i32` length then UTF-16 data
+#[derive(Clone, Copy)]
+pub struct RemoteString<'s> {
+    source: &'s dyn MemorySource,
+    pub address: u64,
+}
+
+impl<'s> RemoteString<'s> {
+    pub fn new(source: &'s dyn MemorySource, address: u64) -> Option<Self> {
+        (address != 0).then_some(Self { source, address })
+    }
+
+    pub fn from_object(object: &RemoteObject<'s>) -> Self {
+        Self {
+            source: object.source,
+            address: object.address,
+        }
+    }
+
+    fn header_size(&self) -> u64 {
+        2 * self.source.pointer_size() as u64
+    }
+
+    /// Length in characters
+    pub fn len(&self) -> Result<usize, String> {
+        let len = self.source.read_i32(self.address + self.header_size())?;
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len < MAX_LENGTH)
+            .ok_or_else(|| format!("String at 0x{:X} has length {}", self.address, len))
+    }
+
+    pub fn is_empty(&self) -> Result<bool, String> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The UTF-16 code units of the string
+    pub fn to_utf16(&self) -> Result<Vec<u16>, String> {
+        let len = self.len()?;
+        let bytes = self
+            .source
+            .read_bytes(self.address + self.header_size() + 4, len * 2)?;
+        Ok(bytes
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect())
+    }
+
+    /// UTF-16 -> UTF-8, unpaired surrogates become U+FFFD
+    pub fn read_string(&self) -> Result<String, String> {
+        Ok(String::from_utf16_lossy(&self.to_utf16()?))
+    }
+}
+
+/// A managed array in the target: the object header, `bounds`, `max_length` then the elements
+#[derive(Clone, Copy)]
+pub struct RemoteArray<'s> {
+    source: &'s dyn MemorySource,
+    pub address: u64,
+}
+
+impl<'s> RemoteArray<'s> {
+    pub fn new(source: &'s dyn MemorySource, address: u64) -> Option<Self> {
+        (address != 0).then_some(Self { source, address })
+    }
+
+    pub fn from_object(object: &RemoteObject<'s>) -> Self {
+        Self {
+            source: object.source,
+            address: object.address,
+        }
+    }
+
+    fn pointer_size(&self) -> u64 {
+        self.source.pointer_size() as u64
+    }
+
+    fn data(&self) -> u64 {
+        self.address + 4 * self.pointer_size()
+    }
+
+    /// Number of elements, over all dimensions
+    pub fn len(&self) -> Result<usize, String> {
+        let len = self
+            .source
+            .read_pointer(self.address + 3 * self.pointer_size())?;
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len < MAX_LENGTH)
+            .ok_or_else(|| format!("Array at 0x{:X} has length {}", self.address, len))
+    }
+
+    pub fn is_empty(&self) -> Result<bool, String> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Element `index` of an array of `T`, `T` being the element storage (a value type
+    /// or a primitive, use `object` for references)
+    pub fn get<T: Pod>(&self, index: usize) -> Result<T, String> {
+        self.check_index(index)?;
+        read_value(
+            self.source,
+            self.data() + (index * std::mem::size_of::<T>()) as u64,
+        )
+    }
+
+    /// Element `index` of an array of references
+    pub fn object(&self, index: usize) -> Result<Option<RemoteObject<'s>>, String> {
+        self.check_index(index)?;
+        let address = self
+            .source
+            .read_pointer(self.data() + index as u64 * self.pointer_size())?;
+        Ok(RemoteObject::new(self.source, address))
+    }
+
+    /// Every element, read in one go
+    pub fn to_vec<T: Pod>(&self) -> Result<Vec<T>, String> {
+        let len = self.len()?;
+        let size = std::mem::size_of::<T>();
+        let bytes = self.source.read_bytes(self.data(), len * size)?;
+        Ok(bytes
+            .chunks_exact(size.max(1))
+            .take(len)
+            .map(|chunk| unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const T) })
+            .collect())
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        let len = self.len()?;
+        if index >= len {
+            return Err(format!(
+                "Index {} out of bounds for array of length {}",
+                index, len
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::assembly::Assembly;
+    use crate::il2cpp_cache::Cache;
+    use crate::il2cpp_dump::fixture::class;
+    use crate::il2cpp_remote::dump::MemoryDump;
+
+    const BASE: u64 = 0x1000;
+
+    // Target memory holding an image, a few C strings and `Il2CppClass` headers
+    struct Target {
+        memory: Vec<u8>,
+    }
+
+    impl Target {
+        fn new() -> Self {
+            Self {
+                memory: vec![0; 0x400],
+            }
+        }
+
+        fn pointer(&mut self, at: u64, value: u64) {
+            let at = (at - BASE) as usize;
+            self.memory[at..at + 8].copy_from_slice(&value.to_le_bytes());
+        }
+
+        fn string(&mut self, at: u64, value: &str) -> u64 {
+            let start = (at - BASE) as usize;
+            self.memory[start..start + value.len()].copy_from_slice(value.as_bytes());
+            at
+        }
+
+        fn class(&mut self, at: u64, namespace: u64, name: u64, declaring_type: u64) -> u64 {
+            self.pointer(at + CLASS_IMAGE * 8, BASE);
+            self.pointer(at + CLASS_NAME * 8, name);
+            self.pointer(at + CLASS_NAMESPACE * 8, namespace);
+            self.pointer(at + CLASS_DECLARING_TYPE * 8, declaring_type);
+            at
+        }
+
+        fn dump(self) -> MemoryDump {
+            let mut dump = MemoryDump::new(8);
+            dump.add_region(BASE, self.memory);
+            dump
+        }
+    }
+
+    fn nested(namespace: &str, name: &str, declaring_type: &str) -> Class {
+        let class = class(namespace, name, "System.Object", false);
+        *class.declaring_type.write() = declaring_type.to_string();
+        class
+    }
+
+    #[test]
+    fn nested_classes_are_matched_by_their_enclosing_class() {
+        let mut target = Target::new();
+        target.pointer(BASE, BASE + 0x10);
+        target.string(BASE + 0x10, "Assembly-CSharp.dll");
+        let game = target.string(BASE + 0x30, "Game");
+        let empty = target.string(BASE + 0x38, "");
+        let player = target.string(BASE + 0x40, "Player");
+        let enemy = target.string(BASE + 0x48, "Enemy");
+        let stats = target.string(BASE + 0x50, "Stats");
+        let player_class = target.class(BASE + 0x100, game, player, 0);
+        let enemy_class = target.class(BASE + 0x180, game, enemy, 0);
+        let player_stats = target.class(BASE + 0x200, empty, stats, player_class);
+        let enemy_stats = target.class(BASE + 0x280, empty, stats, enemy_class);
+        let dump = target.dump();
+
+        let mut assembly = Assembly::new(
+            std::ptr::null_mut(),
+            "Assembly-CSharp.dll".to_string(),
+            "Assembly-CSharp.dll".to_string(),
+        );
+        assembly
+            .classes
+            .push(class("Game", "Player", "System.Object", false));
+        assembly
+            .classes
+            .push(class("Game", "Enemy", "System.Object", false));
+        assembly.classes.push(nested("", "Stats", "Game.Player"));
+        assembly.classes.push(nested("", "Stats", "Game.Enemy"));
+        let mut cache = Cache::default();
+        cache.assemblies.push(assembly);
+
+        let remote = |address| RemoteClass::new(&dump, address).unwrap();
+        let cached = remote(player_stats).cached(&cache).unwrap();
+        assert_eq!(*cached.declaring_type.read(), "Game.Player");
+        let cached = remote(enemy_stats).cached(&cache).unwrap();
+        assert_eq!(*cached.declaring_type.read(), "Game.Enemy");
+        assert_eq!(remote(player_class).cached(&cache).unwrap().name, "Player");
+
+        // identical entries, e.g. from a model without enclosing classes, are ambiguous
+        *cache.assemblies[0].classes[3].declaring_type.write() = "Game.Player".to_string();
+        let error = remote(player_stats).cached(&cache).err().unwrap();
+        assert!(
+            error.contains("Game.Player.Stats matches several"),
+            "{}",
+            error
+        );
+        cache.assemblies[0].classes.truncate(1);
+        let error = remote(enemy_stats).cached(&cache).err().unwrap();
+        assert!(
+            error.contains("Game.Enemy.Stats is not in the cache"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,748 @@
+use crate::il2cpp::classes::class::ClassInner;
+use crate::il2cpp::classes::field::Field;
+use crate::il2cpp::classes::itype::{TypeInner, TypeKind};
+use crate::il2cpp::classes::method::Method;
+use crate::il2cpp_cache::Cache;
+use crate::il2cpp_snapshot::method_rva;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::io::Write;
+
+/// Minimum shape similarity for an unmatched class pair to be reported as a rename
+pub const RENAME_THRESHOLD: f32 = 0.8;
+
+/// Changes between two versions of a game's metadata, usually two loaded snapshots.
+/// Classes are paired by assembly, enclosing class and full name first, and reported by
+/// qualified name (`Outer.Nested` for nested classes). The rest are paired by shape, field offsets with
+/// their types and method signatures with obfuscated type names left out, so renamed
+/// classes are still followed. Members are paired the same way within each class.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetadataDiff {
+    pub added_classes: Vec<String>,
+    pub removed_classes: Vec<String>,
+    pub renamed_classes: Vec<ClassRename>,
+    /// Member changes of the classes found in both versions
+    pub changed_classes: Vec<ClassDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassRename {
+    pub old: String,
+    pub new: String,
+    /// Share of fields and methods with the same shape, 0 to 1
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassDiff {
+    /// Qualified name in the new version
+    pub name: String,
+    pub changes: Vec<MemberChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum MemberChange {
+    FieldAdded {
+        name: String,
+        type_name: String,
+        offset: i32,
+    },
+    FieldRemoved {
+        name: String,
+        type_name: String,
+        offset: i32,
+    },
+    FieldRenamed {
+        old: String,
+        new: String,
+        offset: i32,
+    },
+    FieldOffset {
+        name: String,
+        old: i32,
+        new: i32,
+    },
+    FieldType {
+        name: String,
+        old: String,
+        new: String,
+    },
+    MethodAdded {
+        signature: String,
+        rva: Option<u64>,
+    },
+    MethodRemoved {
+        signature: String,
+    },
+    MethodRenamed {
+        old: String,
+        new: String,
+    },
+    MethodSignature {
+        old: String,
+        new: String,
+    },
+    MethodMoved {
+        signature: String,
+        old: Option<u64>,
+        new: Option<u64>,
+    },
+}
+
+impl MetadataDiff {
+    pub fn between(old: &Cache, new: &Cache) -> Self {
+        let old_base = old.module_base.unwrap_or(0);
+        let new_base = new.module_base.unwrap_or(0);
+        let mut old_classes = all_classes(old);
+        let mut new_classes = all_classes(new);
+
+        let mut pairs: Vec<(&ClassInner, &ClassInner)> =
+            pair_by(&mut old_classes, &mut new_classes, false, class_key)
+                .into_iter()
+                .map(|((_, a), (_, b))| (a, b))
+                .collect();
+        let mut diff = Self::default();
+        let old_classes: Vec<&ClassInner> = old_classes.into_iter().map(|(_, c)| c).collect();
+        let new_classes: Vec<&ClassInner> = new_classes.into_iter().map(|(_, c)| c).collect();
+        let renames = match_renames(&old_classes, &new_classes);
+        let renamed_old: HashSet<usize> = renames.iter().map(|(i, _, _)| *i).collect();
+        let renamed_new: HashSet<usize> = renames.iter().map(|(_, j, _)| *j).collect();
+        for (i, j, similarity) in renames {
+            diff.renamed_classes.push(ClassRename {
+                old: old_classes[i].qualified_name(),
+                new: new_classes[j].qualified_name(),
+                similarity,
+            });
+            pairs.push((old_classes[i], new_classes[j]));
+        }
+        diff.removed_classes = unpaired_names(&old_classes, &renamed_old);
+        diff.added_classes = unpaired_names(&new_classes, &renamed_new);
+
+        for (old_class, new_class) in pairs {
+            let mut changes = diff_fields(old_class, new_class);
+            changes.extend(diff_methods(old_class, new_class, old_base, new_base));
+            if !changes.is_empty() {
+                diff.changed_classes.push(ClassDiff {
+                    name: new_class.qualified_name(),
+                    changes,
+                });
+            }
+        }
+        diff.added_classes.sort();
+        diff.removed_classes.sort();
+        diff.changed_classes.sort_by(|a, b| a.name.cmp(&b.name));
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_classes.is_empty()
+            && self.removed_classes.is_empty()
+            && self.renamed_classes.is_empty()
+            && self.changed_classes.is_empty()
+    }
+
+    pub fn write_json(&self, writer: &mut impl Write) -> Result<(), String> {
+        serde_json::to_writer_pretty(writer, self).map_err(|e| e.to_string())
+    }
+}
+
+impl Display for MetadataDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for name in &self.added_classes {
+            writeln!(f, "+ class {}", name)?;
+        }
+        for name in &self.removed_classes {
+            writeln!(f, "- class {}", name)?;
+        }
+        for rename in &self.renamed_classes {
+            writeln!(
+                f,
+                "~ class {} -> {} ({:.0}% similar)",
+                rename.old,
+                rename.new,
+                rename.similarity * 100.0
+            )?;
+        }
+        for class in &self.changed_classes {
+            writeln!(f, "{}", class.name)?;
+            for change in &class.changes {
+                writeln!(f, "    {}", change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for MemberChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FieldAdded {
+                name,
+                type_name,
+                offset,
+            } => write!(f, "+ field {} {} @ 0x{:X}", type_name, name, offset),
+            Self::FieldRemoved {
+                name,
+                type_name,
+                offset,
+            } => write!(f, "- field {} {} @ 0x{:X}", type_name, name, offset),
+            Self::FieldRenamed { old, new, offset } => {
+                write!(f, "~ field {} -> {} @ 0x{:X}", old, new, offset)
+            }
+            Self::FieldOffset { name, old, new } => {
+                write!(f, "  field {} 0x{:X} -> 0x{:X}", name, old, new)
+            }
+            Self::FieldType { name, old, new } => {
+                write!(f, "  field {} type {} -> {}", name, old, new)
+            }
+            Self::MethodAdded { signature, rva } => {
+                write!(f, "+ method {} @ {}", signature, rva_text(*rva))
+            }
+            Self::MethodRemoved { signature } => write!(f, "- method {}", signature),
+            Self::MethodRenamed { old, new } => write!(f, "~ method {} -> {}", old, new),
+            Self::MethodSignature { old, new } => write!(f, "  method {} -> {}", old, new),
+            Self::MethodMoved {
+                signature,
+                old,
+                new,
+            } => write!(
+                f,
+                "  method {} {} -> {}",
+                signature,
+                rva_text(*old),
+                rva_text(*new)
+            ),
+        }
+    }
+}
+
+fn rva_text(rva: Option<u64>) -> String {
+    match rva {
+        Some(rva) => format!("0x{:X}", rva),
+        None => "-".to_string(),
+    }
+}
+
+fn unpaired_names(classes: &[&ClassInner], renamed: &HashSet<usize>) -> Vec<String> {
+    (0..classes.len())
+        .filter(|i| !renamed.contains(i))
+        .map(|i| classes[i].qualified_name())
+        .collect()
+}
+
+// Classes with the name of their assembly
+fn all_classes(cache: &Cache) -> Vec<(&str, &ClassInner)> {
+    cache
+        .assemblies
+        .iter()
+        .flat_map(|a| a.classes.iter().map(|c| (a.name.as_str(), c.as_ref())))
+        .collect()
+}
+
+// nested classes like `<>c` repeat across enclosing classes, full names alone collide
+fn class_key((assembly, class): &(&str, &ClassInner)) -> (String, String, String) {
+    (
+        assembly.to_string(),
+        class.declaring_type.read().clone(),
+        class.full_name(),
+    )
+}
+
+/// Remove and return the items of `old` and `new` with equal keys, in order for repeated keys.
+/// With `unique` only keys occurring once on each side are paired.
+fn pair_by<T: Clone, K: Hash + Eq>(
+    old: &mut Vec<T>,
+    new: &mut Vec<T>,
+    unique: bool,
+    key: impl Fn(&T) -> K,
+) -> Vec<(T, T)> {
+    let mut new_by_key: HashMap<K, Vec<usize>> = HashMap::new();
+    for (i, item) in new.iter().enumerate() {
+        new_by_key.entry(key(item)).or_default().push(i);
+    }
+    let mut old_counts: HashMap<K, usize> = HashMap::new();
+    for item in old.iter() {
+        *old_counts.entry(key(item)).or_default() += 1;
+    }
+
+    let mut pairs = Vec::new();
+    let mut taken = HashSet::new();
+    let mut unpaired = Vec::new();
+    for item in old.drain(..) {
+        let k = key(&item);
+        let candidates = new_by_key.get_mut(&k).filter(|c| !c.is_empty());
+        match candidates {
+            Some(c) if !unique || (c.len() == 1 && old_counts[&k] == 1) => {
+                let index = c.remove(0);
+                taken.insert(index);
+                pairs.push((item, new[index].clone()));
+            }
+            _ => unpaired.push(item),
+        }
+    }
+    *old = unpaired;
+    let mut index = 0;
+    new.retain(|_| {
+        index += 1;
+        !taken.contains(&(index - 1))
+    });
+    pairs
+}
+
+/// Greedily pair unmatched classes by shape, most similar first, as indices into `old`/`new`
+fn match_renames(old: &[&ClassInner], new: &[&ClassInner]) -> Vec<(usize, usize, f32)> {
+    let old_shapes: Vec<Shape> = old.iter().map(|c| Shape::of(c)).collect();
+    let new_shapes: Vec<Shape> = new.iter().map(|c| Shape::of(c)).collect();
+
+    // only classes of the same kind and a close member count can be similar enough
+    let mut buckets: HashMap<(bool, bool, bool), BTreeMap<usize, Vec<usize>>> = HashMap::new();
+    for (j, b) in new_shapes.iter().enumerate() {
+        let by_total = buckets.entry(b.kind).or_default();
+        by_total.entry(b.total).or_default().push(j);
+    }
+    let mut candidates = Vec::new();
+    for (i, a) in old_shapes.iter().enumerate() {
+        let Some(by_total) = buckets.get(&a.kind) else {
+            continue;
+        };
+        let low = (a.total as f32 * RENAME_THRESHOLD).floor() as usize;
+        let high = (a.total as f32 / RENAME_THRESHOLD).ceil() as usize;
+        for j in by_total.range(low..=high).flat_map(|(_, js)| js) {
+            let similarity = a.similarity(&new_shapes[*j]);
+            if similarity >= RENAME_THRESHOLD {
+                candidates.push((similarity, i, *j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+
+    let mut used_old = HashSet::new();
+    let mut used_new = HashSet::new();
+    let mut renames = Vec::new();
+    for (similarity, i, j) in candidates {
+        if !used_old.contains(&i) && !used_new.contains(&j) {
+            used_old.insert(i);
+            used_new.insert(j);
+            renames.push((i, j, similarity));
+        }
+    }
+    renames
+}
+
+// Name independent summary of a class, members as counted shape keys
+struct Shape {
+    kind: (bool, bool, bool),
+    members: HashMap<String, usize>,
+    total: usize,
+}
+
+impl Shape {
+    fn of(class: &ClassInner) -> Self {
+        let mut members = HashMap::new();
+        let mut total = 0;
+        for field in class.fields.read().iter() {
+            *members.entry(field_shape(field)).or_default() += 1;
+            total += 1;
+        }
+        for method in class.methods.read().iter() {
+            *members.entry(method_shape(method)).or_default() += 1;
+            total += 1;
+        }
+        Self {
+            kind: (class.value_type, class.enum_type, class.is_interface()),
+            members,
+            total,
+        }
+    }
+
+    fn similarity(&self, other: &Shape) -> f32 {
+        // empty classes look alike whatever they are
+        if self.kind != other.kind || self.total == 0 || other.total == 0 {
+            return 0.0;
+        }
+        let (min, max) = (self.total.min(other.total), self.total.max(other.total));
+        if (min as f32) < max as f32 * RENAME_THRESHOLD {
+            return 0.0;
+        }
+        let common: usize = self
+            .members
+            .iter()
+            .map(|(key, count)| (*count).min(other.members.get(key).copied().unwrap_or(0)))
+            .sum();
+        common as f32 / max as f32
+    }
+}
+
+/// Type name when it survives obfuscation (primitives and `System.*`), the kind otherwise
+fn type_token(itype: &TypeInner) -> String {
+    let byref = if itype.byref { "&" } else { "" };
+    let stable = itype.kind.is_primitive()
+        || matches!(
+            itype.kind,
+            TypeKind::Void | TypeKind::String | TypeKind::Object
+        )
+        || itype.name.starts_with("System.");
+    if stable {
+        format!("{}{}", itype.name.trim_end_matches('&'), byref)
+    } else {
+        format!("<{:?}>{}", itype.kind, byref)
+    }
+}
+
+fn field_shape(field: &Field) -> String {
+    format!(
+        "field {} {} {}",
+        field.static_field,
+        field.offset,
+        type_token(&field.itype)
+    )
+}
+
+fn method_shape(method: &Method) -> String {
+    let args: Vec<String> = method
+        .args
+        .read()
+        .iter()
+        .map(|a| type_token(&a.itype))
+        .collect();
+    format!(
+        "method {} {} ({})",
+        method.is_static,
+        type_token(&method.return_type),
+        args.join(", ")
+    )
+}
+
+// `System.Void TakeDamage(System.Single)`
+fn signature(method: &Method) -> String {
+    let args: Vec<String> = method
+        .args
+        .read()
+        .iter()
+        .map(|a| a.itype.name.clone())
+        .collect();
+    format!(
+        "{}{} {}({})",
+        if method.is_static { "static " } else { "" },
+        method.return_type.name,
+        method.name,
+        args.join(", ")
+    )
+}
+
+// overloads share a name, the parameter types tell them apart
+fn overload_key(method: &Method) -> String {
+    let args: Vec<String> = method
+        .args
+        .read()
+        .iter()
+        .map(|a| a.itype.name.clone())
+        .collect();
+    format!("{}({})", method.name, args.join(", "))
+}
+
+fn diff_fields(old_class: &ClassInner, new_class: &ClassInner) -> Vec<MemberChange> {
+    let mut old = old_class.fields.read().clone();
+    let mut new = new_class.fields.read().clone();
+    let mut pairs = pair_by(&mut old, &mut new, false, |f| f.name.clone());
+    pairs.extend(pair_by(&mut old, &mut new, true, field_shape));
+
+    let mut changes = Vec::new();
+    for (a, b) in pairs {
+        if a.name != b.name {
+            changes.push(MemberChange::FieldRenamed {
+                old: a.name.clone(),
+                new: b.name.clone(),
+                offset: b.offset,
+            });
+        }
+        if a.offset != b.offset {
+            changes.push(MemberChange::FieldOffset {
+                name: b.name.clone(),
+                old: a.offset,
+                new: b.offset,
+            });
+        }
+        if a.itype.name != b.itype.name {
+            changes.push(MemberChange::FieldType {
+                name: b.name.clone(),
+                old: a.itype.name.clone(),
+                new: b.itype.name.clone(),
+            });
+        }
+    }
+    for field in old {
+        changes.push(MemberChange::FieldRemoved {
+            name: field.name.clone(),
+            type_name: field.itype.name.clone(),
+            offset: field.offset,
+        });
+    }
+    for field in new {
+        changes.push(MemberChange::FieldAdded {
+            name: field.name.clone(),
+            type_name: field.itype.name.clone(),
+            offset: field.offset,
+        });
+    }
+    changes
+}
+
+fn diff_methods(
+    old_class: &ClassInner,
+    new_class: &ClassInner,
+    old_base: usize,
+    new_base: usize,
+) -> Vec<MemberChange> {
+    let mut old = old_class.methods.read().clone();
+    let mut new = new_class.methods.read().clone();
+    let mut pairs = pair_by(&mut old, &mut new, false, overload_key);
+    pairs.extend(pair_by(&mut old, &mut new, true, |m| m.name.clone()));
+    pairs.extend(pair_by(&mut old, &mut new, true, method_shape));
+
+    let mut changes = Vec::new();
+    for (a, b) in pairs {
+        let (old_signature, new_signature) = (signature(&a), signature(&b));
+        if a.name != b.name {
+            changes.push(MemberChange::MethodRenamed {
+                old: old_signature,
+                new: new_signature.clone(),
+            });
+        } else if old_signature != new_signature {
+            changes.push(MemberChange::MethodSignature {
+                old: old_signature,
+                new: new_signature.clone(),
+            });
+        }
+        let (old_rva, new_rva) = (method_rva(&a, old_base), method_rva(&b, new_base));
+        if old_rva != new_rva {
+            changes.push(MemberChange::MethodMoved {
+                signature: new_signature,
+                old: old_rva,
+                new: new_rva,
+            });
+        }
+    }
+    for method in old {
+        changes.push(MemberChange::MethodRemoved {
+            signature: signature(&method),
+        });
+    }
+    for method in new {
+        changes.push(MemberChange::MethodAdded {
+            signature: signature(&method),
+            rva: method_rva(&method, new_base),
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::assembly::Assembly;
+    use crate::il2cpp::classes::class::Class;
+    use crate::il2cpp_dump::fixture::{add_field, add_method, class, itype};
+
+    fn int() -> crate::il2cpp::classes::itype::Type {
+        itype("System.Int32", TypeKind::I4, 4, true)
+    }
+
+    // obfuscated type names differ between builds, only their kind is compared
+    fn enemy(name: &str, target: &str) -> Class {
+        let enemy = class("", name, "UnityEngine.MonoBehaviour", false);
+        add_field(&enemy, "hp", int(), 0x18, 0);
+        add_field(
+            &enemy,
+            "target",
+            itype(target, TypeKind::Class, 8, false),
+            0x20,
+            0,
+        );
+        add_method(
+            &enemy,
+            "Hit",
+            itype("System.Void", TypeKind::Void, 0, false),
+            &[("damage", int())],
+            0,
+        );
+        add_method(
+            &enemy,
+            "get_Target",
+            itype(target, TypeKind::Class, 8, false),
+            &[],
+            0,
+        );
+        enemy
+    }
+
+    #[test]
+    fn pair_by_keeps_order_of_repeated_keys() {
+        let mut old = vec![(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd')];
+        let mut new = vec![(1, 'x'), (4, 'y'), (1, 'z')];
+        let pairs = pair_by(&mut old, &mut new, false, |item| item.0);
+        assert_eq!(pairs, vec![((1, 'a'), (1, 'x')), ((1, 'c'), (1, 'z'))]);
+        assert_eq!(old, vec![(2, 'b'), (3, 'd')]);
+        assert_eq!(new, vec![(4, 'y')]);
+    }
+
+    #[test]
+    fn pair_by_unique_skips_ambiguous_keys() {
+        let mut old = vec![(1, 'a'), (1, 'b'), (2, 'c')];
+        let mut new = vec![(1, 'x'), (2, 'y'), (2, 'z')];
+        let pairs = pair_by(&mut old, &mut new, true, |item| item.0);
+        assert!(pairs.is_empty());
+        assert_eq!(old.len(), 3);
+        assert_eq!(new.len(), 3);
+
+        let mut old = vec![(1, 'a'), (2, 'b')];
+        let mut new = vec![(2, 'x'), (1, 'y')];
+        let pairs = pair_by(&mut old, &mut new, true, |item| item.0);
+        assert_eq!(pairs, vec![((1, 'a'), (1, 'y')), ((2, 'b'), (2, 'x'))]);
+    }
+
+    #[test]
+    fn renamed_classes_match_by_shape() {
+        let (old, new) = (enemy("ABCDEF", "GHIJ"), enemy("KLMNOP", "QRST"));
+        let other = class("", "Other", "System.Object", false);
+        add_field(&other, "hp", int(), 0x10, 0);
+        let renames = match_renames(&[&other, &old], &[&new]);
+        assert_eq!(renames, vec![(1, 0, 1.0)]);
+    }
+
+    #[test]
+    fn renames_span_member_counts_within_the_threshold() {
+        let old = enemy("ABCDEF", "GHIJ");
+        add_field(&old, "armor", int(), 0x28, 0);
+        let new = enemy("KLMNOP", "QRST");
+        let small = class("", "Small", "UnityEngine.MonoBehaviour", false);
+        add_field(&small, "hp", int(), 0x18, 0);
+        let renames = match_renames(&[&old], &[&small, &new]);
+        assert_eq!(renames, vec![(0, 1, 0.8)]);
+    }
+
+    fn nested(outer: &str, offset: i32) -> Class {
+        let nested = class("", "<>c", "System.Object", false);
+        *nested.declaring_type.write() = outer.to_string();
+        add_field(&nested, "state", int(), offset, 0);
+        nested
+    }
+
+    fn cache(classes: Vec<Class>) -> Cache {
+        let mut assembly = Assembly::new(
+            std::ptr::null_mut(),
+            "Assembly-CSharp".to_string(),
+            "Assembly-CSharp.dll".to_string(),
+        );
+        assembly.classes = classes;
+        Cache {
+            assemblies: vec![assembly],
+            module_base: Some(0),
+            binary: None,
+        }
+    }
+
+    #[test]
+    fn nested_classes_pair_by_declaring_type() {
+        let old = cache(vec![
+            nested("Game.Outer1", 0x10),
+            nested("Game.Outer2", 0x10),
+        ]);
+        let new = cache(vec![
+            nested("Game.Outer2", 0x10),
+            nested("Game.Outer1", 0x18),
+        ]);
+        let diff = MetadataDiff::between(&old, &new);
+        assert!(diff.added_classes.is_empty() && diff.removed_classes.is_empty());
+        assert!(diff.renamed_classes.is_empty());
+        assert_eq!(diff.changed_classes.len(), 1);
+        assert_eq!(diff.changed_classes[0].name, "Game.Outer1.<>c");
+        assert_eq!(
+            diff.changed_classes[0].changes,
+            vec![MemberChange::FieldOffset {
+                name: "state".to_string(),
+                old: 0x10,
+                new: 0x18
+            }]
+        );
+
+        let new = cache(vec![nested("Game.Outer1", 0x10)]);
+        let diff = MetadataDiff::between(&old, &new);
+        assert_eq!(diff.removed_classes, vec!["Game.Outer2.<>c"]);
+    }
+
+    #[test]
+    fn dissimilar_classes_are_not_renames() {
+        let old = enemy("ABCDEF", "GHIJ");
+        let new = enemy("KLMNOP", "QRST");
+        add_field(
+            &new,
+            "speed",
+            itype("System.Single", TypeKind::R4, 4, true),
+            0x28,
+            0,
+        );
+        add_field(&new, "armor", int(), 0x2C, 0);
+        assert!(match_renames(&[&old], &[&new]).is_empty());
+        // kinds must agree even when the members do
+        let value = class("", "Value", "System.ValueType", true);
+        add_field(&value, "hp", int(), 0x18, 0);
+        let reference = class("", "Reference", "System.Object", false);
+        add_field(&reference, "hp", int(), 0x18, 0);
+        assert!(match_renames(&[&value], &[&reference]).is_empty());
+    }
+
+    #[test]
+    fn member_changes_are_reported() {
+        let old = enemy("Enemy", "Player");
+        let new = class("", "Enemy", "UnityEngine.MonoBehaviour", false);
+        add_field(&new, "health", int(), 0x18, 0);
+        add_field(
+            &new,
+            "target",
+            itype("Player", TypeKind::Class, 8, false),
+            0x28,
+            0,
+        );
+        add_method(
+            &new,
+            "Hit",
+            itype("System.Void", TypeKind::Void, 0, false),
+            &[("damage", itype("System.Single", TypeKind::R4, 4, true))],
+            0,
+        );
+
+        let changes = diff_fields(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                MemberChange::FieldOffset {
+                    name: "target".to_string(),
+                    old: 0x20,
+                    new: 0x28
+                },
+                MemberChange::FieldRenamed {
+                    old: "hp".to_string(),
+                    new: "health".to_string(),
+                    offset: 0x18
+                },
+            ]
+        );
+        let changes = diff_methods(&old, &new, 0, 0);
+        assert_eq!(
+            changes,
+            vec![
+                MemberChange::MethodSignature {
+                    old: "System.Void Hit(System.Int32)".to_string(),
+                    new: "System.Void Hit(System.Single)".to_string()
+                },
+                MemberChange::MethodRemoved {
+                    signature: "Player get_Target()".to_string()
+                },
+            ]
+        );
+    }
+}
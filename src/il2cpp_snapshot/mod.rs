@@ -0,0 +1,407 @@
+// Offline snapshots of the metadata cache, saved as JSON or a compact binary format
+pub mod diff;
+
+use crate::il2cpp::classes::{
+    arg::ArgInner,
+    assembly::Assembly,
+    class::ClassInner,
+    field::FieldInner,
+    itype::{Type, TypeInner, TypeKind},
+    method::MethodInner,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+// Leads binary snapshots, JSON ones start with `{`
+const BINARY_MAGIC: &[u8; 8] = b"IL2CPPRS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Pretty printed, for reading and diffing by hand
+    Json,
+    /// bincode behind a magic header, types stored once and referenced by index
+    Binary,
+}
+
+/// Serializable form of a `Cache`. Pointers into the il2cpp runtime are dropped, method
+/// pointers are kept as RVAs into GameAssembly so snapshots compare across runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    /// Every distinct type, referenced by index from fields, methods and args
+    pub types: Vec<SnapshotType>,
+    pub assemblies: Vec<SnapshotAssembly>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SnapshotType {
+    pub name: String,
+    pub size: i64,
+    pub kind: TypeKind,
+    pub byref: bool,
+    pub value_type: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAssembly {
+    pub name: String,
+    pub file: String,
+    pub classes: Vec<SnapshotClass>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotClass {
+    pub name: String,
+    pub namespace: String,
+    pub parent: String,
+    /// Enclosing class of a nested class, empty otherwise
+    pub declaring_type: String,
+    pub flags: i32,
+    pub value_type: bool,
+    pub enum_type: bool,
+    pub fields: Vec<SnapshotField>,
+    pub methods: Vec<SnapshotMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotField {
+    pub name: String,
+    pub itype: u32,
+    pub offset: i32,
+    pub flags: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMethod {
+    pub name: String,
+    pub return_type: u32,
+    pub flags: i32,
+    pub is_static: bool,
+    /// `None` for methods without native code or outside the module
+    pub rva: Option<u64>,
+    pub args: Vec<SnapshotArg>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotArg {
+    pub name: String,
+    pub itype: u32,
+}
+
+impl Snapshot {
+    /// Capture `assemblies`, method pointers are made relative to `module_base`
+    pub fn from_assemblies(assemblies: &[Assembly], module_base: usize) -> Self {
+        let mut types = TypeTable::default();
+        let assemblies = assemblies
+            .iter()
+            .map(|assembly| SnapshotAssembly {
+                name: assembly.name.clone(),
+                file: assembly.file.clone(),
+                classes: assembly
+                    .classes
+                    .iter()
+                    .map(|class| snapshot_class(class, module_base, &mut types))
+                    .collect(),
+            })
+            .collect();
+        Self {
+            version: SNAPSHOT_VERSION,
+            types: types.types,
+            assemblies,
+        }
+    }
+
+    /// Rebuild the metadata graph. Runtime handles and method pointers are null, the saved
+    /// RVAs go to `code_address`, so the result is for reading and dumping, not for invoking.
+    pub fn to_assemblies(&self) -> Result<Vec<Assembly>, String> {
+        let types: Vec<Type> = self
+            .types
+            .iter()
+            .map(|t| {
+                TypeInner::new(
+                    std::ptr::null_mut(),
+                    t.name.clone(),
+                    t.size as isize,
+                    t.kind,
+                    t.byref,
+                    t.value_type,
+                )
+            })
+            .collect();
+        let get_type = |index: u32| {
+            types
+                .get(index as usize)
+                .cloned()
+                .ok_or(format!("type index {} out of range", index))
+        };
+
+        let mut assemblies = Vec::new();
+        for snapshot_assembly in &self.assemblies {
+            let mut assembly = Assembly::new(
+                std::ptr::null_mut(),
+                snapshot_assembly.name.clone(),
+                snapshot_assembly.file.clone(),
+            );
+            for snapshot_class in &snapshot_assembly.classes {
+                let class = ClassInner::new(
+                    std::ptr::null_mut(),
+                    snapshot_class.name.clone(),
+                    snapshot_class.parent.clone(),
+                    snapshot_class.namespace.clone(),
+                    snapshot_class.flags,
+                    snapshot_class.value_type,
+                    snapshot_class.enum_type,
+                );
+                *class.declaring_type.write() = snapshot_class.declaring_type.clone();
+                for field in &snapshot_class.fields {
+                    class.fields.write().push(FieldInner::new(
+                        std::ptr::null_mut(),
+                        field.name.clone(),
+                        get_type(field.itype)?,
+                        Arc::downgrade(&class),
+                        field.offset,
+                        field.flags,
+                    ));
+                }
+                for method in &snapshot_class.methods {
+                    let mut args = Vec::new();
+                    for arg in &method.args {
+                        args.push(ArgInner::new(arg.name.clone(), get_type(arg.itype)?));
+                    }
+                    class.methods.write().push(MethodInner::new_offline(
+                        std::ptr::null_mut(),
+                        method.name.clone(),
+                        Arc::downgrade(&class),
+                        get_type(method.return_type)?,
+                        method.flags,
+                        method.is_static,
+                        method.rva.unwrap_or(0) as usize,
+                        RwLock::new(args),
+                    ));
+                }
+                assembly.classes.push(class);
+            }
+            assemblies.push(assembly);
+        }
+        Ok(assemblies)
+    }
+
+    pub fn write(&self, writer: &mut impl Write, format: SnapshotFormat) -> Result<(), String> {
+        match format {
+            SnapshotFormat::Json => {
+                serde_json::to_writer_pretty(writer, self).map_err(|e| e.to_string())
+            }
+            SnapshotFormat::Binary => {
+                writer.write_all(BINARY_MAGIC).map_err(|e| e.to_string())?;
+                bincode::serialize_into(writer, self).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Read a snapshot in either format, told apart by the binary magic
+    pub fn read(reader: &mut impl Read) -> Result<Self, String> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).map_err(|e| e.to_string())?;
+        let snapshot: Self = match data.strip_prefix(BINARY_MAGIC.as_slice()) {
+            Some(body) => bincode::deserialize(body).map_err(|e| e.to_string())?,
+            None => serde_json::from_slice(&data).map_err(|e| e.to_string())?,
+        };
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "unsupported snapshot version {}, expected {}",
+                snapshot.version, SNAPSHOT_VERSION
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>, format: SnapshotFormat) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut writer = BufWriter::new(file);
+        self.write(&mut writer, format)?;
+        writer.flush().map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        Self::read(&mut BufReader::new(file))
+    }
+}
+
+/// Method pointer relative to `module_base`, `None` when null or below the module
+pub(crate) fn method_rva(method: &MethodInner, module_base: usize) -> Option<u64> {
+    let va = method.code_address;
+    (va != 0 && va >= module_base).then(|| (va - module_base) as u64)
+}
+
+// Interns types so each distinct one is stored once
+#[derive(Default)]
+struct TypeTable {
+    types: Vec<SnapshotType>,
+    indices: HashMap<SnapshotType, u32>,
+}
+
+impl TypeTable {
+    fn intern(&mut self, itype: &TypeInner) -> u32 {
+        let key = SnapshotType {
+            name: itype.name.clone(),
+            size: itype.size as i64,
+            kind: itype.kind,
+            byref: itype.byref,
+            value_type: itype.value_type,
+        };
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+        let index = self.types.len() as u32;
+        self.types.push(key.clone());
+        self.indices.insert(key, index);
+        index
+    }
+}
+
+fn snapshot_class(class: &ClassInner, module_base: usize, types: &mut TypeTable) -> SnapshotClass {
+    SnapshotClass {
+        name: class.name.clone(),
+        namespace: class.namespace.clone(),
+        parent: class.parent.clone(),
+        declaring_type: class.declaring_type.read().clone(),
+        flags: class.flags,
+        value_type: class.value_type,
+        enum_type: class.enum_type,
+        fields: class
+            .fields
+            .read()
+            .iter()
+            .map(|field| SnapshotField {
+                name: field.name.clone(),
+                itype: types.intern(&field.itype),
+                offset: field.offset,
+                flags: field.flags,
+            })
+            .collect(),
+        methods: class
+            .methods
+            .read()
+            .iter()
+            .map(|method| SnapshotMethod {
+                name: method.name.clone(),
+                return_type: types.intern(&method.return_type),
+                flags: method.flags,
+                is_static: method.is_static,
+                rva: method_rva(method, module_base),
+                args: method
+                    .args
+                    .read()
+                    .iter()
+                    .map(|arg| SnapshotArg {
+                        name: arg.name.clone(),
+                        itype: types.intern(&arg.itype),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::il2cpp::classes::itype::TypeKind;
+    use crate::il2cpp_dump::fixture::{add_method_at, assemblies, class, itype};
+
+    const BASE: usize = 0x10000;
+
+    fn snapshot() -> Snapshot {
+        let mut assemblies = assemblies();
+        let void = itype("System.Void", TypeKind::Void, 0, false);
+        let inner = class("", "Inner", "System.Object", false);
+        *inner.declaring_type.write() = "Game.Player".to_string();
+        add_method_at(&inner, "Run", void.clone(), &[], 0, BASE + 0x1230);
+        // below the module, dropped from the snapshot
+        add_method_at(&inner, "Hook", void, &[], 0, 0x100);
+        assemblies[0].classes.push(inner);
+        Snapshot::from_assemblies(&assemblies, BASE)
+    }
+
+    fn round_trip(format: SnapshotFormat) {
+        let snapshot = snapshot();
+        let mut data = Vec::new();
+        snapshot.write(&mut data, format).unwrap();
+        match format {
+            SnapshotFormat::Json => assert_eq!(data[0], b'{'),
+            SnapshotFormat::Binary => assert!(data.starts_with(BINARY_MAGIC)),
+        }
+
+        let read = Snapshot::read(&mut data.as_slice()).unwrap();
+        let loaded = read.to_assemblies().unwrap();
+        let inner = loaded[0]
+            .classes
+            .iter()
+            .find(|c| c.name == "Inner")
+            .unwrap();
+        assert_eq!(*inner.declaring_type.read(), "Game.Player");
+        let methods = inner.methods.read();
+        assert_eq!(methods[0].code_address, 0x1230);
+        assert!(methods[0].function.is_null());
+        assert_eq!(methods[1].code_address, 0);
+
+        // the loaded graph is based at 0, so a second capture keeps the same RVAs
+        let again = Snapshot::from_assemblies(&loaded, 0);
+        assert_eq!(
+            serde_json::to_value(&again).unwrap(),
+            serde_json::to_value(&snapshot).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(SnapshotFormat::Json);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        round_trip(SnapshotFormat::Binary);
+    }
+
+    #[test]
+    fn loaded_methods_are_not_callable() {
+        let loaded = snapshot().to_assemblies().unwrap();
+        let inner = loaded[0]
+            .classes
+            .iter()
+            .find(|c| c.name == "Inner")
+            .unwrap();
+        let run = inner.methods.read()[0].clone();
+        let error = run.thunk::<(), ()>().err().unwrap();
+        assert!(error.contains("loaded offline"));
+        let error = run.try_callable::<extern "C" fn()>().unwrap_err();
+        assert!(error.contains("0x1230"));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut snapshot = snapshot();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        for format in [SnapshotFormat::Json, SnapshotFormat::Binary] {
+            let mut data = Vec::new();
+            snapshot.write(&mut data, format).unwrap();
+            let error = Snapshot::read(&mut data.as_slice()).unwrap_err();
+            assert_eq!(
+                error,
+                format!(
+                    "unsupported snapshot version {}, expected {}",
+                    SNAPSHOT_VERSION + 1,
+                    SNAPSHOT_VERSION
+                )
+            );
+        }
+    }
+}
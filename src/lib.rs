@@ -1,4 +1,12 @@
 pub mod console;
 pub mod il2cpp;
 pub mod il2cpp_cache;
+pub mod il2cpp_dump;
+pub mod il2cpp_offline;
+pub mod il2cpp_remote;
+pub mod il2cpp_snapshot;
 pub mod prof;
+
+// used by the exported view macros
+#[doc(hidden)]
+pub use paste;
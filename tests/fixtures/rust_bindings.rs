@@ -0,0 +1,115 @@
+// Rust bindings generated by il2cpp_rs from live il2cpp metadata.
+// Offsets are only valid for the game build they were generated from.
+// Include this file as a module, e.g. `mod game;`
+#![allow(non_snake_case, non_camel_case_types, dead_code, unused_imports, clippy::all)]
+
+use il2cpp_rs::il2cpp::classes::il2cpp_view::Il2CppManagedClass;
+use il2cpp_rs::il2cpp::classes::object::ObjectInner;
+use il2cpp_rs::il2cpp::classes::string::UnityStringInner;
+use il2cpp_rs::il2cpp::classes::thunk::Thunk;
+use il2cpp_rs::il2cpp_cache::Il2CppCacheTrait;
+use il2cpp_rs::il2cpp_view;
+use std::ffi::c_void;
+
+pub mod global {
+    use super::*;
+
+    // Marker (value type)
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct Marker {
+    }
+}
+
+// namespace Game
+pub mod game {
+    use super::*;
+
+    // Game.Player : UnityEngine.MonoBehaviour
+    il2cpp_view! {
+        #[il2cpp(class = "Game.Player", assembly = "Assembly-CSharp.dll")]
+        pub struct Player {
+            pub obj: ObjectInner,
+            _pad_0x10: [u8; 0x8],
+            pub health: i32, // 0x18 System.Int32
+            pub type_: i32, // 0x1C System.Int32
+            pub name: *mut UnityStringInner, // 0x20 System.String
+            pub stats: [u8; 8], // 0x28 Game.Data.Stats
+            pub _Alive_k__BackingField: u8, // 0x30 System.Boolean
+        }
+    }
+
+    impl<'a> PlayerView<'a> {
+        pub fn get_health(&self) -> i32 {
+            self.as_ref().health
+        }
+        pub fn get_type_(&self) -> i32 {
+            self.as_ref().type_
+        }
+        pub fn get_name(&self) -> *mut UnityStringInner {
+            self.as_ref().name
+        }
+        pub fn get_stats(&self) -> [u8; 8] {
+            self.as_ref().stats
+        }
+        pub fn get__alive_k__backing_field(&self) -> bool {
+            self.as_ref()._Alive_k__BackingField != 0
+        }
+        pub fn get_static_count(cache: &impl Il2CppCacheTrait) -> Result<i32, String> {
+            Self::cached_field(cache, "count")?.get_static()
+        }
+        pub fn set_static_count(cache: &impl Il2CppCacheTrait, value: i32) -> Result<(), String> {
+            Self::cached_field(cache, "count")?.set_static(value)
+        }
+        pub fn get_static_max_health(cache: &impl Il2CppCacheTrait) -> Result<i32, String> {
+            Self::cached_field(cache, "MaxHealth")?.get_static()
+        }
+        pub fn thunk_take_damage(cache: &impl Il2CppCacheTrait) -> Result<Thunk<(i32,), ()>, String> {
+            Self::cached_method(cache, "TakeDamage", &["System.Int32"])?.thunk()
+        }
+        pub fn thunk_get_name(cache: &impl Il2CppCacheTrait) -> Result<Thunk<(), *mut UnityStringInner>, String> {
+            Self::cached_method(cache, "get_Name", &[])?.thunk()
+        }
+        // get_Stats: generic or value type signature, use MethodInner::invoke
+        pub fn thunk_revive(cache: &impl Il2CppCacheTrait) -> Result<Thunk<(u8,), u8>, String> {
+            Self::cached_method(cache, "Revive", &["System.Boolean"])?.thunk()
+        }
+        pub fn thunk_spawn(cache: &impl Il2CppCacheTrait) -> Result<Thunk<(*mut UnityStringInner,), ()>, String> {
+            Self::cached_method(cache, "Spawn", &["System.String"])?.thunk()
+        }
+    }
+}
+
+// namespace Game.Data
+pub mod game_data {
+    use super::*;
+
+    // Game.Data.Stats (value type)
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct Stats {
+        pub attack: f32, // 0x0 System.Single
+        pub defense: f32, // 0x4 System.Single
+    }
+}
+
+// namespace Game_Data
+pub mod game_data_1 {
+    use super::*;
+
+    // Game_Data.Config : System.Object
+    il2cpp_view! {
+        #[il2cpp(class = "Game_Data.Config", assembly = "Assembly-CSharp.dll")]
+        pub struct Config {
+            pub obj: ObjectInner,
+            pub volume: f32, // 0x10 System.Single
+        }
+    }
+
+    impl<'a> ConfigView<'a> {
+        pub fn get_volume(&self) -> f32 {
+            self.as_ref().volume
+        }
+    }
+}
+
@@ -0,0 +1,39 @@
+// The golden output of `write_rust_bindings` (kept in sync by its unit test) must compile
+// against the public API and lay structs out at the dumped offsets
+#![cfg(target_pointer_width = "64")]
+
+// generator output, kept byte for byte
+#[rustfmt::skip]
+#[path = "fixtures/rust_bindings.rs"]
+mod bindings;
+
+use bindings::{game, game_data, game_data_1, global};
+use il2cpp_rs::il2cpp::classes::il2cpp_view::Il2CppManagedClass;
+use std::mem::{offset_of, size_of};
+
+#[test]
+fn views_use_dumped_offsets() {
+    assert_eq!(offset_of!(game::PlayerInner, health), 0x18);
+    assert_eq!(offset_of!(game::PlayerInner, name), 0x20);
+    assert_eq!(offset_of!(game::PlayerInner, _Alive_k__BackingField), 0x30);
+    assert_eq!(offset_of!(game_data_1::ConfigInner, volume), 0x10);
+}
+
+#[test]
+fn value_types_are_rebased() {
+    assert_eq!(size_of::<game_data::Stats>(), 8);
+    assert_eq!(offset_of!(game_data::Stats, defense), 4);
+    assert_eq!(size_of::<global::Marker>(), 0);
+}
+
+#[test]
+fn views_name_their_class() {
+    assert_eq!(
+        <game::PlayerView as Il2CppManagedClass>::CLASS_NAME,
+        "Game.Player"
+    );
+    assert_eq!(
+        <game_data_1::ConfigView as Il2CppManagedClass>::ASSEMBLY,
+        "Assembly-CSharp.dll"
+    );
+}